@@ -0,0 +1,75 @@
+// Renders a few frames of particles + UI without a window, each in its own layer.
+//   cargo run -p daniengine --example headless [-- out.png]
+
+use daniengine::input::Input;
+use daniengine::particles::{EmitterConfig, ParticleSystem};
+use daniengine::render::canvas::{BlendMode, Canvas, Color};
+use daniengine::render::codec;
+use daniengine::render::image_canvas::ImageCanvas;
+use daniengine::render::target::{Layers, RenderTarget};
+use daniengine::ui::{Rect, Ui};
+
+fn main() -> anyhow::Result<()> {
+    let mut canvas = ImageCanvas::new(320, 180);
+
+    let mut frame_no = 0u32;
+    canvas.set_present_callback(move |frame, w, h| {
+        frame_no += 1;
+        let lit = frame.chunks_exact(4).filter(|p| p[0] > 12 || p[1] > 12 || p[2] > 16).count();
+        println!("frame {frame_no}: {w}x{h}, {lit} lit pixels");
+        Ok(())
+    });
+
+    let cfg = EmitterConfig {
+        count: 64,
+        speed_min: 80.0,
+        speed_max: 220.0,
+        spread_radians: std::f32::consts::FRAC_PI_2,
+        base_direction: -std::f32::consts::FRAC_PI_2,
+        life_min: 0.6,
+        life_max: 1.2,
+        size_min: 2.0,
+        size_max: 4.0,
+        start_color: Color(255, 160, 240, 255),
+        end_color: Color(180, 200, 255, 0),
+    };
+
+    let mut ps = ParticleSystem::new(1_000);
+    let input = Input::new();
+    let mut ui = Ui::new();
+
+    // Particles glow additively over the background; UI stays a plain alpha layer.
+    let mut layers = Layers::new();
+    let mut glow = RenderTarget::new(320, 180);
+    glow.blend = BlendMode::Additive;
+    layers.add("particles", glow);
+    layers.add("ui", RenderTarget::new(320, 180));
+
+    let dt = 1.0 / 60.0;
+    for _ in 0..10 {
+        ps.emit_burst([160.0, 120.0], cfg);
+        ps.update(dt);
+
+        layers.clear_all();
+        if let Some(t) = layers.get_mut("particles") {
+            t.clear(Color(0, 0, 0, 255));
+            ps.draw(t);
+        }
+        if let Some(t) = layers.get_mut("ui") {
+            ui.begin();
+            ui.label(t, Rect { x: 8.0, y: 8.0, w: 304.0, h: 18.0 }, "headless");
+            ui.button(&input, t, Rect { x: 8.0, y: 32.0, w: 110.0, h: 22.0 }, "Button");
+        }
+
+        canvas.clear(Color(12, 12, 16, 255));
+        layers.composite_onto(&mut canvas);
+        canvas.present().map_err(anyhow::Error::msg)?;
+    }
+
+    if let Some(path) = std::env::args().nth(1) {
+        codec::save_frame(&path, &canvas)?;
+        println!("saved {path}");
+    }
+
+    Ok(())
+}
@@ -1,15 +1,21 @@
+// Without `render-pixels` only the stub `main` below is built.
+#![cfg_attr(not(feature = "render-pixels"), allow(dead_code, unused_imports))]
+
 use std::time::Instant;
 
 use daniengine::prelude::*;
 use daniengine::render::canvas::{Canvas, Color, CanvasFloatExt};
 use daniengine::particles::{EmitterConfig, ParticleSystem};
 use daniengine::physics;
+use daniengine::camera::{Camera2D, CameraCanvas, CameraRig};
+use daniengine::render::capture::Capture;
+use daniengine::render::postfx::{Bloom, Crt, PostChain, Vignette};
 
 use daniengine::input::{Input, Key, Mods, MouseButton};
 use daniengine::ui::{Ui, Rect};
 
 #[cfg(feature = "render-pixels")]
-use daniengine::render::pixels_impl::PixelsCanvas;
+use daniengine::render::pixels_impl::{PixelsCanvas, ScaleMode};
 
 #[cfg(feature = "render-pixels")]
 use winit::{
@@ -22,11 +28,11 @@ fn main() -> anyhow::Result<()> {
     env_logger::init();
 
     // Window + canvas
-    let (mut canvas, event_loop, window) =
+    let (mut canvas, event_loop, _window) =
         PixelsCanvas::new(320, 180, 3, "DaniEngine • Particles")?;
 
     // App (wraps input/ui/state)
-    let mut app = App::new(&mut canvas);
+    let mut app = App::new();
 
     let mut last = Instant::now();
 
@@ -44,36 +50,16 @@ fn main() -> anyhow::Result<()> {
                         *control_flow = ControlFlow::Exit;
                     }
 
-                    // Convert physical cursor coords -> canvas coords, write into Input
+                    WindowEvent::Resized(_) | WindowEvent::ScaleFactorChanged { .. } => {
+                        if let Err(e) = canvas.handle_window_event(&event) {
+                            eprintln!("resize error: {e}");
+                        }
+                    }
+
+                    // Physical cursor coords -> canvas coords (letterbox-aware)
                     WindowEvent::CursorMoved { position, .. } => {
-                        let px = position.x as f32;
-                        let py = position.y as f32;
-
-                        // Physical window size
-                        let ws = window.inner_size();
-                        let ww = ws.width as f32;
-                        let wh = ws.height as f32;
-
-                        // Internal canvas size
-                        let (cw_i, ch_i) = canvas.size();
-                        let cw = cw_i as f32;
-                        let ch = ch_i as f32;
-
-                        // Scale & letterbox offset
-                        let scale = (ww / cw).min(wh / ch);
-                        let ox = (ww - cw * scale) * 0.5;
-                        let oy = (wh - ch * scale) * 0.5;
-
-                        // Transform, clamp into canvas bounds
-                        let cx = ((px - ox) / scale).clamp(0.0, cw - 1.0);
-                        let cy = ((py - oy) / scale).clamp(0.0, ch - 1.0);
-
-                        let new_pos = Vec2::new(cx, cy);
-                        app.input.mouse_delta = Vec2::new(
-                            new_pos.x - app.input.mouse_pos.x,
-                            new_pos.y - app.input.mouse_pos.y,
-                        );
-                        app.input.mouse_pos = new_pos;
+                        let pos = Vec2::new(position.x as f32, position.y as f32);
+                        app.input.set_mouse_pos(canvas.window_to_canvas(pos));
                     }
 
                     _ => {
@@ -100,6 +86,15 @@ fn main() -> anyhow::Result<()> {
                 app.ui_click_consumed = over_any_button && 
                     app.input.mouse_pressed(MouseButton::Left);
 
+                // Window scaling: F4 switches crisp integer / fill-the-window fractional
+                if app.input.action_just_pressed("toggle_scale_mode", Mods::empty()) {
+                    let mode = match canvas.scale_mode() {
+                        ScaleMode::Integer => ScaleMode::Fractional,
+                        ScaleMode::Fractional => ScaleMode::Integer,
+                    };
+                    if let Err(e) = canvas.set_scale_mode(mode) { eprintln!("scale mode error: {e}"); }
+                }
+
                 // Update
                 let should_quit = app.update(dt);
                 if should_quit {
@@ -116,10 +111,16 @@ fn main() -> anyhow::Result<()> {
                     return;
                 }
 
+                app.post.apply(&mut canvas);
+
+                match app.capture.capture_frame(&canvas, dt) {
+                    Ok(paths) => paths.iter().for_each(|p| println!("saved {}", p.display())),
+                    Err(e) => eprintln!("capture error: {e:#}"),
+                }
+
                 if let Err(e) = canvas.present() {
                     eprintln!("present error: {e}");
                     *control_flow = ControlFlow::Exit;
-                    return;
                 }
             }
 
@@ -130,6 +131,10 @@ fn main() -> anyhow::Result<()> {
 
 // ----------------- App -----------------
 
+// World size in world units; the camera maps it onto the canvas.
+const WORLD_W: f32 = 320.0;
+const WORLD_H: f32 = 180.0;
+
 struct App {
     // simulation
     ps: ParticleSystem,
@@ -153,6 +158,16 @@ struct App {
     // bouncing square (like playground)
     body: physics::Body,
 
+    // world view (wheel zooms, C recenters, V follows the square)
+    rig: CameraRig,
+    follow_body: bool,
+
+    // post-processing (F1 bloom, F2 CRT, F3 vignette)
+    post: PostChain,
+
+    // F12 screenshot, F11 GIF clip (Capture::gif_frames frames)
+    capture: Capture,
+
     // systems
     input: Input,
     ui: Ui,
@@ -160,7 +175,7 @@ struct App {
 }
 
 impl App {
-    fn new(_canvas: &mut PixelsCanvas) -> Self {
+    fn new() -> Self {
         let mut input = Input::new();
 
         // --- Actions (single place to bind keys) ---
@@ -179,6 +194,19 @@ impl App {
         input.bind_action("well_radius_up",    Input::chord(Key::RBracket, Mods::empty()));
         input.bind_action("well_strength_down",Input::chord(Key::Minus, Mods::empty()));
         input.bind_action("well_strength_up",  Input::chord(Key::Equals, Mods::empty()));
+        input.bind_action("reset_camera",      Input::chord(Key::C, Mods::empty()));
+        input.bind_action("toggle_follow",     Input::chord(Key::V, Mods::empty()));
+
+        input.bind_action("toggle_bloom",      Input::chord(Key::F1, Mods::empty()));
+        input.bind_action("toggle_crt",        Input::chord(Key::F2, Mods::empty()));
+        input.bind_action("toggle_vignette",   Input::chord(Key::F3, Mods::empty()));
+
+        input.bind_action("toggle_scale_mode", Input::chord(Key::F4, Mods::empty()));
+
+        input.bind_action("screenshot",        Input::chord(Key::F12, Mods::empty()));
+        input.bind_action("record_gif",        Input::chord(Key::F11, Mods::empty()));
+        let mut capture = Capture::new("captures", "particles");
+        capture.bind_actions(Some("screenshot"), Some("record_gif"));
 
         // --- Presets (same as your original) ---
         let burst_cfg = EmitterConfig {
@@ -248,14 +276,34 @@ impl App {
                 size: Vec2::new(18.0, 18.0),
             },
 
+            rig: {
+                let mut rig = CameraRig::new(Camera2D::new(IRect::new(0, 0, WORLD_W as i32, WORLD_H as i32)), 0xC0FFEE);
+                rig.bounds = Some(physics::Aabb { x: 0.0, y: 0.0, w: WORLD_W, h: WORLD_H });
+                rig.look_ahead = 0.25;
+                rig
+            },
+            follow_body: false,
+
+            post: {
+                let mut post = PostChain::new();
+                post.push(Bloom::new(150, 0.8, 3)).push(Crt::new(0.35, 0.08)).push(Vignette::new(0.6, 0.45));
+                post.set_enabled("crt", false);
+                post
+            },
+
+            capture,
+
             input,
             ui: Ui::new(),
             ui_click_consumed: false,
         }
     }
 
+    /// Cursor in world coordinates (Input::mouse_pos is in canvas pixels).
+    fn mouse_world(&self) -> Vec2 { self.rig.view().screen_to_world(self.input.mouse_pos) }
+
     // Returns all interactive button rects for current canvas size.
-    fn ui_button_rects(&self, canvas_w: f32, canvas_h: f32) -> Vec<daniengine::ui::Rect> {
+    fn ui_button_rects(&self, canvas_w: f32, _canvas_h: f32) -> Vec<daniengine::ui::Rect> {
         use daniengine::ui::Rect;
 
         // Keep these in sync with draw_ui()
@@ -342,7 +390,7 @@ impl App {
             println!("Gravity well: {}", if self.well_active { "ON" } else { "OFF" });
         }
         if self.input.action_just_pressed("move_well_to_mouse", Mods::empty()) {
-            self.well_pos = self.input.mouse_pos.into();
+            self.well_pos = self.mouse_world();
             println!("Well moved to mouse");
         }
         if self.input.action_just_pressed("well_radius_down", Mods::empty()) {
@@ -362,15 +410,44 @@ impl App {
             println!("Well strength: {:.0}", self.well_strength);
         }
 
+        self.capture.handle_input(&self.input);
+
+        for (action, effect) in [("toggle_bloom", "bloom"), ("toggle_crt", "crt"), ("toggle_vignette", "vignette")] {
+            if self.input.action_just_pressed(action, Mods::empty())
+                && let Some(on) = self.post.toggle(effect)
+            {
+                println!("{effect}: {}", if on { "ON" } else { "OFF" });
+            }
+        }
+
+        // --- Camera: wheel zooms around the cursor ---
+        if self.input.wheel_delta != 0.0 {
+            let anchor = self.mouse_world();
+            let cam = &mut self.rig.camera;
+            cam.zoom = (cam.zoom * 1.1f32.powf(self.input.wheel_delta)).clamp(0.25, 8.0);
+            let drift = self.mouse_world();
+            let cam = &mut self.rig.camera;
+            cam.position = Vec2::new(cam.position.x + anchor.x - drift.x, cam.position.y + anchor.y - drift.y);
+        }
+        if self.input.action_just_pressed("reset_camera", Mods::empty()) {
+            let viewport = self.rig.camera.viewport;
+            self.rig.camera = Camera2D::new(viewport);
+            self.follow_body = false;
+        }
+        if self.input.action_just_pressed("toggle_follow", Mods::empty()) {
+            self.follow_body = !self.follow_body;
+            println!("Camera follow: {}", if self.follow_body { "ON" } else { "OFF" });
+        }
+
         // --- Emitters (mouse) ---
         let mouse_left_down = self.input.mouse_pressed(MouseButton::Left);
         let block_this_frame = self.ui_click_consumed;
 
         if mouse_left_down && !block_this_frame {
+            let mouse = self.mouse_world();
             let mut cfg = self.active_cfg;
             // tiny x-based wiggle so it feels alive
-            cfg.base_direction = (-std::f32::consts::FRAC_PI_2) + 0.3 * 
-                ((self.input.mouse_pos.x / 50.0).sin());
+            cfg.base_direction = (-std::f32::consts::FRAC_PI_2) + 0.3 * ((mouse.x / 50.0).sin());
 
             // Reverse with Shift (either)
             let reverse = self.input.pressed(Key::LShift) || self.input.pressed(Key::RShift);
@@ -378,7 +455,7 @@ impl App {
                 cfg.base_direction += std::f32::consts::PI;
             }
 
-            self.ps.emit_burst([self.input.mouse_pos.x, self.input.mouse_pos.y], cfg);
+            self.ps.emit_burst([mouse.x, mouse.y], cfg);
         }
 
         if self.fountain {
@@ -395,9 +472,7 @@ impl App {
                 cfg.base_direction += std::f32::consts::PI;
             }
 
-            // We'll query canvas size in render; here just pick a reasonable 320x180 default
-            // (the exact position isn't critical; visually updated in render loop)
-            self.ps.emit_burst([160.0, 180.0 * 0.9], cfg);
+            self.ps.emit_burst([WORLD_W * 0.5, WORLD_H * 0.9], cfg);
         }
 
         if self.well_active {
@@ -412,16 +487,26 @@ impl App {
         // --- Bouncing square (physics demo) ---
         self.body.pos = self.body.pos.add(self.body.vel.mul(dt));
 
-        // We'll clamp in render after we know the current canvas size; but to keep it robust,
-        // use the nominal 320x180 when dt spikes (it'll get corrected visually anyway).
-        let (w, h) = (320.0, 180.0);
+        let (w, h) = (WORLD_W, WORLD_H);
         let s = self.body.size.x;
 
+        let before = self.body.vel;
         if self.body.pos.x <= 0.0 { self.body.pos.x = 0.0; self.body.vel.x =  self.body.vel.x.abs(); }
         if self.body.pos.x + s >= w { self.body.pos.x = w - s; self.body.vel.x = -self.body.vel.x.abs(); }
         if self.body.pos.y <= 0.0 { self.body.pos.y = 0.0; self.body.vel.y =  self.body.vel.y.abs(); }
         if self.body.pos.y + s >= h { self.body.pos.y = h - s; self.body.vel.y = -self.body.vel.y.abs(); }
 
+        // --- Camera: follow the square, shake when it hits a wall ---
+        if self.follow_body {
+            self.rig.follow(&self.body);
+            if before.x != self.body.vel.x || before.y != self.body.vel.y {
+                self.rig.add_trauma(0.35);
+            }
+        } else {
+            self.rig.clear_target();
+        }
+        self.rig.update(dt);
+
         // --- Update particles ---
         self.ps.update(dt);
 
@@ -433,18 +518,37 @@ impl App {
         false
     }
 
-    fn render(&mut self, canvas: &mut PixelsCanvas) -> anyhow::Result<()> {
-        // Real canvas size for bounds-sensitive drawing
+    fn render<C: Canvas>(&mut self, canvas: &mut C) -> anyhow::Result<()> {
+        // Camera follows the canvas size; world stays WORLD_W x WORLD_H
         let (w_i, h_i) = canvas.size();
-        let (w, h) = (w_i as f32, h_i as f32);
+        self.rig.camera.viewport = IRect::new(0, 0, w_i as i32, h_i as i32);
 
-        // Keep the physics box in-bounds with current size
-        let s = self.body.size.x;
-        if self.body.pos.x + s >= w { self.body.pos.x = w - s; self.body.vel.x = -self.body.vel.x.abs(); }
-        if self.body.pos.y + s >= h { self.body.pos.y = h - s; self.body.vel.y = -self.body.vel.y.abs(); }
+        let mouse = self.mouse_world();
+        let mut world = CameraCanvas::new(canvas, self.rig.view());
+        self.render_world(&mut world, mouse);
+        drop(world);
+
+        // --- UI overlay (screen space) ---
+        self.draw_ui(canvas);
+
+        Ok(())
+    }
+
+    fn render_world<C: Canvas>(&mut self, canvas: &mut CameraCanvas<'_, C>, mouse: Vec2) {
+        // World border
+        canvas.draw_line_f32(0.0, 0.0, WORLD_W, 0.0, Color(60, 60, 80, 255));
+        canvas.draw_line_f32(WORLD_W, 0.0, WORLD_W, WORLD_H, Color(60, 60, 80, 255));
+        canvas.draw_line_f32(WORLD_W, WORLD_H, 0.0, WORLD_H, Color(60, 60, 80, 255));
+        canvas.draw_line_f32(0.0, WORLD_H, 0.0, 0.0, Color(60, 60, 80, 255));
 
         // Gravity well visual
         if self.well_active {
+            canvas.fill_circle_f32(
+                self.well_pos.x,
+                self.well_pos.y,
+                self.well_radius,
+                Color(120, 200, 255, 24)
+            );
             canvas.draw_circle_f32(
                 self.well_pos.x,
                 self.well_pos.y,
@@ -470,17 +574,7 @@ impl App {
         }
 
         // Mouse dot
-        canvas.fill_rect_f32(
-            self.input.mouse_pos.x - 2.0,
-            self.input.mouse_pos.y - 2.0,
-            4.0, 4.0,
-            Color(255, 255, 255, 160),
-        );
-
-        // --- UI overlay ---
-        self.draw_ui(canvas);
-
-        Ok(())
+        canvas.fill_rect_f32(mouse.x - 2.0, mouse.y - 2.0, 4.0, 4.0, Color(255, 255, 255, 160));
     }
 
     fn draw_ui(&mut self, canvas: &mut impl Canvas) {
@@ -488,7 +582,7 @@ impl App {
 
         // Canvas size (logical px)
         let (cw_i, ch_i) = canvas.size();
-        let (w, h) = (cw_i as f32, ch_i as f32);
+        let (w, _h) = (cw_i as f32, ch_i as f32);
 
         // ---- Layout constants (tweak these) ----
         let m = 8.0;        // outer margin
@@ -572,11 +666,9 @@ impl App {
 
         // Stretch the "Well @ Mouse" to the right edge but keep margins
         let rem_w = (w - m) - xg;
-        if rem_w > 40.0 {
-            if self.ui.button(&self.input, canvas, 
+        if rem_w > 40.0 && self.ui.button(&self.input, canvas,
             Rect { x: xg, y: y3, w: rem_w, h: bh }, "Well @ Mouse") {
-                self.well_pos = self.input.mouse_pos.into();
-            }
+            self.well_pos = self.mouse_world();
         }
     }
 }
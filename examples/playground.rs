@@ -1,3 +1,6 @@
+// Without `render-pixels` only the stub `main` below is built.
+#![cfg_attr(not(feature = "render-pixels"), allow(unused_imports))]
+
 use std::time::{Duration, Instant};
 
 use daniengine::prelude::*;
@@ -10,7 +13,7 @@ use daniengine::render::pixels_impl::PixelsCanvas;
 #[cfg(feature = "render-pixels")]
 use winit::{
     event::{Event, WindowEvent, ElementState, VirtualKeyCode, KeyboardInput}, 
-    event_loop::{ControlFlow, EventLoop},
+    event_loop::ControlFlow,
 };
 
 #[cfg(feature = "render-pixels")]
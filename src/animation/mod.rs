@@ -0,0 +1,290 @@
+//! Sprite-sheet animation.
+//! - `Animation`: frames (sheet region + duration), a play mode and optional frame events
+//! - `Animator`: advances with `update(dt)`, returns the events fired, draws the current frame
+//! - `SpriteSheet`: image + animations imported from Aseprite's JSON export
+//!
+//! Durations are in seconds. Events fire when their frame is entered.
+
+use std::path::Path;
+
+use anyhow::{bail, Context};
+use serde_json::Value;
+
+use crate::render::canvas::{BlitParams, Canvas, IRect};
+use crate::render::codec;
+use crate::render::image::Image;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PlayMode {
+    #[default]
+    Loop,
+    /// 0, 1, .., n-1, n-2, .., 1, 0, 1, ..
+    PingPong,
+    /// Stops on the last frame.
+    Once,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    /// Pixels in the sheet.
+    pub region: IRect,
+    pub duration: f32,
+    /// Where `region` sits inside the untrimmed frame (trimmed sheets).
+    pub offset: (i32, i32),
+    /// Untrimmed frame size; used to mirror `offset` when flipped.
+    pub size: (i32, i32),
+    pub event: Option<String>,
+}
+
+impl Frame {
+    pub fn new(region: IRect, duration: f32) -> Self {
+        Self { region, duration, offset: (0, 0), size: (region.w, region.h), event: None }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Animation {
+    pub name: String,
+    pub frames: Vec<Frame>,
+    pub mode: PlayMode,
+    /// Stop on the last frame after this many passes (`None` = forever). Ping-pong counts
+    /// each direction as one pass; `Once` always plays one.
+    pub repeat: Option<u32>,
+}
+
+impl Animation {
+    pub fn new(name: &str, frames: Vec<Frame>, mode: PlayMode) -> Self {
+        Self { name: name.to_string(), frames, mode, repeat: None }
+    }
+
+    /// Play `count` passes, then stop (see `repeat`).
+    pub fn with_repeat(mut self, count: u32) -> Self {
+        self.repeat = Some(count);
+        self
+    }
+
+    /// `count` frames the size of `first`, left to right, at `fps` frames per second.
+    pub fn from_strip(name: &str, first: IRect, count: usize, fps: f32, mode: PlayMode) -> Self {
+        let d = 1.0 / fps.max(0.001);
+        let frames = (0..count as i32)
+            .map(|i| Frame::new(IRect::new(first.x + i * first.w, first.y, first.w, first.h), d))
+            .collect();
+        Self::new(name, frames, mode)
+    }
+
+    /// Fire `event` whenever frame `index` is entered (ignored if out of range).
+    pub fn with_event(mut self, index: usize, event: &str) -> Self {
+        if let Some(f) = self.frames.get_mut(index) { f.event = Some(event.to_string()); }
+        self
+    }
+
+    /// One pass through the frames (ping-pong: there and back).
+    pub fn duration(&self) -> f32 {
+        let pass: f32 = self.frames.iter().map(|f| f.duration).sum();
+        match self.mode {
+            PlayMode::PingPong if self.frames.len() > 2 => {
+                let n = self.frames.len();
+                pass + self.frames[1..n - 1].iter().map(|f| f.duration).sum::<f32>()
+            }
+            _ => pass,
+        }
+    }
+}
+
+/* ------------------------------ animator ------------------------------ */
+
+// Frames shorter than this are clamped so a zero duration can't stall `update`
+// (tile animations clamp the same way).
+pub(crate) const MIN_FRAME_TIME: f32 = 0.001;
+
+#[derive(Clone, Debug)]
+pub struct Animator {
+    animation: Animation,
+    frame: usize,
+    time: f32,
+    forward: bool,
+    finished: bool,
+    /// Passes completed, for `Animation::repeat`.
+    passes: u32,
+    /// The current frame was entered but its event hasn't been reported yet.
+    entered: bool,
+    /// Playback rate multiplier (1 = normal, 0 = paused).
+    pub speed: f32,
+}
+
+impl Animator {
+    pub fn new(animation: Animation) -> Self {
+        Self { animation, frame: 0, time: 0.0, forward: true, finished: false, passes: 0, entered: true, speed: 1.0 }
+    }
+
+    /// Switch animations; keeps going if `animation` is already playing (same name).
+    pub fn play(&mut self, animation: &Animation) {
+        if self.animation.name == animation.name && !self.finished { return; }
+        self.animation = animation.clone();
+        self.restart();
+    }
+
+    pub fn restart(&mut self) {
+        self.frame = 0;
+        self.time = 0.0;
+        self.forward = true;
+        self.finished = false;
+        self.passes = 0;
+        self.entered = true;
+    }
+
+    pub fn animation(&self) -> &Animation { &self.animation }
+    pub fn frame_index(&self) -> usize { self.frame }
+    pub fn current_frame(&self) -> Option<&Frame> { self.animation.frames.get(self.frame) }
+    /// A `Once` (or finitely repeating) animation has reached the end of its last frame.
+    pub fn is_finished(&self) -> bool { self.finished }
+
+    /// Advance by `dt` seconds; returns the events of every frame entered, in order.
+    pub fn update(&mut self, dt: f32) -> Vec<String> {
+        let mut events = Vec::new();
+        if self.animation.frames.is_empty() { return events; }
+        self.fire_entered(&mut events);
+        if self.finished { return events; }
+
+        self.time += dt * self.speed.max(0.0);
+        loop {
+            let d = self.animation.frames[self.frame].duration.max(MIN_FRAME_TIME);
+            if self.time < d { break; }
+            self.time -= d;
+            if !self.step() {
+                self.time = d;
+                self.finished = true;
+                break;
+            }
+            self.entered = true;
+            self.fire_entered(&mut events);
+        }
+        events
+    }
+
+    fn fire_entered(&mut self, events: &mut Vec<String>) {
+        if !std::mem::take(&mut self.entered) { return; }
+        if let Some(e) = &self.animation.frames[self.frame].event { events.push(e.clone()); }
+    }
+
+    // Move to the next frame; false when the animation is out of passes.
+    fn step(&mut self) -> bool {
+        let n = self.animation.frames.len();
+        let at_end = if self.forward { self.frame + 1 >= n } else { self.frame == 0 };
+        if at_end {
+            self.passes += 1;
+            let limit = if self.animation.mode == PlayMode::Once { Some(1) } else { self.animation.repeat };
+            if limit.is_some_and(|r| self.passes >= r) { return false; }
+        }
+        match self.animation.mode {
+            PlayMode::Loop | PlayMode::Once => self.frame = (self.frame + 1) % n,
+            PlayMode::PingPong => {
+                if n == 1 { return true; }
+                if at_end { self.forward = !self.forward; }
+                if self.forward { self.frame += 1; } else { self.frame -= 1; }
+            }
+        }
+        true
+    }
+
+    /// Draw the current frame with its untrimmed top-left at `(x, y)`.
+    pub fn draw(&self, canvas: &mut impl Canvas, sheet: &Image, x: i32, y: i32, params: BlitParams) {
+        let Some(f) = self.current_frame() else { return };
+        let ox = if params.flip_x { f.size.0 - f.offset.0 - f.region.w } else { f.offset.0 };
+        let oy = if params.flip_y { f.size.1 - f.offset.1 - f.region.h } else { f.offset.1 };
+        canvas.draw_image_region(sheet, f.region, x + ox, y + oy, params);
+    }
+}
+
+/* ------------------------------ Aseprite import ------------------------------ */
+
+/// A sheet image and the animations cut from it.
+#[derive(Clone, Debug)]
+pub struct SpriteSheet {
+    pub image: Image,
+    /// Every frame in sheet order.
+    pub frames: Vec<Frame>,
+    pub animations: Vec<Animation>,
+}
+
+impl SpriteSheet {
+    pub fn animation(&self, name: &str) -> Option<&Animation> {
+        self.animations.iter().find(|a| a.name == name)
+    }
+
+    /// Parse Aseprite's JSON export (hash or array frames).
+    /// - Each frame tag becomes an animation: forward/reverse loop, ping-pong plays
+    ///   ping-pong, `repeat: "n"` stops after n passes; without tags there is one `"default"` loop
+    /// - Cel user data (export with layers) becomes the event of its frame
+    pub fn from_aseprite_json(json: &str, image: Image) -> anyhow::Result<Self> {
+        let root: Value = serde_json::from_str(json).context("invalid JSON")?;
+        let entries: Vec<&Value> = match &root["frames"] {
+            Value::Array(a) => a.iter().collect(),
+            Value::Object(m) => m.values().collect(),
+            _ => bail!("missing 'frames'"),
+        };
+
+        let mut frames = Vec::with_capacity(entries.len());
+        for (i, e) in entries.iter().enumerate() {
+            let rect = |v: &Value| -> Option<IRect> {
+                Some(IRect::new(int(&v["x"])?, int(&v["y"])?, int(&v["w"])?, int(&v["h"])?))
+            };
+            let region = rect(&e["frame"]).with_context(|| format!("frame {i}: bad 'frame' rect"))?;
+            if e["rotated"].as_bool() == Some(true) { bail!("frame {i}: rotated frames are not supported"); }
+            if region.intersect(&image.bounds()) != region { bail!("frame {i} lies outside the sheet image"); }
+            let duration = e["duration"].as_f64().unwrap_or(100.0) as f32 / 1000.0;
+            let mut frame = Frame::new(region, duration);
+            if let Some(src) = rect(&e["spriteSourceSize"]) { frame.offset = (src.x, src.y); }
+            if let (Some(w), Some(h)) = (int(&e["sourceSize"]["w"]), int(&e["sourceSize"]["h"])) { frame.size = (w, h); }
+            frames.push(frame);
+        }
+
+        let meta = &root["meta"];
+        for layer in meta["layers"].as_array().into_iter().flatten() {
+            for cel in layer["cels"].as_array().into_iter().flatten() {
+                let (Some(i), Some(data)) = (cel["frame"].as_u64(), cel["data"].as_str()) else { continue };
+                if let Some(f) = frames.get_mut(i as usize) && !data.is_empty() {
+                    f.event = Some(data.to_string());
+                }
+            }
+        }
+
+        let mut animations = Vec::new();
+        for tag in meta["frameTags"].as_array().into_iter().flatten() {
+            let name = tag["name"].as_str().context("frame tag without a name")?;
+            let (from, to) = (tag["from"].as_u64().unwrap_or(0) as usize, tag["to"].as_u64().unwrap_or(0) as usize);
+            if from > to || to >= frames.len() { bail!("tag {name:?}: frames {from}..={to} out of range"); }
+            let mut seq = frames[from..=to].to_vec();
+            let dir = tag["direction"].as_str().unwrap_or("forward");
+            if dir.ends_with("reverse") { seq.reverse(); }
+            // "0" or no repeat means forever
+            let repeat = match tag["repeat"].as_str() {
+                Some(r) => r.parse::<u32>().with_context(|| format!("tag {name:?}: bad repeat {r:?}"))?,
+                None => 0,
+            };
+            let mode = match (dir, repeat) {
+                (_, 1) => PlayMode::Once,
+                ("pingpong" | "pingpong_reverse", _) => PlayMode::PingPong,
+                _ => PlayMode::Loop,
+            };
+            let anim = Animation::new(name, seq, mode);
+            animations.push(if repeat > 1 { anim.with_repeat(repeat) } else { anim });
+        }
+        if animations.is_empty() && !frames.is_empty() {
+            animations.push(Animation::new("default", frames.clone(), PlayMode::Loop));
+        }
+        Ok(Self { image, frames, animations })
+    }
+
+    /// Load a `.json` export and the sheet image named in `meta.image` (relative to the JSON).
+    pub fn load_aseprite(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let root: Value = serde_json::from_str(&json).with_context(|| format!("parsing {}", path.display()))?;
+        let file = root["meta"]["image"].as_str().with_context(|| format!("{}: missing meta.image", path.display()))?;
+        let image = codec::load_image(path.parent().unwrap_or(Path::new("")).join(file))?;
+        Self::from_aseprite_json(&json, image).with_context(|| format!("parsing {}", path.display()))
+    }
+}
+
+fn int(v: &Value) -> Option<i32> { v.as_i64().and_then(|n| i32::try_from(n).ok()) }
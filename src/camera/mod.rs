@@ -0,0 +1,245 @@
+//! 2D camera for DaniEngine.
+//! - `Camera2D`: world <-> screen transform (position, zoom, rotation, viewport)
+//! - `CameraCanvas`: wraps any `Canvas` so world-space draw calls land on screen
+//! - `rig::CameraRig`: follow, dead-zone, bounds, look-ahead and screen shake
+//!
+//! `CameraCanvas` overrides the `Canvas` f32 hooks (`fill_rect_f32`, `draw_circle_f32`,
+//! `draw_line_f32`), so generic code keeps sub-pixel precision through it. The other
+//! `CanvasFloatExt` helpers still round to whole world units first.
+
+use crate::math::Vec2;
+use crate::physics::Aabb;
+use crate::render::canvas::{
+    BlendMode, BlitParams, Canvas, CanvasFloatExt, Color, IRect, ImageTransform,
+};
+use crate::render::image::Image;
+
+pub mod rig;
+pub use rig::CameraRig;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Camera2D {
+    /// World point shown at the center of the viewport.
+    pub position: Vec2,
+    /// Screen pixels per world unit.
+    pub zoom: f32,
+    /// Radians; positive turns the view clockwise (the world appears to turn counter-clockwise).
+    pub rotation: f32,
+    /// Screen-space rect the camera renders into.
+    pub viewport: IRect,
+}
+
+impl Camera2D {
+    /// Camera filling `viewport` with world == screen pixels (zoom 1, no rotation).
+    pub fn new(viewport: IRect) -> Self {
+        Self {
+            position: Vec2::new(viewport.x as f32 + viewport.w as f32 * 0.5, viewport.y as f32 + viewport.h as f32 * 0.5),
+            zoom: 1.0,
+            rotation: 0.0,
+            viewport,
+        }
+    }
+
+    fn viewport_center(&self) -> Vec2 {
+        Vec2::new(
+            self.viewport.x as f32 + self.viewport.w as f32 * 0.5,
+            self.viewport.y as f32 + self.viewport.h as f32 * 0.5,
+        )
+    }
+
+    pub fn world_to_screen(&self, p: Vec2) -> Vec2 {
+        let (sin, cos) = (-self.rotation).sin_cos();
+        let (dx, dy) = ((p.x - self.position.x) * self.zoom, (p.y - self.position.y) * self.zoom);
+        let c = self.viewport_center();
+        Vec2::new(c.x + dx * cos - dy * sin, c.y + dx * sin + dy * cos)
+    }
+
+    /// Inverse of `world_to_screen`; e.g. `camera.screen_to_world(input.mouse_pos)`.
+    pub fn screen_to_world(&self, p: Vec2) -> Vec2 {
+        let (sin, cos) = self.rotation.sin_cos();
+        let c = self.viewport_center();
+        let inv = 1.0 / self.zoom;
+        let (dx, dy) = ((p.x - c.x) * inv, (p.y - c.y) * inv);
+        Vec2::new(self.position.x + dx * cos - dy * sin, self.position.y + dx * sin + dy * cos)
+    }
+
+    /// World-space box containing everything the viewport can show (for culling).
+    pub fn visible_world_rect(&self) -> Aabb {
+        let v = self.viewport;
+        let corners = [
+            self.screen_to_world(Vec2::new(v.x as f32, v.y as f32)),
+            self.screen_to_world(Vec2::new((v.x + v.w) as f32, v.y as f32)),
+            self.screen_to_world(Vec2::new(v.x as f32, (v.y + v.h) as f32)),
+            self.screen_to_world(Vec2::new((v.x + v.w) as f32, (v.y + v.h) as f32)),
+        ];
+        bounds_of(&corners)
+    }
+}
+
+fn bounds_of(pts: &[Vec2]) -> Aabb {
+    let min_x = pts.iter().map(|p| p.x).fold(f32::INFINITY, f32::min);
+    let max_x = pts.iter().map(|p| p.x).fold(f32::NEG_INFINITY, f32::max);
+    let min_y = pts.iter().map(|p| p.y).fold(f32::INFINITY, f32::min);
+    let max_y = pts.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max);
+    Aabb { x: min_x, y: min_y, w: max_x - min_x, h: max_y - min_y }
+}
+
+/// Draws in world space onto `inner`, clipped to the camera viewport.
+/// The viewport clip is pushed on creation and popped on drop.
+pub struct CameraCanvas<'a, C: Canvas> {
+    inner: &'a mut C,
+    camera: Camera2D,
+}
+
+impl<'a, C: Canvas> CameraCanvas<'a, C> {
+    pub fn new(inner: &'a mut C, camera: Camera2D) -> Self {
+        inner.push_clip(camera.viewport);
+        Self { inner, camera }
+    }
+
+    pub fn camera(&self) -> &Camera2D { &self.camera }
+
+    /// The wrapped screen-space canvas.
+    pub fn inner(&mut self) -> &mut C { self.inner }
+
+    fn to_screen(&self, x: f32, y: f32) -> Vec2 { self.camera.world_to_screen(Vec2::new(x, y)) }
+
+    // Rotated ellipses become polygons.
+    fn ellipse_points(&self, cx: i32, cy: i32, rx: i32, ry: i32) -> Vec<Vec2> {
+        let segs = ((rx.max(ry) as f32 * self.camera.zoom) as usize).clamp(12, 64);
+        (0..segs)
+            .map(|i| {
+                let a = std::f32::consts::TAU * i as f32 / segs as f32;
+                self.to_screen(cx as f32 + a.cos() * rx as f32, cy as f32 + a.sin() * ry as f32)
+            })
+            .collect()
+    }
+}
+
+impl<C: Canvas> Drop for CameraCanvas<'_, C> {
+    fn drop(&mut self) { self.inner.pop_clip(); }
+}
+
+impl<C: Canvas> Canvas for CameraCanvas<'_, C> {
+    fn size(&self) -> (u32, u32) { self.inner.size() }
+    fn clear(&mut self, color: Color) { self.inner.clear(color); }
+
+    fn fill_rect(&mut self, x: i32, y: i32, w: i32, h: i32, color: Color) {
+        self.fill_rect_f32(x as f32, y as f32, w as f32, h as f32, color);
+    }
+
+    fn present(&mut self) -> Result<(), String> { self.inner.present() }
+
+    fn blend_mode(&self) -> BlendMode { self.inner.blend_mode() }
+    fn set_blend_mode(&mut self, mode: BlendMode) { self.inner.set_blend_mode(mode); }
+
+    /// `rect` is in world units; the screen clip is its bounding box.
+    fn push_clip(&mut self, rect: IRect) {
+        let (x0, y0) = (rect.x as f32, rect.y as f32);
+        let (x1, y1) = ((rect.x + rect.w) as f32, (rect.y + rect.h) as f32);
+        let b = bounds_of(&[self.to_screen(x0, y0), self.to_screen(x1, y0), self.to_screen(x0, y1), self.to_screen(x1, y1)]);
+        let (sx, sy) = (b.x.floor() as i32, b.y.floor() as i32);
+        self.inner.push_clip(IRect::new(sx, sy, (b.x + b.w).ceil() as i32 - sx, (b.y + b.h).ceil() as i32 - sy));
+    }
+
+    fn pop_clip(&mut self) { self.inner.pop_clip(); }
+
+    fn clip_rect(&self) -> IRect {
+        let c = self.inner.clip_rect();
+        let s2w = |x: i32, y: i32| self.camera.screen_to_world(Vec2::new(x as f32, y as f32));
+        let b = bounds_of(&[s2w(c.x, c.y), s2w(c.x + c.w, c.y), s2w(c.x, c.y + c.h), s2w(c.x + c.w, c.y + c.h)]);
+        let (x, y) = (b.x.floor() as i32, b.y.floor() as i32);
+        IRect::new(x, y, (b.x + b.w).ceil() as i32 - x, (b.y + b.h).ceil() as i32 - y)
+    }
+
+    fn fill_rect_f32(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        if self.camera.rotation == 0.0 {
+            let a = self.to_screen(x, y);
+            let b = self.to_screen(x + w, y + h);
+            let (x0, y0) = (a.x.min(b.x).round() as i32, a.y.min(b.y).round() as i32);
+            let (x1, y1) = (a.x.max(b.x).round() as i32, a.y.max(b.y).round() as i32);
+            self.inner.fill_rect(x0, y0, (x1 - x0).max(1), (y1 - y0).max(1), color);
+        } else {
+            let pts = [self.to_screen(x, y), self.to_screen(x + w, y), self.to_screen(x + w, y + h), self.to_screen(x, y + h)];
+            self.inner.fill_polygon_f32(&pts, color);
+        }
+    }
+
+    fn draw_circle_f32(&mut self, x: f32, y: f32, radius: f32, color: Color) {
+        let c = self.to_screen(x, y);
+        self.inner.draw_circle_f32(c.x, c.y, radius * self.camera.zoom, color);
+    }
+
+    fn draw_line_f32(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, color: Color) {
+        let (a, b) = (self.to_screen(x1, y1), self.to_screen(x2, y2));
+        self.inner.draw_line_f32(a.x, a.y, b.x, b.y, color);
+    }
+
+    fn draw_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: Color) {
+        self.draw_line_f32(x1 as f32, y1 as f32, x2 as f32, y2 as f32, color);
+    }
+
+    fn draw_circle(&mut self, cx: i32, cy: i32, radius: i32, color: Color) {
+        self.draw_circle_f32(cx as f32, cy as f32, radius as f32, color);
+    }
+
+    fn fill_circle(&mut self, cx: i32, cy: i32, radius: i32, color: Color) {
+        let c = self.to_screen(cx as f32, cy as f32);
+        self.inner.fill_circle_f32(c.x, c.y, radius as f32 * self.camera.zoom, color);
+    }
+
+    fn draw_ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, color: Color) {
+        if self.camera.rotation == 0.0 {
+            let c = self.to_screen(cx as f32, cy as f32);
+            let z = self.camera.zoom;
+            self.inner.draw_ellipse_f32(c.x, c.y, rx as f32 * z, ry as f32 * z, color);
+        } else {
+            let pts = self.ellipse_points(cx, cy, rx, ry);
+            for (i, a) in pts.iter().enumerate() {
+                let b = pts[(i + 1) % pts.len()];
+                self.inner.draw_line_f32(a.x, a.y, b.x, b.y, color);
+            }
+        }
+    }
+
+    fn fill_ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, color: Color) {
+        if self.camera.rotation == 0.0 {
+            let c = self.to_screen(cx as f32, cy as f32);
+            let z = self.camera.zoom;
+            self.inner.fill_ellipse_f32(c.x, c.y, rx as f32 * z, ry as f32 * z, color);
+        } else {
+            let pts = self.ellipse_points(cx, cy, rx, ry);
+            self.inner.fill_polygon_f32(&pts, color);
+        }
+    }
+
+    fn fill_rounded_rect(&mut self, x: i32, y: i32, w: i32, h: i32, radius: i32, color: Color) {
+        if self.camera.rotation == 0.0 {
+            let a = self.to_screen(x as f32, y as f32);
+            let z = self.camera.zoom;
+            self.inner.fill_rounded_rect_f32(a.x, a.y, w as f32 * z, h as f32 * z, radius as f32 * z, color);
+        } else {
+            // corners are below a pixel at typical zooms; keep the rotated body exact
+            self.fill_rect_f32(x as f32, y as f32, w as f32, h as f32, color);
+        }
+    }
+
+    fn fill_polygon(&mut self, points: &[(i32, i32)], color: Color) {
+        let pts: Vec<Vec2> = points.iter().map(|&(x, y)| self.to_screen(x as f32, y as f32)).collect();
+        self.inner.fill_polygon_f32(&pts, color);
+    }
+
+    fn draw_image_region(&mut self, image: &Image, src: IRect, x: i32, y: i32, params: BlitParams) {
+        self.draw_image_transformed(image, src, ImageTransform::at(x as f32, y as f32), params);
+    }
+
+    fn draw_image_transformed(&mut self, image: &Image, src: IRect, xf: ImageTransform, params: BlitParams) {
+        let screen = ImageTransform {
+            pos: self.camera.world_to_screen(xf.pos),
+            rotation: xf.rotation - self.camera.rotation,
+            scale: Vec2::new(xf.scale.x * self.camera.zoom, xf.scale.y * self.camera.zoom),
+            ..xf
+        };
+        self.inner.draw_image_transformed(image, src, screen, params);
+    }
+}
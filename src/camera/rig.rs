@@ -0,0 +1,154 @@
+//! Gameplay camera behaviors on top of `Camera2D`.
+//! - Smooth follow of a `physics::Body` with a dead-zone
+//! - Velocity look-ahead and clamping to level bounds
+//! - Trauma-based screen shake (seeded, deterministic for a given dt sequence)
+
+use crate::math::Vec2;
+use crate::physics::{Aabb, Body};
+
+use super::Camera2D;
+
+pub struct CameraRig {
+    /// Smoothed camera without shake; edit zoom/rotation/viewport here.
+    pub camera: Camera2D,
+    /// Half-size (world units) of the box around the view center the target can move in freely.
+    pub dead_zone: Vec2,
+    /// Exponential follow rate per second; `f32::INFINITY` snaps.
+    pub follow_speed: f32,
+    /// Seconds of target velocity to lead by.
+    pub look_ahead: f32,
+    /// Exponential rate per second the look-ahead offset eases toward its goal.
+    pub look_ahead_speed: f32,
+    /// Keep the view inside this world rect (centered on an axis if the rect is smaller).
+    pub bounds: Option<Aabb>,
+
+    /// Shake offset in screen pixels at full trauma.
+    pub max_shake_offset: Vec2,
+    /// Shake rotation in radians at full trauma.
+    pub max_shake_angle: f32,
+    /// Trauma lost per second.
+    pub trauma_decay: f32,
+
+    target: Option<(Vec2, Vec2)>, // center, velocity
+    look: Vec2,
+    trauma: f32,
+    shake_offset: Vec2,
+    shake_angle: f32,
+    rng_state: u32,
+}
+
+impl CameraRig {
+    pub fn new(camera: Camera2D, seed: u32) -> Self {
+        Self {
+            camera,
+            dead_zone: Vec2::new(16.0, 12.0),
+            follow_speed: 8.0,
+            look_ahead: 0.0,
+            look_ahead_speed: 4.0,
+            bounds: None,
+            max_shake_offset: Vec2::new(8.0, 8.0),
+            max_shake_angle: 0.05,
+            trauma_decay: 1.5,
+            target: None,
+            look: Vec2::default(),
+            trauma: 0.0,
+            shake_offset: Vec2::default(),
+            shake_angle: 0.0,
+            rng_state: seed,
+        }
+    }
+
+    /// Track `body` this frame; call before `update`.
+    pub fn follow(&mut self, body: &Body) {
+        let center = Vec2::new(body.pos.x + body.size.x * 0.5, body.pos.y + body.size.y * 0.5);
+        self.target = Some((center, body.vel));
+    }
+
+    pub fn clear_target(&mut self) { self.target = None; }
+
+    /// Jump straight to the target (e.g. after a level load) and drop any easing.
+    pub fn snap_to_target(&mut self) {
+        if let Some((center, vel)) = self.target {
+            self.look = vel.mul(self.look_ahead);
+            self.camera.position = center.add(self.look);
+            self.clamp_to_bounds();
+        }
+    }
+
+    /// Add trauma in `[0, 1]`; shake strength grows with trauma squared.
+    pub fn add_trauma(&mut self, amount: f32) { self.trauma = (self.trauma + amount).clamp(0.0, 1.0); }
+    pub fn trauma(&self) -> f32 { self.trauma }
+
+    pub fn update(&mut self, dt: f32) {
+        if let Some((center, vel)) = self.target {
+            let look_goal = vel.mul(self.look_ahead);
+            let k = 1.0 - (-self.look_ahead_speed * dt).exp();
+            self.look = Vec2::new(
+                self.look.x + (look_goal.x - self.look.x) * k,
+                self.look.y + (look_goal.y - self.look.y) * k,
+            );
+
+            // Move only as far as needed to bring the goal back inside the dead-zone.
+            let goal = center.add(self.look);
+            let pos = self.camera.position;
+            let desired = Vec2::new(
+                pos.x + excess(goal.x - pos.x, self.dead_zone.x),
+                pos.y + excess(goal.y - pos.y, self.dead_zone.y),
+            );
+            let k = 1.0 - (-self.follow_speed * dt).exp();
+            self.camera.position = Vec2::new(pos.x + (desired.x - pos.x) * k, pos.y + (desired.y - pos.y) * k);
+        }
+        self.clamp_to_bounds();
+
+        self.trauma = (self.trauma - self.trauma_decay * dt).max(0.0);
+        let shake = self.trauma * self.trauma;
+        if shake > 0.0 {
+            self.shake_offset = Vec2::new(
+                self.max_shake_offset.x * shake * self.rand_signed(),
+                self.max_shake_offset.y * shake * self.rand_signed(),
+            );
+            self.shake_angle = self.max_shake_angle * shake * self.rand_signed();
+        } else {
+            self.shake_offset = Vec2::default();
+            self.shake_angle = 0.0;
+        }
+    }
+
+    /// Camera to render with this frame: the smoothed camera plus shake.
+    pub fn view(&self) -> Camera2D {
+        let mut cam = self.camera;
+        let inv = 1.0 / cam.zoom;
+        cam.position = Vec2::new(
+            cam.position.x + self.shake_offset.x * inv,
+            cam.position.y + self.shake_offset.y * inv,
+        );
+        cam.rotation += self.shake_angle;
+        cam
+    }
+
+    fn clamp_to_bounds(&mut self) {
+        let Some(b) = self.bounds else { return };
+        let (hw, hh) = (
+            self.camera.viewport.w as f32 * 0.5 / self.camera.zoom,
+            self.camera.viewport.h as f32 * 0.5 / self.camera.zoom,
+        );
+        let p = &mut self.camera.position;
+        p.x = if b.w <= hw * 2.0 { b.x + b.w * 0.5 } else { p.x.clamp(b.x + hw, b.x + b.w - hw) };
+        p.y = if b.h <= hh * 2.0 { b.y + b.h * 0.5 } else { p.y.clamp(b.y + hh, b.y + b.h - hh) };
+    }
+
+    // --- tiny LCG, same as ParticleSystem ---
+    fn rand_u32(&mut self) -> u32 {
+        self.rng_state = self.rng_state.wrapping_mul(1664525).wrapping_add(1013904223);
+        self.rng_state
+    }
+
+    fn rand_signed(&mut self) -> f32 {
+        (self.rand_u32() as f32) / (u32::MAX as f32 + 1.0) * 2.0 - 1.0
+    }
+}
+
+/// How far `d` sticks out of `[-half, half]`.
+fn excess(d: f32, half: f32) -> f32 {
+    if d > half { d - half } else if d < -half { d + half } else { 0.0 }
+}
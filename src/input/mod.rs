@@ -2,7 +2,7 @@
 //! - Action mapping (strings -> keys)
 //! - Axes (e.g., "move_x" from A/D)
 //! - Edge detection for keys/mouse
-//! - Mouse position/delta provided by caller (you can set it from your pixels transform)
+//! - Mouse position/delta provided by caller via `set_mouse_pos` (e.g. `PixelsCanvas::window_to_canvas`)
 
 use std::collections::{HashMap, HashSet};
 
@@ -61,8 +61,8 @@ impl Input {
     }
 
     /// Feed winit window events (keyboard/mouse buttons + wheel).
-    /// NOTE: We intentionally ignore `CursorMoved` here so the example can
-    /// set mouse_pos in canvas coordinates after doing its pixels scaling transform.
+    /// NOTE: We intentionally ignore `CursorMoved` here: its position is in window pixels,
+    /// so map it to canvas coordinates first and pass it to `set_mouse_pos`.
     pub fn handle_window_event(&mut self, e: &WindowEvent) {
         match e {
             WindowEvent::KeyboardInput { input, .. } => {
@@ -89,6 +89,15 @@ impl Input {
         }
     }
 
+    /// Move the pointer (canvas coordinates) and accumulate this frame's delta.
+    pub fn set_mouse_pos(&mut self, pos: Vec2) {
+        self.mouse_delta = Vec2::new(
+            self.mouse_delta.x + pos.x - self.mouse_pos.x,
+            self.mouse_delta.y + pos.y - self.mouse_pos.y,
+        );
+        self.mouse_pos = pos;
+    }
+
     // ---------- Queries ----------
     pub fn pressed(&self, key: Key) -> bool { self.pressed_now.contains(&key) }
     pub fn just_pressed(&self, key: Key) -> bool {
@@ -5,6 +5,11 @@ pub mod input;
 pub mod scene;
 pub mod particles;
 pub mod ui;
+pub mod camera;
+pub mod animation;
+pub mod tilemap;
+pub mod parallax;
+pub mod lighting;
 
 // use daniengine::prelude::*;
 pub mod prelude {
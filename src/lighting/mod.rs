@@ -0,0 +1,251 @@
+//! 2D lighting pass for `Framebuffer` canvases (run after the scene, before UI / `present()`).
+//! - `Light`: point or cone light with color, radius, falloff and intensity
+//! - `Occluder`: segments and `physics::Aabb`s that cast hard shadows
+//! - `Lighting`: accumulates ambient + lights into a light buffer and multiplies it
+//!   over the frame; additive particles can be added as small emissive lights
+//!
+//! Lights and occluders are in world space; `apply` maps screen pixels through a `Camera2D`.
+//! Shadows are traced per lit pixel against the occluders inside the light's radius,
+//! so keep radii and occluder counts modest at high resolutions.
+
+use crate::camera::Camera2D;
+use crate::math::Vec2;
+use crate::particles::ParticleSystem;
+use crate::physics::Aabb;
+use crate::render::canvas::{Color, Framebuffer};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cone {
+    /// Radians, 0 = +x, clockwise on screen (y down).
+    pub direction: f32,
+    /// Half-angle of the beam in radians.
+    pub spread: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Light {
+    pub pos: Vec2,
+    /// Alpha is ignored; use `intensity`.
+    pub color: Color,
+    /// World units; nothing is lit beyond it.
+    pub radius: f32,
+    /// Attenuation exponent: `(1 - d / radius)^falloff` (1 = linear, 2 = softer core).
+    pub falloff: f32,
+    pub intensity: f32,
+    pub cone: Option<Cone>,
+    pub cast_shadows: bool,
+}
+
+impl Light {
+    pub fn point(pos: Vec2, radius: f32, color: Color) -> Self {
+        Self { pos, color, radius, falloff: 2.0, intensity: 1.0, cone: None, cast_shadows: true }
+    }
+
+    pub fn cone(pos: Vec2, radius: f32, color: Color, direction: f32, spread: f32) -> Self {
+        Self { cone: Some(Cone { direction, spread }), ..Self::point(pos, radius, color) }
+    }
+
+    pub fn with_falloff(self, falloff: f32) -> Self { Self { falloff, ..self } }
+    pub fn with_intensity(self, intensity: f32) -> Self { Self { intensity, ..self } }
+    pub fn without_shadows(self) -> Self { Self { cast_shadows: false, ..self } }
+
+    fn bounds(&self) -> Aabb {
+        Aabb { x: self.pos.x - self.radius, y: self.pos.y - self.radius, w: self.radius * 2.0, h: self.radius * 2.0 }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Occluder {
+    /// Blocks light from both sides.
+    Segment(Vec2, Vec2),
+    /// Shadows what lies behind it; its own interior stays lit (e.g. the top of a wall),
+    /// and a light inside it is not blocked.
+    Box(Aabb),
+}
+
+impl Occluder {
+    fn bounds(&self) -> Aabb {
+        match *self {
+            Occluder::Segment(a, b) => Aabb { x: a.x.min(b.x), y: a.y.min(b.y), w: (a.x - b.x).abs(), h: (a.y - b.y).abs() },
+            Occluder::Box(r) => r,
+        }
+    }
+
+    /// Does the ray from `from` to `to` pass through this occluder before reaching `to`?
+    fn blocks(&self, from: Vec2, to: Vec2) -> bool {
+        let (dx, dy) = (to.x - from.x, to.y - from.y);
+        match *self {
+            Occluder::Segment(a, b) => {
+                let (ex, ey) = (b.x - a.x, b.y - a.y);
+                let denom = dx * ey - dy * ex;
+                if denom.abs() < 1e-6 { return false; }
+                let (ax, ay) = (a.x - from.x, a.y - from.y);
+                let t = (ax * ey - ay * ex) / denom;
+                let u = (ax * dy - ay * dx) / denom;
+                t > 1e-4 && t < 1.0 - 1e-4 && (0.0..=1.0).contains(&u)
+            }
+            Occluder::Box(r) => {
+                let inside = |p: Vec2| p.x >= r.x && p.x <= r.x + r.w && p.y >= r.y && p.y <= r.y + r.h;
+                // lit interior; a light inside (torch on a wall, carried by the body) shines out
+                if inside(from) || inside(to) { return false; }
+                // slab test, clipped to the segment
+                let (mut t0, mut t1) = (0.0f32, 1.0f32);
+                for (o, d, lo, hi) in [(from.x, dx, r.x, r.x + r.w), (from.y, dy, r.y, r.y + r.h)] {
+                    if d.abs() < 1e-6 {
+                        if o < lo || o > hi { return false; }
+                        continue;
+                    }
+                    let (a, b) = ((lo - o) / d, (hi - o) / d);
+                    t0 = t0.max(a.min(b));
+                    t1 = t1.min(a.max(b));
+                    if t0 > t1 { return false; }
+                }
+                true
+            }
+        }
+    }
+}
+
+pub struct Lighting {
+    /// Light everything gets; black = pitch dark outside lights, white = lighting off.
+    pub ambient: Color,
+    lights: Vec<Light>,
+    occluders: Vec<Occluder>,
+    /// Emissive particle lights for the next `apply` only.
+    glows: Vec<Light>,
+    /// Linear RGB per pixel, 1.0 = unchanged.
+    buffer: Vec<[f32; 3]>,
+    size: (u32, u32),
+}
+
+impl Lighting {
+    pub fn new(ambient: Color) -> Self {
+        Self { ambient, lights: Vec::new(), occluders: Vec::new(), glows: Vec::new(), buffer: Vec::new(), size: (0, 0) }
+    }
+
+    /// Returns the light's index for `lights_mut`.
+    pub fn add_light(&mut self, light: Light) -> usize {
+        self.lights.push(light);
+        self.lights.len() - 1
+    }
+
+    pub fn lights(&self) -> &[Light] { &self.lights }
+    pub fn lights_mut(&mut self) -> &mut [Light] { &mut self.lights }
+    pub fn clear_lights(&mut self) { self.lights.clear(); }
+
+    pub fn add_occluder(&mut self, occluder: Occluder) { self.occluders.push(occluder); }
+    pub fn add_segment(&mut self, a: Vec2, b: Vec2) { self.add_occluder(Occluder::Segment(a, b)); }
+    pub fn add_aabb(&mut self, aabb: Aabb) { self.add_occluder(Occluder::Box(aabb)); }
+    pub fn occluders(&self) -> &[Occluder] { &self.occluders }
+    pub fn clear_occluders(&mut self) { self.occluders.clear(); }
+
+    /// Make every alive particle a small unshadowed point light for the next `apply`:
+    /// radius `size * radius_scale`, the particle's color, intensity scaled by its alpha.
+    /// Meant for particles drawn with `draw_additive` (sparks, embers, magic).
+    pub fn add_particles(&mut self, particles: &ParticleSystem, radius_scale: f32, intensity: f32) {
+        for ([x, y], size, color) in particles.alive() {
+            let light = Light::point(Vec2::new(x, y), size * radius_scale, color)
+                .with_falloff(1.0)
+                .with_intensity(intensity * color.3 as f32 / 255.0)
+                .without_shadows();
+            if light.radius > 0.0 && light.intensity > 0.0 { self.glows.push(light); }
+        }
+    }
+
+    /// Light reaching screen pixel `(x, y)` in the last `apply` (linear RGB, 1.0 = unchanged).
+    pub fn light_at(&self, x: i32, y: i32) -> Option<[f32; 3]> {
+        let (w, h) = (self.size.0 as i32, self.size.1 as i32);
+        if x < 0 || y < 0 || x >= w || y >= h { return None; }
+        Some(self.buffer[(y * w + x) as usize])
+    }
+
+    /// Build the light buffer for `fb`'s size and multiply it over the frame.
+    /// Particle lights added since the last call are consumed.
+    pub fn apply(&mut self, fb: &mut impl Framebuffer, camera: &Camera2D) {
+        let (w, h) = fb.size();
+        self.render(w, h, camera);
+        let frame = fb.frame_mut();
+        for (px, l) in frame.chunks_exact_mut(4).zip(&self.buffer) {
+            for (c, v) in px[..3].iter_mut().zip(l) {
+                *c = ((*c as u32 * (v.clamp(0.0, 1.0) * 256.0) as u32) >> 8) as u8;
+            }
+        }
+    }
+
+    fn render(&mut self, w: u32, h: u32, camera: &Camera2D) {
+        let Color(ar, ag, ab, _) = self.ambient;
+        let ambient = [ar as f32 / 255.0, ag as f32 / 255.0, ab as f32 / 255.0];
+        self.size = (w, h);
+        self.buffer.clear();
+        self.buffer.resize(w as usize * h as usize, ambient);
+
+        // screen -> world is affine: world(x, y) = origin + x * step_x + y * step_y (pixel centers)
+        let origin = camera.screen_to_world(Vec2::new(0.5, 0.5));
+        let sx = camera.screen_to_world(Vec2::new(1.5, 0.5));
+        let sy = camera.screen_to_world(Vec2::new(0.5, 1.5));
+        let view = ViewMap {
+            origin,
+            step_x: Vec2::new(sx.x - origin.x, sx.y - origin.y),
+            step_y: Vec2::new(sy.x - origin.x, sy.y - origin.y),
+        };
+
+        let glows = std::mem::take(&mut self.glows);
+        let mut nearby = Vec::new();
+        for light in self.lights.iter().chain(&glows) {
+            if light.radius <= 0.0 || light.intensity <= 0.0 { continue; }
+            nearby.clear();
+            if light.cast_shadows {
+                let lb = light.bounds();
+                nearby.extend(self.occluders.iter().filter(|o| overlaps(&o.bounds(), &lb)));
+            }
+            accumulate(&mut self.buffer, w, h, camera, &view, light, &nearby);
+        }
+    }
+}
+
+struct ViewMap {
+    origin: Vec2,
+    step_x: Vec2,
+    step_y: Vec2,
+}
+
+fn accumulate(buffer: &mut [[f32; 3]], w: u32, h: u32, camera: &Camera2D, view: &ViewMap, light: &Light, occluders: &[Occluder]) {
+    // screen-space box around the light's world-space bounds
+    let b = light.bounds();
+    let corners = [(b.x, b.y), (b.x + b.w, b.y), (b.x, b.y + b.h), (b.x + b.w, b.y + b.h)]
+        .map(|(x, y)| camera.world_to_screen(Vec2::new(x, y)));
+    let min_x = corners.iter().map(|p| p.x).fold(f32::INFINITY, f32::min).floor().max(0.0) as u32;
+    let min_y = corners.iter().map(|p| p.y).fold(f32::INFINITY, f32::min).floor().max(0.0) as u32;
+    let max_x = (corners.iter().map(|p| p.x).fold(f32::NEG_INFINITY, f32::max).ceil().max(0.0) as u32).min(w);
+    let max_y = (corners.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max).ceil().max(0.0) as u32).min(h);
+
+    let Color(r, g, bl, _) = light.color;
+    let k = light.intensity / 255.0;
+    let rgb = [r as f32 * k, g as f32 * k, bl as f32 * k];
+    let r2 = light.radius * light.radius;
+    let cone = light.cone.map(|c| (c.direction.cos(), c.direction.sin(), c.spread.cos()));
+
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            let p = Vec2::new(
+                view.origin.x + view.step_x.x * x as f32 + view.step_y.x * y as f32,
+                view.origin.y + view.step_x.y * x as f32 + view.step_y.y * y as f32,
+            );
+            let (dx, dy) = (p.x - light.pos.x, p.y - light.pos.y);
+            let d2 = dx * dx + dy * dy;
+            if d2 >= r2 { continue; }
+            let d = d2.sqrt();
+            if let Some((cx, cy, min_cos)) = cone && d > 0.0 && (dx * cx + dy * cy) / d < min_cos { continue; }
+            if occluders.iter().any(|o| o.blocks(light.pos, p)) { continue; }
+
+            let a = (1.0 - d / light.radius).powf(light.falloff);
+            let px = &mut buffer[(y * w + x) as usize];
+            for (c, l) in px.iter_mut().zip(rgb) { *c += l * a; }
+        }
+    }
+}
+
+// Like `Aabb::intersects`, but touching counts (segments have zero-width bounds).
+fn overlaps(a: &Aabb, b: &Aabb) -> bool {
+    a.x <= b.x + b.w && a.x + a.w >= b.x && a.y <= b.y + b.h && a.y + a.h >= b.y
+}
@@ -1,5 +1,6 @@
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Vec2 { pub x: f32, pub y: f32 }
+#[allow(clippy::should_implement_trait)]
 impl Vec2 {
     pub fn new(x: f32, y: f32) -> Self { Self { x, y } }
     pub fn add(self, o: Self) -> Self { Self::new(self.x + o.x, self.y + o.y) }
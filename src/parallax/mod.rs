@@ -0,0 +1,116 @@
+//! Parallax backgrounds.
+//! - A stack of image layers drawn back to front
+//! - Per-layer scroll factor (0 = fixed to the screen, 1 = moves with the world)
+//! - Repeat on x and/or y, auto-scroll velocity, tint
+//! - Procedural layers are generated once into an image tile (`ParallaxLayer::procedural`)
+//!
+//! `camera` is the world position at the canvas' top-left (as for `Tilemap::draw`);
+//! draw onto the screen canvas, not through a `CameraCanvas`.
+
+use crate::math::Vec2;
+use crate::render::canvas::{BlitParams, Canvas, Color};
+use crate::render::image::Image;
+
+#[derive(Clone, Debug)]
+pub struct ParallaxLayer {
+    pub image: Image,
+    /// How much of the camera movement the layer follows, per axis.
+    pub scroll: Vec2,
+    pub repeat_x: bool,
+    pub repeat_y: bool,
+    /// Auto-scroll in pixels per second (clouds, conveyor-belt starfields).
+    pub velocity: Vec2,
+    pub tint: Color,
+    /// Screen position of the image when the camera is at the origin.
+    pub offset: Vec2,
+    pub visible: bool,
+    /// Distance travelled by `velocity` so far.
+    drift: Vec2,
+}
+
+impl ParallaxLayer {
+    /// Repeats horizontally, no velocity, untinted.
+    pub fn new(image: Image, scroll: Vec2) -> Self {
+        Self {
+            image,
+            scroll,
+            repeat_x: true,
+            repeat_y: false,
+            velocity: Vec2::default(),
+            tint: Color::WHITE,
+            offset: Vec2::default(),
+            visible: true,
+            drift: Vec2::default(),
+        }
+    }
+
+    /// `width x height` tile filled by `f(x, y)`; usually repeated on both axes.
+    pub fn procedural(width: u32, height: u32, scroll: Vec2, f: impl FnMut(u32, u32) -> Color) -> Self {
+        Self { repeat_y: true, ..Self::new(Image::from_fn(width, height, f), scroll) }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.drift = self.drift.add(self.velocity.mul(dt));
+        // keep the drift small on repeating axes so precision doesn't degrade over time
+        let (w, h) = (self.image.width() as f32, self.image.height() as f32);
+        if self.repeat_x && w > 0.0 { self.drift.x = self.drift.x.rem_euclid(w); }
+        if self.repeat_y && h > 0.0 { self.drift.y = self.drift.y.rem_euclid(h); }
+    }
+
+    /// Screen position of the image's top-left copy for a camera at `camera`.
+    pub fn origin(&self, camera: Vec2) -> Vec2 {
+        Vec2::new(
+            self.offset.x + self.drift.x - camera.x * self.scroll.x,
+            self.offset.y + self.drift.y - camera.y * self.scroll.y,
+        )
+    }
+
+    pub fn draw(&self, canvas: &mut impl Canvas, camera: Vec2) {
+        let (w, h) = (self.image.width() as i32, self.image.height() as i32);
+        if !self.visible || w == 0 || h == 0 { return; }
+        let o = self.origin(camera);
+        let (ox, oy) = (o.x.round() as i32, o.y.round() as i32);
+        let clip = canvas.clip_rect();
+
+        // first copy at or before the clip edge when repeating
+        let span = |origin: i32, size: i32, lo: i32, len: i32, repeat: bool| -> (i32, i32) {
+            if repeat { (lo - (lo - origin).rem_euclid(size), lo + len) } else { (origin, origin + 1) }
+        };
+        let (x0, x1) = span(ox, w, clip.x, clip.w, self.repeat_x);
+        let (y0, y1) = span(oy, h, clip.y, clip.h, self.repeat_y);
+
+        let params = BlitParams { tint: self.tint, ..BlitParams::default() };
+        for y in (y0..y1).step_by(h as usize) {
+            for x in (x0..x1).step_by(w as usize) {
+                canvas.draw_image(&self.image, x, y, params);
+            }
+        }
+    }
+}
+
+/// Layers drawn in insertion order (farthest first).
+#[derive(Clone, Debug, Default)]
+pub struct Parallax {
+    layers: Vec<ParallaxLayer>,
+}
+
+impl Parallax {
+    pub fn new() -> Self { Self::default() }
+
+    /// Add a layer in front of the existing ones; returns its index.
+    pub fn add(&mut self, layer: ParallaxLayer) -> usize {
+        self.layers.push(layer);
+        self.layers.len() - 1
+    }
+
+    pub fn layers(&self) -> &[ParallaxLayer] { &self.layers }
+    pub fn layers_mut(&mut self) -> &mut [ParallaxLayer] { &mut self.layers }
+
+    pub fn update(&mut self, dt: f32) {
+        for l in &mut self.layers { l.update(dt); }
+    }
+
+    pub fn draw(&self, canvas: &mut impl Canvas, camera: Vec2) {
+        for l in &self.layers { l.draw(canvas, camera); }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::prelude::{Canvas, Color};
-use crate::render::canvas::CanvasFloatExt;
+use crate::render::canvas::BlendMode;
 
 #[derive(Clone, Copy)]
 pub struct EmitterConfig {
@@ -28,6 +28,22 @@ struct Particle {
     alive: bool,
 }
 
+impl Particle {
+    /// `start_color` fading to `end_color` over the particle's life.
+    fn color(&self) -> Color {
+        let t = (self.life / self.life_total).clamp(0.0, 1.0);
+        let Color(sr, sg, sb, sa) = self.start_color;
+        let Color(er, eg, eb, ea) = self.end_color;
+
+        let r = sr as f32 + (er as f32 - sr as f32) * (1.0 - t);
+        let g = sg as f32 + (eg as f32 - sg as f32) * (1.0 - t);
+        let b = sb as f32 + (eb as f32 - sb as f32) * (1.0 - t);
+        let a = sa as f32 + (ea as f32 - sa as f32) * (1.0 - t);
+
+        Color(r as u8, g as u8, b as u8, a as u8)
+    }
+}
+
 pub struct ParticleSystem {
     particles: Vec<Particle>,
     gravity: [f32; 2],
@@ -172,42 +188,21 @@ impl ParticleSystem {
     pub fn draw<C: Canvas>(&self, canvas: &mut C) {
         for p in &self.particles {
             if !p.alive { continue; }
-            let t = (p.life / p.life_total).clamp(0.0, 1.0);
-            let Color(sr, sg, sb, sa) = p.start_color;
-            let Color(er, eg, eb, ea) = p.end_color;
-
-            let r = sr as f32 + (er as f32 - sr as f32) * (1.0 - t);
-            let g = sg as f32 + (eg as f32 - sg as f32) * (1.0 - t);
-            let b = sb as f32 + (eb as f32 - sb as f32) * (1.0 - t);
-            let a = sa as f32 + (ea as f32 - sa as f32) * (1.0 - t);
-
-            let c = Color(r as u8, g as u8, b as u8, a as u8);
-            canvas.fill_rect_f32(p.pos[0], p.pos[1], p.size, p.size, c);
+            canvas.fill_rect_f32(p.pos[0], p.pos[1], p.size, p.size, p.color());
         }
     }
 
-    pub fn draw_additive<C: Canvas>(&self, canvas: &mut C) {
-        for p in &self.particles {
-            if !p.alive { continue; }
-            let t = (p.life / p.life_total).clamp(0.0, 1.0);
-
-            let Color(sr, sg, sb, sa) = p.start_color;
-            let Color(er, eg, eb, ea) = p.end_color;
-            
-            let r = sr as f32 + (er as f32 - sr as f32) * (1.0 - t);
-            let g = sg as f32 + (eg as f32 - sg as f32) * (1.0 - t);
-            let b = sb as f32 + (eb as f32 - sb as f32) * (1.0 - t);
-            let a = sa as f32 + (ea as f32 - sa as f32) * (1.0 - t);
-
-            let boost = 1.5;
-            let cr = (r * boost).min(255.0);
-            let cg = (g * boost).min(255.0);
-            let cb = (b * boost).min(255.0);
-
-            let c = Color(cr as u8, cg as u8, cb as u8, a as u8);
-            canvas.fill_rect_f32(p.pos[0], p.pos[1], p.size, p.size, c);
+    /// Alive particles as (center, size, current color); e.g. for `Lighting::add_particles`.
+    pub fn alive(&self) -> impl Iterator<Item = ([f32; 2], f32, Color)> + '_ {
+        self.particles.iter().filter(|p| p.alive).map(|p| {
+            let half = p.size * 0.5;
+            ([p.pos[0] + half, p.pos[1] + half], p.size, p.color())
+        })
+    }
 
-        }
+    /// Same as `draw`, but with `BlendMode::Additive` so overlapping particles accumulate light.
+    pub fn draw_additive<C: Canvas>(&self, canvas: &mut C) {
+        canvas.with_blend_mode(BlendMode::Additive, |c| self.draw(c));
     }
 
     // --- internals ---
@@ -1,16 +1,487 @@
-#[derive(Clone, Copy, Debug)]
+use crate::math::Vec2;
+
+use super::image::Image;
+use super::raster;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Color(pub u8, pub u8, pub u8, pub u8);
 
+impl Color {
+    pub const WHITE: Color = Color(255, 255, 255, 255);
+
+    /// Per-channel multiply (`self * other / 255`), alpha included.
+    pub fn modulate(self, other: Color) -> Color {
+        let m = |a: u8, b: u8| ((a as u32 * b as u32 + 127) / 255) as u8;
+        Color(m(self.0, other.0), m(self.1, other.1), m(self.2, other.2), m(self.3, other.3))
+    }
+}
+
+/// Integer pixel rectangle (x, y = top-left).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IRect { pub x: i32, pub y: i32, pub w: i32, pub h: i32 }
+
+impl IRect {
+    pub fn new(x: i32, y: i32, w: i32, h: i32) -> Self { Self { x, y, w, h } }
+
+    pub fn is_empty(&self) -> bool { self.w <= 0 || self.h <= 0 }
+
+    /// Overlap of two rects; empty (w/h = 0) if they don't touch.
+    pub fn intersect(&self, o: &IRect) -> IRect {
+        let x0 = self.x.max(o.x);
+        let y0 = self.y.max(o.y);
+        let x1 = (self.x + self.w).min(o.x + o.w);
+        let y1 = (self.y + self.h).min(o.y + o.h);
+        IRect::new(x0, y0, (x1 - x0).max(0), (y1 - y0).max(0))
+    }
+}
+
+/// Options for `draw_image` / `draw_image_region`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlitParams {
+    pub flip_x: bool,
+    pub flip_y: bool,
+    /// Multiplied into every source pixel (white = unchanged).
+    pub tint: Color,
+    /// Extra opacity on top of the image's own alpha.
+    pub alpha: u8,
+}
+
+impl Default for BlitParams {
+    fn default() -> Self { Self { flip_x: false, flip_y: false, tint: Color::WHITE, alpha: 255 } }
+}
+
+impl BlitParams {
+    /// Final color of one source pixel after tint and alpha.
+    pub fn apply(&self, src: Color) -> Color {
+        let c = src.modulate(self.tint);
+        Color(c.0, c.1, c.2, ((c.3 as u32 * self.alpha as u32 + 127) / 255) as u8)
+    }
+}
+
+/// How a source color combines with the pixel already in the frame.
+/// The source alpha scales the contribution in every mode except `Replace`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// Overwrite the destination, alpha included.
+    Replace,
+    /// Classic "source over"; also correct onto translucent offscreen layers.
+    #[default]
+    Alpha,
+    /// Adds light: `dst + src * a`.
+    Additive,
+    /// Darkens: `dst * src`.
+    Multiply,
+    /// Lightens without blowing out: `1 - (1 - dst) * (1 - src)`.
+    Screen,
+}
+
+impl BlendMode {
+    /// Blend one source color onto a destination RGBA8 pixel.
+    pub fn blend(self, dst: [u8; 4], src: Color) -> [u8; 4] {
+        let Color(sr, sg, sb, sa) = src;
+        if self == BlendMode::Replace { return [sr, sg, sb, sa]; }
+
+        let a = sa as u32;
+        let ia = 255 - a;
+        let da = dst[3] as u32;
+        if self == BlendMode::Alpha && da < 255 {
+            // Straight-alpha "over" onto a translucent pixel (offscreen layers).
+            let den = a * 255 + da * ia;
+            if den == 0 { return [0, 0, 0, 0]; }
+            let ch = |d: u8, s: u8| ((s as u32 * a * 255 + d as u32 * da * ia + den / 2) / den) as u8;
+            return [ch(dst[0], sr), ch(dst[1], sg), ch(dst[2], sb), ((den + 127) / 255) as u8];
+        }
+        // result alpha is "over" for every non-replace mode
+        let out_a = (a + (da * ia + 127) / 255) as u8;
+        let ch = |d: u8, s: u8| -> u8 {
+            let (d, s) = (d as u32, s as u32);
+            let v = match self {
+                BlendMode::Replace => s,
+                BlendMode::Alpha => (s * a + d * ia + 127) / 255,
+                BlendMode::Additive => (d + (s * a + 127) / 255).min(255),
+                BlendMode::Multiply => (d * ((s * a + 255 * ia + 127) / 255) + 127) / 255,
+                BlendMode::Screen => {
+                    let s = (s * a + 127) / 255;
+                    d + s - (d * s + 127) / 255
+                }
+            };
+            v as u8
+        };
+        [ch(dst[0], sr), ch(dst[1], sg), ch(dst[2], sb), out_a]
+    }
+}
+
+/// How `draw_image_transformed` reads the source image.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Sampling {
+    /// Crisp pixels; the right choice for pixel art.
+    #[default]
+    Nearest,
+    /// Smooth, blends the 4 nearest texels.
+    Bilinear,
+}
+
+/// Placement for `draw_image_transformed`.
+/// The source point `pivot` (in source-region pixels) lands on `pos`,
+/// and the image is scaled then rotated (radians, clockwise on screen) around it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ImageTransform {
+    pub pos: Vec2,
+    pub rotation: f32,
+    pub scale: Vec2,
+    pub pivot: Vec2,
+    pub sampling: Sampling,
+}
+
+impl Default for ImageTransform {
+    fn default() -> Self {
+        Self {
+            pos: Vec2::default(),
+            rotation: 0.0,
+            scale: Vec2::new(1.0, 1.0),
+            pivot: Vec2::default(),
+            sampling: Sampling::Nearest,
+        }
+    }
+}
+
+impl ImageTransform {
+    /// Unrotated, unscaled placement with the top-left corner at `(x, y)`.
+    pub fn at(x: f32, y: f32) -> Self { Self { pos: Vec2::new(x, y), ..Self::default() } }
+
+    /// Rotate/scale around the center of a `w x h` source region.
+    pub fn centered(x: f32, y: f32, w: u32, h: u32) -> Self {
+        Self { pos: Vec2::new(x, y), pivot: Vec2::new(w as f32 * 0.5, h as f32 * 0.5), ..Self::default() }
+    }
+}
+
+/// How the edges and center of a nine-slice fill their space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SliceFill {
+    #[default]
+    Stretch,
+    /// Repeat at 1:1, cropping the last copy.
+    Tile,
+}
+
+/// Source region cut into 3x3 by fixed-size borders, for `draw_nine_slice`.
+/// Corners keep their size; edges and center fill the rest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NineSlice {
+    pub src: IRect,
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+    pub edges: SliceFill,
+    pub center: SliceFill,
+    /// Skip the center cell (frames / outlines).
+    pub hollow: bool,
+}
+
+impl NineSlice {
+    /// Stretched edges and center.
+    pub fn new(src: IRect, left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        Self { src, left, top, right, bottom, edges: SliceFill::Stretch, center: SliceFill::Stretch, hollow: false }
+    }
+
+    /// Same border on all four sides.
+    pub fn uniform(src: IRect, border: i32) -> Self { Self::new(src, border, border, border, border) }
+
+    pub fn with_fill(self, edges: SliceFill, center: SliceFill) -> Self { Self { edges, center, ..self } }
+
+    // (src offset, src size, dst offset, dst size) of the three columns or rows along one axis.
+    // Borders shrink proportionally when `dst` is smaller than both together.
+    fn spans(src: i32, lo: i32, hi: i32, dst: i32) -> [(i32, i32, i32, i32); 3] {
+        let (lo, hi) = (lo.clamp(0, src), hi.clamp(0, src - lo.clamp(0, src)));
+        let (dlo, dhi) = if lo + hi > dst && lo + hi > 0 {
+            let dlo = dst.max(0) * lo / (lo + hi);
+            (dlo, dst.max(0) - dlo)
+        } else {
+            (lo, hi)
+        };
+        [
+            (0, dlo, 0, dlo),
+            (lo, src - lo - hi, dlo, dst - dlo - dhi),
+            (src - dhi, dhi, dst - dhi, dhi),
+        ]
+    }
+}
+
+/// End shape for `draw_thick_line_f32`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineCap {
+    /// Ends exactly at the endpoints.
+    #[default]
+    Butt,
+    /// Extends half the width past each endpoint.
+    Square,
+    /// Half-disc around each endpoint.
+    Round,
+}
+
+/// Drawing surface. Backends start in `BlendMode::Alpha` with no clip.
+/// `clear` always replaces, but like every primitive it stays inside the current clip.
 pub trait Canvas {
     fn size(&self) -> (u32, u32);
     fn clear(&mut self, color: Color);
     fn fill_rect(&mut self, x: i32, y: i32, w: i32, h: i32, color: Color);
-    fn draw_circle(&mut self, x: i32, y: i32, radius: i32, color: Color);
-    fn draw_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: Color);
     fn present(&mut self) -> Result<(), String>;
-}
 
-pub trait CanvasFloatExt: Canvas {
+    fn blend_mode(&self) -> BlendMode;
+    fn set_blend_mode(&mut self, mode: BlendMode);
+
+    /// Restrict drawing to `rect` intersected with the current clip.
+    fn push_clip(&mut self, rect: IRect);
+    fn pop_clip(&mut self);
+    /// Effective clip (the whole canvas when nothing is pushed).
+    fn clip_rect(&self) -> IRect;
+
+    /// Run `f` with `rect` pushed as a clip, popping it after.
+    fn with_clip<R>(&mut self, rect: IRect, f: impl FnOnce(&mut Self) -> R) -> R
+    where
+        Self: Sized,
+    {
+        self.push_clip(rect);
+        let r = f(self);
+        self.pop_clip();
+        r
+    }
+
+    /// Run `f` with a temporary blend mode, restoring the previous one after.
+    fn with_blend_mode<R>(&mut self, mode: BlendMode, f: impl FnOnce(&mut Self) -> R) -> R
+    where
+        Self: Sized,
+    {
+        let prev = self.blend_mode();
+        self.set_blend_mode(mode);
+        let r = f(self);
+        self.set_blend_mode(prev);
+        r
+    }
+
+    fn draw_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: Color) {
+        // Bresenham
+        let (mut x, mut y) = (x1, y1);
+        let dx = (x2 - x1).abs();
+        let sx = if x1 < x2 { 1 } else { -1 };
+        let dy = -(y2 - y1).abs();
+        let sy = if y1 < y2 { 1 } else { -1 };
+        let mut err = dx + dy;
+
+        loop {
+            self.fill_rect(x, y, 1, 1, color);
+            if x == x2 && y == y2 { break; }
+            let e2 = 2 * err;
+            if e2 >= dy { err += dy; x += sx; }
+            if e2 <= dx { err += dx; y += sy; }
+        }
+    }
+
+    fn draw_image(&mut self, image: &Image, x: i32, y: i32, params: BlitParams) {
+        self.draw_image_region(image, image.bounds(), x, y, params);
+    }
+
+    /// Blit `src` (in image pixels) with its top-left at `(x, y)`.
+    /// The default goes pixel by pixel through `fill_rect`; framebuffer backends override it.
+    fn draw_image_region(&mut self, image: &Image, src: IRect, x: i32, y: i32, params: BlitParams) {
+        let src = src.intersect(&image.bounds());
+        for dy in 0..src.h {
+            let sy = if params.flip_y { src.y + src.h - 1 - dy } else { src.y + dy };
+            for dx in 0..src.w {
+                let sx = if params.flip_x { src.x + src.w - 1 - dx } else { src.x + dx };
+                if let Some(c) = image.pixel(sx, sy) {
+                    self.fill_rect(x + dx, y + dy, 1, 1, params.apply(c));
+                }
+            }
+        }
+    }
+
+    /// Rotated/scaled blit of `src` via inverse mapping, clipped to the canvas.
+    /// `params.flip_*` mirror the source region before the transform.
+    fn draw_image_transformed(&mut self, image: &Image, src: IRect, xf: ImageTransform, params: BlitParams) {
+        let clip = self.clip_rect();
+        raster::for_each_transformed(image, src, &xf, &params, clip, |x, y, c| {
+            self.fill_rect(x, y, 1, 1, c);
+        });
+    }
+
+    /// Draw `slice` of `image` to fill `dst`. `params.flip_*` are ignored.
+    fn draw_nine_slice(&mut self, image: &Image, slice: &NineSlice, dst: IRect, params: BlitParams) {
+        let params = BlitParams { flip_x: false, flip_y: false, ..params };
+        let cols = NineSlice::spans(slice.src.w, slice.left, slice.right, dst.w);
+        let rows = NineSlice::spans(slice.src.h, slice.top, slice.bottom, dst.h);
+        for (j, &(sy, sh, dy, dh)) in rows.iter().enumerate() {
+            for (i, &(sx, sw, dx, dw)) in cols.iter().enumerate() {
+                if sw <= 0 || sh <= 0 || dw <= 0 || dh <= 0 { continue; }
+                let src = IRect::new(slice.src.x + sx, slice.src.y + sy, sw, sh);
+                let (x, y) = (dst.x + dx, dst.y + dy);
+                let fill = match (i, j) {
+                    (1, 1) if slice.hollow => continue,
+                    (1, 1) => slice.center,
+                    (1, _) | (_, 1) => slice.edges,
+                    // corners are never scaled; shrunken ones are cropped
+                    _ => SliceFill::Tile,
+                };
+                match fill {
+                    SliceFill::Tile => {
+                        for ty in (0..dh).step_by(sh as usize) {
+                            for tx in (0..dw).step_by(sw as usize) {
+                                let part = IRect::new(src.x, src.y, sw.min(dw - tx), sh.min(dh - ty));
+                                self.draw_image_region(image, part, x + tx, y + ty, params);
+                            }
+                        }
+                    }
+                    SliceFill::Stretch if (sw, sh) == (dw, dh) => self.draw_image_region(image, src, x, y, params),
+                    SliceFill::Stretch => {
+                        let xf = ImageTransform {
+                            scale: Vec2::new(dw as f32 / sw as f32, dh as f32 / sh as f32),
+                            ..ImageTransform::at(x as f32, y as f32)
+                        };
+                        self.draw_image_transformed(image, src, xf, params);
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw_circle(&mut self, cx: i32, cy: i32, radius: i32, color: Color) {
+        // Midpoint circle (outline)
+        if radius <= 0 { return; }
+        let mut x = 0;
+        let mut y = radius;
+        let mut d = 1 - radius;
+
+        // helper to plot 8-way symmetry
+        let mut plot = |px: i32, py: i32| { self.fill_rect(px, py, 1, 1, color); };
+
+        // initial cardinal points
+        plot(cx, cy + radius);
+        plot(cx, cy - radius);
+        plot(cx + radius, cy);
+        plot(cx - radius, cy);
+
+        while x < y {
+            if d < 0 {
+                d += 2 * x + 3;
+            } else {
+                d += 2 * (x - y) + 5;
+                y -= 1;
+            }
+            x += 1;
+
+            // 8 octants
+            plot(cx + x, cy + y);
+            plot(cx - x, cy + y);
+            plot(cx + x, cy - y);
+            plot(cx - x, cy - y);
+            plot(cx + y, cy + x);
+            plot(cx - y, cy + x);
+            plot(cx + y, cy - x);
+            plot(cx - y, cy - x);
+        }
+    }
+
+    /* ---------------- filled shapes (spans via fill_rect) ---------------- */
+
+    /// Filled disc; covers the same pixels as the `draw_circle` outline and everything inside it.
+    fn fill_circle(&mut self, cx: i32, cy: i32, radius: i32, color: Color) {
+        if radius < 0 { return; }
+        let r2 = radius * radius + radius;
+        for dy in -radius..=radius {
+            let mut hw = 0;
+            while (hw + 1) * (hw + 1) + dy * dy <= r2 { hw += 1; }
+            self.fill_rect(cx - hw, cy + dy, 2 * hw + 1, 1, color);
+        }
+    }
+
+    fn draw_ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, color: Color) {
+        // Midpoint ellipse (outline)
+        if rx < 0 || ry < 0 { return; }
+        let (rx2, ry2) = ((rx * rx) as i64, (ry * ry) as i64);
+        let (mut x, mut y) = (0i64, ry as i64);
+
+        // 4-way symmetry without double-plotting the axis points
+        let mut plot4 = |x: i64, y: i64| {
+            let (x, y) = (x as i32, y as i32);
+            self.fill_rect(cx + x, cy + y, 1, 1, color);
+            if x != 0 { self.fill_rect(cx - x, cy + y, 1, 1, color); }
+            if y != 0 {
+                self.fill_rect(cx + x, cy - y, 1, 1, color);
+                if x != 0 { self.fill_rect(cx - x, cy - y, 1, 1, color); }
+            }
+        };
+
+        // region 1: slope > -1
+        let mut d = 4 * ry2 - 4 * rx2 * y + rx2;
+        while ry2 * x < rx2 * y {
+            plot4(x, y);
+            if d >= 0 {
+                y -= 1;
+                d -= 8 * rx2 * y;
+            }
+            x += 1;
+            d += 4 * ry2 * (2 * x + 1);
+        }
+
+        // region 2: slope < -1
+        let mut d = ry2 * (2 * x + 1) * (2 * x + 1) + 4 * rx2 * (y - 1) * (y - 1) - 4 * rx2 * ry2;
+        while y >= 0 {
+            plot4(x, y);
+            if d <= 0 {
+                x += 1;
+                d += 8 * ry2 * x;
+            }
+            y -= 1;
+            d += 4 * rx2 * (1 - 2 * y);
+        }
+    }
+
+    fn fill_ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, color: Color) {
+        if rx < 0 || ry < 0 { return; }
+        if ry == 0 { self.fill_rect(cx - rx, cy, 2 * rx + 1, 1, color); return; }
+        let (rx, ry) = (rx as f32, ry as f32);
+        for dy in -(ry as i32)..=(ry as i32) {
+            let t = dy as f32 / (ry + 0.5);
+            let hw = (rx + 0.5) * (1.0 - t * t).max(0.0).sqrt();
+            let hw = (hw - 0.5).round().max(0.0) as i32;
+            self.fill_rect(cx - hw, cy + dy, 2 * hw + 1, 1, color);
+        }
+    }
+
+    /// Filled rectangle with quarter-circle corners (radius clamped to half the short side).
+    fn fill_rounded_rect(&mut self, x: i32, y: i32, w: i32, h: i32, radius: i32, color: Color) {
+        if w <= 0 || h <= 0 { return; }
+        let r = radius.clamp(0, w.min(h) / 2);
+        if r == 0 { self.fill_rect(x, y, w, h, color); return; }
+
+        let rf = r as f32;
+        for row in 0..r {
+            let dy = rf - row as f32 - 0.5;
+            let inset = (rf - (rf * rf - dy * dy).max(0.0).sqrt()).round() as i32;
+            self.fill_rect(x + inset, y + row, w - 2 * inset, 1, color);
+            self.fill_rect(x + inset, y + h - 1 - row, w - 2 * inset, 1, color);
+        }
+        self.fill_rect(x, y + r, w, h - 2 * r, color);
+    }
+
+    fn fill_triangle(&mut self, a: (i32, i32), b: (i32, i32), c: (i32, i32), color: Color) {
+        self.fill_polygon(&[a, b, c], color);
+    }
+
+    /// Scanline fill of any simple polygon, convex or concave (even-odd rule).
+    /// Vertices are pixel corners, so `(0,0) (4,0) (4,4) (0,4)` covers a 4x4 block like `fill_rect`.
+    fn fill_polygon(&mut self, points: &[(i32, i32)], color: Color) {
+        let pts: Vec<Vec2> = points.iter().map(|&(x, y)| Vec2::new(x as f32, y as f32)).collect();
+        let clip = self.clip_rect();
+        raster::scan_polygon(&pts, clip, |y, x0, x1| {
+            self.fill_rect(x0, y, x1 - x0, 1, color);
+        });
+    }
+
+    /* ---------------- sub-pixel hooks (round by default) ---------------- */
+    // Canvases that transform coordinates (e.g. `CameraCanvas`) override these to keep precision.
+
     fn fill_rect_f32(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color) {
         let xi = x.round() as i32;
         let yi = y.round() as i32;
@@ -35,4 +506,143 @@ pub trait CanvasFloatExt: Canvas {
     }
 }
 
+/// A canvas whose pixels live in a CPU-side RGBA8 frame (row-major, no padding).
+pub trait Framebuffer: Canvas {
+    fn frame(&self) -> &[u8];
+    fn frame_mut(&mut self) -> &mut [u8];
+}
+
+pub trait CanvasFloatExt: Canvas {
+    fn fill_circle_f32(&mut self, x: f32, y: f32, radius: f32, color: Color) {
+        self.fill_circle(x.round() as i32, y.round() as i32, radius.max(0.0).round() as i32, color);
+    }
+
+    fn draw_ellipse_f32(&mut self, x: f32, y: f32, rx: f32, ry: f32, color: Color) {
+        let (xi, yi) = (x.round() as i32, y.round() as i32);
+        self.draw_ellipse(xi, yi, rx.max(0.0).round() as i32, ry.max(0.0).round() as i32, color);
+    }
+
+    fn fill_ellipse_f32(&mut self, x: f32, y: f32, rx: f32, ry: f32, color: Color) {
+        let (xi, yi) = (x.round() as i32, y.round() as i32);
+        self.fill_ellipse(xi, yi, rx.max(0.0).round() as i32, ry.max(0.0).round() as i32, color);
+    }
+
+    fn fill_rounded_rect_f32(&mut self, x: f32, y: f32, w: f32, h: f32, radius: f32, color: Color) {
+        let xi = x.round() as i32;
+        let yi = y.round() as i32;
+        let wi = w.max(1.0).round() as i32;
+        let hi = h.max(1.0).round() as i32;
+        self.fill_rounded_rect(xi, yi, wi, hi, radius.max(0.0).round() as i32, color);
+    }
+
+    /// Sub-pixel vertices; coverage is sampled at pixel centers.
+    fn fill_triangle_f32(&mut self, a: Vec2, b: Vec2, c: Vec2, color: Color) {
+        self.fill_polygon_f32(&[a, b, c], color);
+    }
+
+    /// Sub-pixel vertices; coverage is sampled at pixel centers (even-odd rule).
+    fn fill_polygon_f32(&mut self, points: &[Vec2], color: Color) {
+        let clip = self.clip_rect();
+        raster::scan_polygon(points, clip, |y, x0, x1| {
+            self.fill_rect(x0, y, x1 - x0, 1, color);
+        });
+    }
+
+    /// Anti-aliased 1px line (Xiaolin Wu) with sub-pixel endpoints.
+    /// Coordinates match `fill_polygon_f32`: pixel `(x, y)` spans `[x, x + 1)`, so
+    /// `(0.5, 0.5) -> (8.5, 0.5)` is a crisp row. Coverage goes into the alpha channel,
+    /// so a canvas in `BlendMode::Replace` is switched to `Alpha` for the call.
+    fn draw_line_aa_f32(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, color: Color) {
+        let prev = self.blend_mode();
+        if prev == BlendMode::Replace { self.set_blend_mode(BlendMode::Alpha); }
+
+        let mut plot = |x: i32, y: i32, coverage: f32| {
+            let a = (color.3 as f32 * coverage.clamp(0.0, 1.0)).round() as u8;
+            if a > 0 { self.fill_rect(x, y, 1, 1, Color(color.0, color.1, color.2, a)); }
+        };
+        let fpart = |v: f32| v - v.floor();
+        let rfpart = |v: f32| 1.0 - fpart(v);
+
+        // Wu works with pixel centers on integers.
+        let (mut x0, mut y0, mut x1, mut y1) = (x1 - 0.5, y1 - 0.5, x2 - 0.5, y2 - 0.5);
+        let steep = (y1 - y0).abs() > (x1 - x0).abs();
+        if steep { std::mem::swap(&mut x0, &mut y0); std::mem::swap(&mut x1, &mut y1); }
+        if x0 > x1 { std::mem::swap(&mut x0, &mut x1); std::mem::swap(&mut y0, &mut y1); }
+        let mut put = |a: i32, b: i32, c: f32| if steep { plot(b, a, c) } else { plot(a, b, c) };
+
+        let dx = x1 - x0;
+        let gradient = if dx == 0.0 { 1.0 } else { (y1 - y0) / dx };
+
+        // first endpoint
+        let xend = x0.round();
+        let yend = y0 + gradient * (xend - x0);
+        let xgap = rfpart(x0 + 0.5);
+        let xpx1 = xend as i32;
+        put(xpx1, yend.floor() as i32, rfpart(yend) * xgap);
+        put(xpx1, yend.floor() as i32 + 1, fpart(yend) * xgap);
+        let mut intery = yend + gradient;
+
+        // second endpoint
+        let xend = x1.round();
+        let yend = y1 + gradient * (xend - x1);
+        let xgap = fpart(x1 + 0.5);
+        let xpx2 = xend as i32;
+        if xpx2 != xpx1 {
+            put(xpx2, yend.floor() as i32, rfpart(yend) * xgap);
+            put(xpx2, yend.floor() as i32 + 1, fpart(yend) * xgap);
+        }
+
+        for x in xpx1 + 1..xpx2 {
+            put(x, intery.floor() as i32, rfpart(intery));
+            put(x, intery.floor() as i32 + 1, fpart(intery));
+            intery += gradient;
+        }
+
+        self.set_blend_mode(prev);
+    }
+
+    /// Line of any `width`, filled as a single polygon (caps included) so
+    /// translucent colors don't double-blend where pieces meet.
+    fn draw_thick_line_f32(&mut self, a: Vec2, b: Vec2, width: f32, cap: LineCap, color: Color) {
+        let (x1, y1, x2, y2) = (a.x, a.y, b.x, b.y);
+        let hw = width * 0.5;
+        if hw <= 0.0 { return; }
+        let (dx, dy) = (x2 - x1, y2 - y1);
+        let len = (dx * dx + dy * dy).sqrt();
+
+        // unit direction (arbitrary for zero-length lines) and its normal
+        let (ux, uy) = if len > 1e-6 { (dx / len, dy / len) } else { (1.0, 0.0) };
+        let (nx, ny) = (-uy * hw, ux * hw);
+
+        let pts: Vec<Vec2> = match cap {
+            LineCap::Butt if len <= 1e-6 => return,
+            LineCap::Butt | LineCap::Square => {
+                let ext = if cap == LineCap::Square { hw } else { 0.0 };
+                let (ax, ay) = (x1 - ux * ext, y1 - uy * ext);
+                let (bx, by) = (x2 + ux * ext, y2 + uy * ext);
+                vec![
+                    Vec2::new(ax + nx, ay + ny),
+                    Vec2::new(bx + nx, by + ny),
+                    Vec2::new(bx - nx, by - ny),
+                    Vec2::new(ax - nx, ay - ny),
+                ]
+            }
+            LineCap::Round => {
+                // half-circle around each end, enough segments to look round at this size
+                let segs = ((hw * 2.0).ceil() as usize).clamp(4, 32);
+                let base = uy.atan2(ux);
+                let mut pts = Vec::with_capacity(2 * segs + 2);
+                for (cx, cy, start) in [(x2, y2, base - std::f32::consts::FRAC_PI_2), (x1, y1, base + std::f32::consts::FRAC_PI_2)] {
+                    for i in 0..=segs {
+                        let a = start + std::f32::consts::PI * i as f32 / segs as f32;
+                        pts.push(Vec2::new(cx + a.cos() * hw, cy + a.sin() * hw));
+                    }
+                }
+                pts
+            }
+        };
+        self.fill_polygon_f32(&pts, color);
+    }
+}
+
 impl<T: Canvas> CanvasFloatExt for T {}
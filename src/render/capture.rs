@@ -0,0 +1,158 @@
+//! Screenshots and animated GIF clips from framebuffer canvases.
+//! - `Capture::screenshot()` / `record_gif(n)` from code, or bind `Input` actions
+//! - Call `capture_frame(fb, dt)` once per frame after drawing (and post-fx)
+//! - GIF frames are quantized to 256 colors each; delays follow the real `dt`
+//!
+//! Frames are buffered in memory and encoded when the clip is complete.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{ensure, Context};
+
+use super::canvas::Framebuffer;
+use super::codec;
+use crate::input::{Input, Mods};
+
+/// One recorded frame: RGBA8 pixels and how long it was on screen (seconds).
+pub struct GifFrame {
+    pub rgba: Vec<u8>,
+    pub dt: f32,
+}
+
+/// Encode frames as a looping GIF. `speed` is the quantizer speed, 1 (best) to 30 (fastest).
+pub fn encode_gif(width: u32, height: u32, frames: &[GifFrame], speed: i32) -> anyhow::Result<Vec<u8>> {
+    ensure!(width <= u16::MAX as u32 && height <= u16::MAX as u32, "{width}x{height} is too large for GIF");
+    let (w, h) = (width as u16, height as u16);
+    let mut out = Vec::new();
+    {
+        let mut enc = gif::Encoder::new(&mut out, w, h, &[])?;
+        enc.set_repeat(gif::Repeat::Infinite)?;
+        // Round the running time, not each delay, so rounding error doesn't accumulate.
+        let mut t = 0.0f64;
+        for f in frames {
+            ensure!(f.rgba.len() == width as usize * height as usize * 4, "GIF frame size mismatch");
+            let start = (t * 100.0).round() as u16;
+            t += f.dt.max(0.0) as f64;
+            let mut rgba = f.rgba.clone();
+            let mut frame = gif::Frame::from_rgba_speed(w, h, &mut rgba, speed.clamp(1, 30));
+            // most viewers treat delays under 2cs as "slow", so clamp up
+            frame.delay = ((t * 100.0).round() as u16).saturating_sub(start).max(2);
+            enc.write_frame(&frame)?;
+        }
+    }
+    Ok(out)
+}
+
+pub fn save_gif(path: impl AsRef<Path>, width: u32, height: u32, frames: &[GifFrame], speed: i32) -> anyhow::Result<()> {
+    let path = path.as_ref();
+    let bytes = encode_gif(width, height, frames, speed)?;
+    std::fs::write(path, bytes).with_context(|| format!("writing {}", path.display()))
+}
+
+struct Recording {
+    size: (u32, u32),
+    frames: Vec<GifFrame>,
+    remaining: u32,
+}
+
+pub struct Capture {
+    dir: PathBuf,
+    prefix: String,
+    next_id: u32,
+    /// Quantizer speed for GIFs, 1 (best) to 30 (fastest).
+    pub gif_speed: i32,
+    /// Frames recorded by the GIF action.
+    pub gif_frames: u32,
+    screenshot_action: Option<&'static str>,
+    gif_action: Option<&'static str>,
+    pending_screenshot: bool,
+    recording: Option<Recording>,
+}
+
+impl Capture {
+    /// Files go to `dir` as `<prefix>-0001.png`, `<prefix>-0002.gif`, ...
+    pub fn new(dir: impl Into<PathBuf>, prefix: &str) -> Self {
+        Self {
+            dir: dir.into(),
+            prefix: prefix.to_string(),
+            next_id: 1,
+            gif_speed: 10,
+            gif_frames: 120,
+            screenshot_action: None,
+            gif_action: None,
+            pending_screenshot: false,
+            recording: None,
+        }
+    }
+
+    /// Trigger captures from `Input` actions (bound with `Input::bind_action`).
+    pub fn bind_actions(&mut self, screenshot: Option<&'static str>, gif: Option<&'static str>) {
+        self.screenshot_action = screenshot;
+        self.gif_action = gif;
+    }
+
+    /// Check the bound actions; call once per frame after input events.
+    pub fn handle_input(&mut self, input: &Input) {
+        if self.screenshot_action.is_some_and(|a| input.action_just_pressed(a, Mods::empty())) {
+            self.screenshot();
+        }
+        if self.gif_action.is_some_and(|a| input.action_just_pressed(a, Mods::empty())) {
+            let n = self.gif_frames;
+            self.record_gif(n);
+        }
+    }
+
+    /// Save the next captured frame as PNG.
+    pub fn screenshot(&mut self) { self.pending_screenshot = true; }
+
+    /// Record the next `frames` captured frames into a GIF (ignored while already recording).
+    pub fn record_gif(&mut self, frames: u32) {
+        if self.recording.is_none() && frames > 0 {
+            self.recording = Some(Recording { size: (0, 0), frames: Vec::new(), remaining: frames });
+        }
+    }
+
+    pub fn is_recording(&self) -> bool { self.recording.is_some() }
+
+    /// Feed this frame (`dt` = seconds since the previous one). Returns the paths written, if any.
+    pub fn capture_frame(&mut self, fb: &impl Framebuffer, dt: f32) -> anyhow::Result<Vec<PathBuf>> {
+        let mut written = Vec::new();
+        if self.pending_screenshot {
+            self.pending_screenshot = false;
+            let path = self.next_path("png")?;
+            codec::save_frame(&path, fb)?;
+            written.push(path);
+        }
+
+        let Some(rec) = self.recording.as_mut() else { return Ok(written) };
+        let size = fb.size();
+        if rec.frames.is_empty() {
+            rec.size = size;
+        } else if rec.size != size {
+            // canvas resized mid-clip: keep what we have
+            rec.remaining = 0;
+        }
+        if rec.remaining > 0 {
+            // the dt that belongs to a frame is the time until the next one; use the latest as an estimate
+            if let Some(last) = rec.frames.last_mut() { last.dt = dt; }
+            rec.frames.push(GifFrame { rgba: fb.frame().to_vec(), dt });
+            rec.remaining -= 1;
+        }
+        if rec.remaining == 0 {
+            let rec = self.recording.take().unwrap();
+            let path = self.next_path("gif")?;
+            save_gif(&path, rec.size.0, rec.size.1, &rec.frames, self.gif_speed)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+
+    fn next_path(&mut self, ext: &str) -> anyhow::Result<PathBuf> {
+        std::fs::create_dir_all(&self.dir).with_context(|| format!("creating {}", self.dir.display()))?;
+        loop {
+            let path = self.dir.join(format!("{}-{:04}.{ext}", self.prefix, self.next_id));
+            self.next_id += 1;
+            if !path.exists() { return Ok(path); }
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Image file encoding/decoding for RGBA8 frames and `Image`s.
+//! - PNG (lossless, keeps alpha)
+//! - QOI (decode only)
+//! - PPM, binary (P6) or plain ASCII (P3); alpha is dropped on save, opaque on load
+//!
+//! Decoders take `&[u8]`, so `include_bytes!` assets work the same as files.
+
+use std::path::Path;
+
+use anyhow::{Context, bail, ensure};
+
+use super::canvas::Framebuffer;
+use super::image::Image;
+
+// Refuse absurd dimensions before allocating (same cap the QOI spec uses).
+const MAX_PIXELS: u64 = 400_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PpmFormat { Binary, Plain }
+
+fn check_len(width: u32, height: u32, rgba: &[u8]) -> anyhow::Result<()> {
+    ensure!(
+        rgba.len() == width as usize * height as usize * 4,
+        "frame is {} bytes, expected {}x{}x4",
+        rgba.len(), width, height
+    );
+    Ok(())
+}
+
+pub fn encode_png(width: u32, height: u32, rgba: &[u8]) -> anyhow::Result<Vec<u8>> {
+    check_len(width, height, rgba)?;
+    let mut out = Vec::new();
+    {
+        let mut enc = png::Encoder::new(&mut out, width, height);
+        enc.set_color(png::ColorType::Rgba);
+        enc.set_depth(png::BitDepth::Eight);
+        let mut writer = enc.write_header().context("writing PNG header")?;
+        writer.write_image_data(rgba).context("writing PNG data")?;
+    }
+    Ok(out)
+}
+
+pub fn encode_ppm(width: u32, height: u32, rgba: &[u8], format: PpmFormat) -> anyhow::Result<Vec<u8>> {
+    check_len(width, height, rgba)?;
+    let mut out = Vec::with_capacity(width as usize * height as usize * 3 + 32);
+    match format {
+        PpmFormat::Binary => {
+            out.extend_from_slice(format!("P6\n{width} {height}\n255\n").as_bytes());
+            for px in rgba.chunks_exact(4) {
+                out.extend_from_slice(&px[..3]);
+            }
+        }
+        PpmFormat::Plain => {
+            out.extend_from_slice(format!("P3\n{width} {height}\n255\n").as_bytes());
+            // one row per line, wrapped: P3 lines must not exceed 70 characters
+            for row in rgba.chunks_exact(width.max(1) as usize * 4) {
+                let mut len = 0;
+                for v in row.chunks_exact(4).flat_map(|px| &px[..3]) {
+                    let s = v.to_string();
+                    if len > 0 && len + 1 + s.len() > 70 {
+                        out.push(b'\n');
+                        len = 0;
+                    } else if len > 0 {
+                        out.push(b' ');
+                        len += 1;
+                    }
+                    out.extend_from_slice(s.as_bytes());
+                    len += s.len();
+                }
+                out.push(b'\n');
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Writes a PNG or PPM depending on the file extension (`.png`, `.ppm`).
+pub fn save_rgba(path: impl AsRef<Path>, width: u32, height: u32, rgba: &[u8]) -> anyhow::Result<()> {
+    let path = path.as_ref();
+    let ext = path.extension().and_then(|e| e.to_str()).map(|e| e.to_ascii_lowercase());
+    let bytes = match ext.as_deref() {
+        Some("png") => encode_png(width, height, rgba)?,
+        Some("ppm") => encode_ppm(width, height, rgba, PpmFormat::Binary)?,
+        _ => bail!("unsupported image extension: {}", path.display()),
+    };
+    std::fs::write(path, bytes).with_context(|| format!("writing {}", path.display()))
+}
+
+/// Saves the current contents of any framebuffer canvas.
+pub fn save_frame(path: impl AsRef<Path>, fb: &impl Framebuffer) -> anyhow::Result<()> {
+    let (w, h) = fb.size();
+    save_rgba(path, w, h, fb.frame())
+}
+
+/// Decodes a PNG into `(width, height, rgba8)`, expanding palette/gray/RGB to RGBA.
+pub(crate) fn decode_png_rgba(bytes: &[u8]) -> anyhow::Result<(u32, u32, Vec<u8>)> {
+    let mut decoder = png::Decoder::new(bytes);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().context("reading PNG header")?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).context("decoding PNG data")?;
+    buf.truncate(info.buffer_size());
+
+    let rgba = match info.color_type {
+        png::ColorType::Rgba => buf,
+        png::ColorType::Rgb => buf.chunks_exact(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha => buf.chunks_exact(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        png::ColorType::Grayscale => buf.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        png::ColorType::Indexed => bail!("indexed PNG was not expanded"),
+    };
+    Ok((info.width, info.height, rgba))
+}
+
+/* ------------------------------ decoding ------------------------------ */
+
+/// Decode PNG, QOI or PPM, picked by the file's magic bytes.
+pub fn decode_image(bytes: &[u8]) -> anyhow::Result<Image> {
+    if bytes.starts_with(b"\x89PNG") {
+        decode_png(bytes)
+    } else if bytes.starts_with(b"qoif") {
+        decode_qoi(bytes)
+    } else if bytes.starts_with(b"P6") || bytes.starts_with(b"P3") {
+        decode_ppm(bytes)
+    } else {
+        bail!("unrecognized image format (expected PNG, QOI or PPM)")
+    }
+}
+
+/// Read and decode an image file (PNG, QOI or PPM).
+pub fn load_image(path: impl AsRef<Path>) -> anyhow::Result<Image> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    decode_image(&bytes).with_context(|| format!("decoding {}", path.display()))
+}
+
+pub fn decode_png(bytes: &[u8]) -> anyhow::Result<Image> {
+    let (w, h, rgba) = decode_png_rgba(bytes)?;
+    Image::from_rgba(w, h, rgba)
+}
+
+pub fn decode_qoi(bytes: &[u8]) -> anyhow::Result<Image> {
+    const END: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 1];
+    ensure!(bytes.len() >= 14 + END.len(), "QOI: file too short");
+    ensure!(&bytes[..4] == b"qoif", "QOI: bad magic");
+
+    let width = u32::from_be_bytes(bytes[4..8].try_into().unwrap());
+    let height = u32::from_be_bytes(bytes[8..12].try_into().unwrap());
+    let channels = bytes[12];
+    ensure!(width > 0 && height > 0, "QOI: zero-sized image");
+    ensure!(channels == 3 || channels == 4, "QOI: invalid channel count {channels}");
+    ensure!(bytes[13] <= 1, "QOI: invalid colorspace {}", bytes[13]);
+    ensure!((width as u64) * (height as u64) <= MAX_PIXELS, "QOI: {width}x{height} is too large");
+
+    ensure!(bytes.ends_with(&END), "QOI: missing end marker");
+    let data = &bytes[14..bytes.len() - END.len()];
+
+    // every op decodes to at most 62 pixels, so this also catches lying headers early
+    let n = width as usize * height as usize;
+    ensure!(n <= data.len() * 62, "QOI: truncated pixel data");
+    let mut out = Vec::with_capacity(n * 4);
+    let mut index = [[0u8; 4]; 64];
+    let mut px = [0u8, 0, 0, 255];
+    let mut pos = 0;
+    let mut run = 0;
+
+    let take = |count: usize, pos: &mut usize| -> anyhow::Result<&[u8]> {
+        ensure!(*pos + count <= data.len(), "QOI: truncated pixel data");
+        let s = &data[*pos..*pos + count];
+        *pos += count;
+        Ok(s)
+    };
+
+    while out.len() < n * 4 {
+        if run > 0 {
+            run -= 1;
+        } else {
+            let b = take(1, &mut pos)?[0];
+            match b {
+                0xFE => { let s = take(3, &mut pos)?; px[..3].copy_from_slice(s); }
+                0xFF => { let s = take(4, &mut pos)?; px.copy_from_slice(s); }
+                _ => match b >> 6 {
+                    0b00 => px = index[b as usize],
+                    0b01 => {
+                        px[0] = px[0].wrapping_add(((b >> 4) & 3).wrapping_sub(2));
+                        px[1] = px[1].wrapping_add(((b >> 2) & 3).wrapping_sub(2));
+                        px[2] = px[2].wrapping_add((b & 3).wrapping_sub(2));
+                    }
+                    0b10 => {
+                        let b2 = take(1, &mut pos)?[0];
+                        let dg = (b & 0x3F).wrapping_sub(32);
+                        px[0] = px[0].wrapping_add(dg.wrapping_sub(8).wrapping_add(b2 >> 4));
+                        px[1] = px[1].wrapping_add(dg);
+                        px[2] = px[2].wrapping_add(dg.wrapping_sub(8).wrapping_add(b2 & 0x0F));
+                    }
+                    _ => run = (b & 0x3F) as usize, // this pixel plus `run` more
+                },
+            }
+            let hash = (px[0] as usize * 3 + px[1] as usize * 5 + px[2] as usize * 7 + px[3] as usize * 11) % 64;
+            index[hash] = px;
+        }
+        out.extend_from_slice(&px);
+    }
+
+    Image::from_rgba(width, height, out)
+}
+
+/// Binary (P6) or ASCII (P3) PPM; 16-bit samples are scaled down to 8 bits.
+pub fn decode_ppm(bytes: &[u8]) -> anyhow::Result<Image> {
+    let mut pos = 0;
+    let magic = ppm_token(bytes, &mut pos).context("PPM: missing magic")?;
+    let binary = match magic {
+        b"P6" => true,
+        b"P3" => false,
+        other => bail!("PPM: unsupported magic {:?}", String::from_utf8_lossy(other)),
+    };
+
+    let mut header = [0u32; 3];
+    for (v, name) in header.iter_mut().zip(["width", "height", "maxval"]) {
+        let tok = ppm_token(bytes, &mut pos).with_context(|| format!("PPM: missing {name}"))?;
+        *v = std::str::from_utf8(tok).ok().and_then(|s| s.parse().ok())
+            .with_context(|| format!("PPM: invalid {name} {:?}", String::from_utf8_lossy(tok)))?;
+    }
+    let [width, height, maxval] = header;
+    ensure!(width > 0 && height > 0, "PPM: zero-sized image");
+    ensure!((1..=65535).contains(&maxval), "PPM: maxval {maxval} out of range");
+    ensure!((width as u64) * (height as u64) <= MAX_PIXELS, "PPM: {width}x{height} is too large");
+
+    let n = width as usize * height as usize * 3;
+    let scale = |v: u32| -> anyhow::Result<u8> {
+        ensure!(v <= maxval, "PPM: sample {v} exceeds maxval {maxval}");
+        Ok(((v * 255 + maxval / 2) / maxval) as u8)
+    };
+
+    let mut samples = Vec::with_capacity(n);
+    if binary {
+        // exactly one whitespace byte separates the header from the raster
+        pos += 1;
+        let wide = maxval > 255;
+        let need = if wide { n * 2 } else { n };
+        ensure!(bytes.len() >= pos + need, "PPM: truncated pixel data");
+        let raster = &bytes[pos..pos + need];
+        if wide {
+            for s in raster.chunks_exact(2) { samples.push(scale(u16::from_be_bytes([s[0], s[1]]) as u32)?); }
+        } else {
+            for &s in raster { samples.push(scale(s as u32)?); }
+        }
+    } else {
+        for _ in 0..n {
+            let tok = ppm_token(bytes, &mut pos).context("PPM: truncated pixel data")?;
+            let v = std::str::from_utf8(tok).ok().and_then(|s| s.parse().ok())
+                .with_context(|| format!("PPM: invalid sample {:?}", String::from_utf8_lossy(tok)))?;
+            samples.push(scale(v)?);
+        }
+    }
+
+    let rgba = samples.chunks_exact(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect();
+    Image::from_rgba(width, height, rgba)
+}
+
+// Next whitespace-separated header token, skipping `#` comments.
+fn ppm_token<'a>(bytes: &'a [u8], pos: &mut usize) -> Option<&'a [u8]> {
+    loop {
+        while *pos < bytes.len() && bytes[*pos].is_ascii_whitespace() { *pos += 1; }
+        if *pos < bytes.len() && bytes[*pos] == b'#' {
+            while *pos < bytes.len() && bytes[*pos] != b'\n' { *pos += 1; }
+        } else {
+            break;
+        }
+    }
+    let start = *pos;
+    while *pos < bytes.len() && !bytes[*pos].is_ascii_whitespace() { *pos += 1; }
+    (*pos > start).then(|| &bytes[start..*pos])
+}
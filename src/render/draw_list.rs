@@ -0,0 +1,314 @@
+//! Recorded draw commands.
+//! - `DrawList` implements `Canvas` and records instead of drawing
+//! - Each command keeps a z-layer, the blend mode and clip active when recorded
+//! - `sort()` orders by z (stable), `replay()` draws onto any backend
+//! - `to_text()` / `diff()` for debugging and frame comparisons in tests
+//!
+//! Images are copied (just the used region) when recorded, so a list owns
+//! everything it needs and can outlive the sprites it was built from.
+
+use std::fmt;
+
+use crate::math::Vec2;
+
+use super::canvas::{BlendMode, BlitParams, Canvas, Color, IRect, ImageTransform};
+use super::image::Image;
+use super::raster::ClipStack;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DrawCmd {
+    Clear(Color),
+    FillRect(IRect, Color),
+    Line { from: (i32, i32), to: (i32, i32), color: Color },
+    Circle { center: (i32, i32), radius: i32, color: Color },
+    FillCircle { center: (i32, i32), radius: i32, color: Color },
+    Ellipse { center: (i32, i32), rx: i32, ry: i32, color: Color },
+    FillEllipse { center: (i32, i32), rx: i32, ry: i32, color: Color },
+    FillRoundedRect { rect: IRect, radius: i32, color: Color },
+    FillPolygon { points: Vec<(i32, i32)>, color: Color },
+    Image { image: Image, x: i32, y: i32, params: BlitParams },
+    ImageTransformed { image: Image, xf: ImageTransform, params: BlitParams },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DrawItem {
+    pub z: i32,
+    /// Recording order; keeps sorting stable and replay deterministic.
+    pub seq: u32,
+    pub blend: BlendMode,
+    pub clip: IRect,
+    pub cmd: DrawCmd,
+}
+
+impl DrawItem {
+    // Same drawing, wherever it was recorded.
+    fn same_as(&self, other: &DrawItem) -> bool {
+        self.z == other.z && self.blend == other.blend && self.clip == other.clip && self.cmd == other.cmd
+    }
+}
+
+pub struct DrawList {
+    width: u32,
+    height: u32,
+    items: Vec<DrawItem>,
+    z: i32,
+    blend: BlendMode,
+    clips: ClipStack,
+}
+
+impl DrawList {
+    /// Record for a `width x height` target (used for `size()` and the default clip).
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height, items: Vec::new(), z: 0, blend: BlendMode::default(), clips: ClipStack::default() }
+    }
+
+    /// z-layer for commands recorded from now on; higher draws later.
+    pub fn set_z(&mut self, z: i32) { self.z = z; }
+    pub fn z(&self) -> i32 { self.z }
+
+    /// Record `f` on layer `z`, restoring the previous layer after.
+    pub fn with_z<R>(&mut self, z: i32, f: impl FnOnce(&mut Self) -> R) -> R {
+        let prev = self.z;
+        self.z = z;
+        let r = f(self);
+        self.z = prev;
+        r
+    }
+
+    pub fn items(&self) -> &[DrawItem] { &self.items }
+    pub fn len(&self) -> usize { self.items.len() }
+    pub fn is_empty(&self) -> bool { self.items.is_empty() }
+
+    /// Drop all commands and reset z, blend and clip for the next frame.
+    pub fn reset(&mut self) {
+        self.items.clear();
+        self.z = 0;
+        self.blend = BlendMode::default();
+        self.clips = ClipStack::default();
+    }
+
+    /// Order by z; commands on the same layer keep their recording order.
+    pub fn sort(&mut self) { self.items.sort_by_key(|it| (it.z, it.seq)); }
+
+    /// Draw every command onto `dst` in list order (call `sort()` first for z-order).
+    /// Each command gets its recorded blend mode and clip; `dst`'s blend mode is restored after.
+    pub fn replay(&self, dst: &mut impl Canvas) {
+        let prev = dst.blend_mode();
+        for it in &self.items {
+            dst.set_blend_mode(it.blend);
+            dst.push_clip(it.clip);
+            match &it.cmd {
+                DrawCmd::Clear(c) => dst.clear(*c),
+                DrawCmd::FillRect(r, c) => dst.fill_rect(r.x, r.y, r.w, r.h, *c),
+                DrawCmd::Line { from, to, color } => dst.draw_line(from.0, from.1, to.0, to.1, *color),
+                DrawCmd::Circle { center, radius, color } => dst.draw_circle(center.0, center.1, *radius, *color),
+                DrawCmd::FillCircle { center, radius, color } => dst.fill_circle(center.0, center.1, *radius, *color),
+                DrawCmd::Ellipse { center, rx, ry, color } => dst.draw_ellipse(center.0, center.1, *rx, *ry, *color),
+                DrawCmd::FillEllipse { center, rx, ry, color } => dst.fill_ellipse(center.0, center.1, *rx, *ry, *color),
+                DrawCmd::FillRoundedRect { rect, radius, color } => {
+                    dst.fill_rounded_rect(rect.x, rect.y, rect.w, rect.h, *radius, *color)
+                }
+                DrawCmd::FillPolygon { points, color } => dst.fill_polygon(points, *color),
+                DrawCmd::Image { image, x, y, params } => dst.draw_image_region(image, image.bounds(), *x, *y, *params),
+                DrawCmd::ImageTransformed { image, xf, params } => {
+                    dst.draw_image_transformed(image, image.bounds(), *xf, *params)
+                }
+            }
+            dst.pop_clip();
+        }
+        dst.set_blend_mode(prev);
+    }
+
+    /// One line per command, e.g. `z=2 Alpha clip=0,0,320,180 FillRect 8,8 16x16 #ff8000ff`.
+    pub fn to_text(&self) -> String {
+        self.items.iter().map(|it| format!("{it}\n")).collect()
+    }
+
+    /// Human-readable differences against `other` (`-` = only in self, `+` = only in other,
+    /// `#n` = index in that list). Items are aligned by longest common subsequence, so one
+    /// inserted command shows up as a single `+` line; `seq` is ignored.
+    /// Empty when both lists record the same frame.
+    pub fn diff(&self, other: &DrawList) -> Vec<String> {
+        let (a, b) = (&self.items[..], &other.items[..]);
+        let pre = a.iter().zip(b).take_while(|(x, y)| x.same_as(y)).count();
+        let suf = a[pre..].iter().rev().zip(b[pre..].iter().rev()).take_while(|(x, y)| x.same_as(y)).count();
+        let (a, b) = (&a[pre..a.len() - suf], &b[pre..b.len() - suf]);
+
+        // lcs[i * w + j] = LCS length of a[i..] and b[j..]
+        let w = b.len() + 1;
+        let mut lcs = vec![0u32; (a.len() + 1) * w];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i * w + j] = if a[i].same_as(&b[j]) {
+                    lcs[(i + 1) * w + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * w + j].max(lcs[i * w + j + 1])
+                };
+            }
+        }
+
+        let mut out = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            if i < a.len() && j < b.len() && a[i].same_as(&b[j]) {
+                i += 1;
+                j += 1;
+            } else if j == b.len() || (i < a.len() && lcs[(i + 1) * w + j] >= lcs[i * w + j + 1]) {
+                out.push(format!("#{} - {}", pre + i, a[i]));
+                i += 1;
+            } else {
+                out.push(format!("#{} + {}", pre + j, b[j]));
+                j += 1;
+            }
+        }
+        out
+    }
+
+    fn push(&mut self, cmd: DrawCmd) {
+        let seq = self.items.len() as u32;
+        let clip = self.clip_rect();
+        if clip.is_empty() { return; }
+        self.items.push(DrawItem { z: self.z, seq, blend: self.blend, clip, cmd });
+    }
+
+    fn bounds(&self) -> IRect { IRect::new(0, 0, self.width as i32, self.height as i32) }
+}
+
+impl Canvas for DrawList {
+    fn size(&self) -> (u32, u32) { (self.width, self.height) }
+    fn clear(&mut self, color: Color) { self.push(DrawCmd::Clear(color)); }
+
+    fn fill_rect(&mut self, x: i32, y: i32, w: i32, h: i32, color: Color) {
+        if w <= 0 || h <= 0 { return; }
+        self.push(DrawCmd::FillRect(IRect::new(x, y, w, h), color));
+    }
+
+    /// Nothing to show; use `replay`.
+    fn present(&mut self) -> Result<(), String> { Ok(()) }
+
+    fn blend_mode(&self) -> BlendMode { self.blend }
+    fn set_blend_mode(&mut self, mode: BlendMode) { self.blend = mode; }
+
+    fn push_clip(&mut self, rect: IRect) { self.clips.push(rect, self.bounds()); }
+    fn pop_clip(&mut self) { self.clips.pop(); }
+    fn clip_rect(&self) -> IRect { self.clips.current(self.bounds()) }
+
+    fn draw_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: Color) {
+        self.push(DrawCmd::Line { from: (x1, y1), to: (x2, y2), color });
+    }
+
+    fn draw_image_region(&mut self, image: &Image, src: IRect, x: i32, y: i32, params: BlitParams) {
+        // keep the destination offset of a region that was clamped to the image
+        let r = src.intersect(&image.bounds());
+        if r.is_empty() { return; }
+        let (x, y) = (x + r.x - src.x, y + r.y - src.y);
+        self.push(DrawCmd::Image { image: image.crop(r), x, y, params });
+    }
+
+    fn draw_image_transformed(&mut self, image: &Image, src: IRect, xf: ImageTransform, params: BlitParams) {
+        let r = src.intersect(&image.bounds());
+        if r.is_empty() { return; }
+        let pivot = Vec2::new(xf.pivot.x - (r.x - src.x) as f32, xf.pivot.y - (r.y - src.y) as f32);
+        self.push(DrawCmd::ImageTransformed { image: image.crop(r), xf: ImageTransform { pivot, ..xf }, params });
+    }
+
+    fn draw_circle(&mut self, cx: i32, cy: i32, radius: i32, color: Color) {
+        self.push(DrawCmd::Circle { center: (cx, cy), radius, color });
+    }
+
+    fn fill_circle(&mut self, cx: i32, cy: i32, radius: i32, color: Color) {
+        self.push(DrawCmd::FillCircle { center: (cx, cy), radius, color });
+    }
+
+    fn draw_ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, color: Color) {
+        self.push(DrawCmd::Ellipse { center: (cx, cy), rx, ry, color });
+    }
+
+    fn fill_ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, color: Color) {
+        self.push(DrawCmd::FillEllipse { center: (cx, cy), rx, ry, color });
+    }
+
+    fn fill_rounded_rect(&mut self, x: i32, y: i32, w: i32, h: i32, radius: i32, color: Color) {
+        self.push(DrawCmd::FillRoundedRect { rect: IRect::new(x, y, w, h), radius, color });
+    }
+
+    fn fill_polygon(&mut self, points: &[(i32, i32)], color: Color) {
+        self.push(DrawCmd::FillPolygon { points: points.to_vec(), color });
+    }
+}
+
+// ---- text form ----
+
+struct Hex(Color);
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Color(r, g, b, a) = self.0;
+        write!(f, "#{r:02x}{g:02x}{b:02x}{a:02x}")
+    }
+}
+
+/// Size plus an FNV-1a hash of the pixels, so different sprites show up in diffs.
+struct ImageId<'a>(&'a Image);
+
+impl fmt::Display for ImageId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hash = self.0.pixels().iter().fold(0x811c9dc5u32, |h, &b| (h ^ b as u32).wrapping_mul(0x01000193));
+        write!(f, "{}x{}:{hash:08x}", self.0.width(), self.0.height())
+    }
+}
+
+fn fmt_params(f: &mut fmt::Formatter<'_>, p: &BlitParams) -> fmt::Result {
+    if p.flip_x { write!(f, " flip_x")?; }
+    if p.flip_y { write!(f, " flip_y")?; }
+    if p.tint != Color::WHITE { write!(f, " tint={}", Hex(p.tint))?; }
+    if p.alpha != 255 { write!(f, " alpha={}", p.alpha)?; }
+    Ok(())
+}
+
+impl fmt::Display for DrawItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = self.clip;
+        write!(f, "z={} {:?} clip={},{},{},{} ", self.z, self.blend, c.x, c.y, c.w, c.h)?;
+        match &self.cmd {
+            DrawCmd::Clear(col) => write!(f, "Clear {}", Hex(*col)),
+            DrawCmd::FillRect(r, col) => write!(f, "FillRect {},{} {}x{} {}", r.x, r.y, r.w, r.h, Hex(*col)),
+            DrawCmd::Line { from, to, color } => {
+                write!(f, "Line {},{} -> {},{} {}", from.0, from.1, to.0, to.1, Hex(*color))
+            }
+            DrawCmd::Circle { center, radius, color } => {
+                write!(f, "Circle {},{} r={} {}", center.0, center.1, radius, Hex(*color))
+            }
+            DrawCmd::FillCircle { center, radius, color } => {
+                write!(f, "FillCircle {},{} r={} {}", center.0, center.1, radius, Hex(*color))
+            }
+            DrawCmd::Ellipse { center, rx, ry, color } => {
+                write!(f, "Ellipse {},{} {}x{} {}", center.0, center.1, rx, ry, Hex(*color))
+            }
+            DrawCmd::FillEllipse { center, rx, ry, color } => {
+                write!(f, "FillEllipse {},{} {}x{} {}", center.0, center.1, rx, ry, Hex(*color))
+            }
+            DrawCmd::FillRoundedRect { rect, radius, color } => {
+                write!(f, "FillRoundedRect {},{} {}x{} r={} {}", rect.x, rect.y, rect.w, rect.h, radius, Hex(*color))
+            }
+            DrawCmd::FillPolygon { points, color } => {
+                write!(f, "FillPolygon")?;
+                for (x, y) in points { write!(f, " {x},{y}")?; }
+                write!(f, " {}", Hex(*color))
+            }
+            DrawCmd::Image { image, x, y, params } => {
+                write!(f, "Image {} at {},{}", ImageId(image), x, y)?;
+                fmt_params(f, params)
+            }
+            DrawCmd::ImageTransformed { image, xf, params } => {
+                write!(
+                    f,
+                    "ImageTransformed {} at {},{} rot={} scale={},{} pivot={},{} {:?}",
+                    ImageId(image), xf.pos.x, xf.pos.y, xf.rotation, xf.scale.x, xf.scale.y,
+                    xf.pivot.x, xf.pivot.y, xf.sampling
+                )?;
+                fmt_params(f, params)
+            }
+        }
+    }
+}
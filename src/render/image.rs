@@ -0,0 +1,110 @@
+//! Owned RGBA8 image (sprites, sprite sheets, offscreen frames).
+
+use anyhow::ensure;
+
+use super::canvas::{Color, IRect};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Transparent black image.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height, pixels: vec![0; width as usize * height as usize * 4] }
+    }
+
+    /// Wrap row-major RGBA8 bytes; the length must be `width * height * 4`.
+    pub fn from_rgba(width: u32, height: u32, pixels: Vec<u8>) -> anyhow::Result<Self> {
+        ensure!(
+            pixels.len() == width as usize * height as usize * 4,
+            "image data is {} bytes, expected {}x{}x4",
+            pixels.len(), width, height
+        );
+        Ok(Self { width, height, pixels })
+    }
+
+    /// Build an image by evaluating `f(x, y)` for every pixel.
+    pub fn from_fn(width: u32, height: u32, mut f: impl FnMut(u32, u32) -> Color) -> Self {
+        let mut img = Self::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let Color(r, g, b, a) = f(x, y);
+                let i = (y as usize * width as usize + x as usize) * 4;
+                img.pixels[i..i + 4].copy_from_slice(&[r, g, b, a]);
+            }
+        }
+        img
+    }
+
+    pub fn width(&self) -> u32 { self.width }
+    pub fn height(&self) -> u32 { self.height }
+    pub fn size(&self) -> (u32, u32) { (self.width, self.height) }
+    pub fn bounds(&self) -> IRect { IRect::new(0, 0, self.width as i32, self.height as i32) }
+
+    pub fn pixels(&self) -> &[u8] { &self.pixels }
+    pub fn pixels_mut(&mut self) -> &mut [u8] { &mut self.pixels }
+    pub fn into_pixels(self) -> Vec<u8> { self.pixels }
+
+    pub fn pixel(&self, x: i32, y: i32) -> Option<Color> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 { return None; }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        let p = &self.pixels[i..i + 4];
+        Some(Color(p[0], p[1], p[2], p[3]))
+    }
+
+    pub fn set_pixel(&mut self, x: i32, y: i32, color: Color) {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 { return; }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        self.pixels[i..i + 4].copy_from_slice(&[color.0, color.1, color.2, color.3]);
+    }
+
+    /// Copy of `region` (clamped to the image) as a new image.
+    pub fn crop(&self, region: IRect) -> Image {
+        let r = region.intersect(&self.bounds());
+        let mut out = Image::new(r.w as u32, r.h as u32);
+        let row = r.w as usize * 4;
+        for y in 0..r.h as usize {
+            let s = ((r.y as usize + y) * self.width as usize + r.x as usize) * 4;
+            out.pixels[y * row..(y + 1) * row].copy_from_slice(&self.pixels[s..s + row]);
+        }
+        out
+    }
+
+    /// Bilinear sample at `(u, v)` in `region`-local pixel units (texel centers at +0.5),
+    /// clamped to the region's edges. Interpolates premultiplied to avoid dark fringes.
+    pub fn sample_bilinear(&self, region: IRect, u: f32, v: f32) -> Color {
+        let fx = (u - 0.5).clamp(0.0, (region.w - 1) as f32);
+        let fy = (v - 0.5).clamp(0.0, (region.h - 1) as f32);
+        let (x0, y0) = (fx as i32, fy as i32);
+        let (x1, y1) = ((x0 + 1).min(region.w - 1), (y0 + 1).min(region.h - 1));
+        let (tx, ty) = (fx - x0 as f32, fy - y0 as f32);
+
+        let texel = |x: i32, y: i32| {
+            let i = ((region.y + y) as usize * self.width as usize + (region.x + x) as usize) * 4;
+            let p = &self.pixels[i..i + 4];
+            let a = p[3] as f32 / 255.0;
+            [p[0] as f32 * a, p[1] as f32 * a, p[2] as f32 * a, p[3] as f32]
+        };
+        let (c00, c10, c01, c11) = (texel(x0, y0), texel(x1, y0), texel(x0, y1), texel(x1, y1));
+
+        let mut out = [0.0f32; 4];
+        for i in 0..4 {
+            let top = c00[i] + (c10[i] - c00[i]) * tx;
+            let bot = c01[i] + (c11[i] - c01[i]) * tx;
+            out[i] = top + (bot - top) * ty;
+        }
+        let a = out[3];
+        if a <= 0.0 { return Color(0, 0, 0, 0); }
+        let un = 255.0 / a;
+        Color(
+            (out[0] * un).round().min(255.0) as u8,
+            (out[1] * un).round().min(255.0) as u8,
+            (out[2] * un).round().min(255.0) as u8,
+            a.round() as u8,
+        )
+    }
+}
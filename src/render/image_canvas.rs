@@ -0,0 +1,87 @@
+//! Headless canvas backed by an owned RGBA8 `Image`.
+//! - No window, no GPU: works in CI and server-side tools
+//! - `present()` is a no-op unless a frame callback is installed
+
+use super::canvas::{BlendMode, BlitParams, Canvas, Color, Framebuffer, IRect, ImageTransform};
+use super::image::Image;
+use super::raster::{ClipStack, Raster};
+
+type PresentFn = Box<dyn FnMut(&[u8], u32, u32) -> Result<(), String>>;
+
+pub struct ImageCanvas {
+    image: Image,
+    blend: BlendMode,
+    clips: ClipStack,
+    on_present: Option<PresentFn>,
+}
+
+impl ImageCanvas {
+    /// New canvas cleared to transparent black.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self::from_image(Image::new(width, height))
+    }
+
+    /// Draw on top of an existing image.
+    pub fn from_image(image: Image) -> Self {
+        Self { image, blend: BlendMode::default(), clips: ClipStack::default(), on_present: None }
+    }
+
+    /// Called with `(frame, width, height)` every time `present()` runs.
+    pub fn set_present_callback(
+        &mut self,
+        f: impl FnMut(&[u8], u32, u32) -> Result<(), String> + 'static,
+    ) {
+        self.on_present = Some(Box::new(f));
+    }
+
+    /// Read back a single pixel; `None` if out of bounds.
+    pub fn pixel(&self, x: i32, y: i32) -> Option<Color> { self.image.pixel(x, y) }
+
+    /// The pixels drawn so far, e.g. to blit this canvas onto another one.
+    pub fn image(&self) -> &Image { &self.image }
+    pub fn into_image(self) -> Image { self.image }
+
+    fn raster(&mut self) -> Raster<'_> {
+        let width = self.image.width();
+        let clip = self.clip_rect();
+        Raster { frame: self.image.pixels_mut(), width, blend: self.blend, clip }
+    }
+}
+
+impl Canvas for ImageCanvas {
+    fn size(&self) -> (u32, u32) { self.image.size() }
+
+    fn clear(&mut self, color: Color) { self.raster().clear(color); }
+
+    fn fill_rect(&mut self, x: i32, y: i32, w: i32, h: i32, color: Color) {
+        self.raster().fill_rect(x, y, w, h, color);
+    }
+
+    fn blend_mode(&self) -> BlendMode { self.blend }
+    fn set_blend_mode(&mut self, mode: BlendMode) { self.blend = mode; }
+
+    fn push_clip(&mut self, rect: IRect) { self.clips.push(rect, self.image.bounds()); }
+    fn pop_clip(&mut self) { self.clips.pop(); }
+    fn clip_rect(&self) -> IRect { self.clips.current(self.image.bounds()) }
+
+    fn draw_image_region(&mut self, image: &Image, src: IRect, x: i32, y: i32, params: BlitParams) {
+        self.raster().blit(image, src, x, y, params);
+    }
+
+    fn draw_image_transformed(&mut self, image: &Image, src: IRect, xf: ImageTransform, params: BlitParams) {
+        self.raster().blit_transformed(image, src, &xf, &params);
+    }
+
+    fn present(&mut self) -> Result<(), String> {
+        let (w, h) = self.image.size();
+        match self.on_present.as_mut() {
+            Some(f) => f(self.image.pixels(), w, h),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Framebuffer for ImageCanvas {
+    fn frame(&self) -> &[u8] { self.image.pixels() }
+    fn frame_mut(&mut self) -> &mut [u8] { self.image.pixels_mut() }
+}
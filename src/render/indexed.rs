@@ -0,0 +1,282 @@
+//! Palette-indexed canvas (retro 8-bit style).
+//! - One `u8` per pixel into a 256-entry `Palette`
+//! - Resolved to RGBA on `present()`; swap palettes at runtime without redrawing
+//! - Color cycling ranges advanced with `update(dt)`
+//! - Palettes load from Lospec `.hex` and GIMP `.gpl` files
+//!
+//! Colors passed to the regular `Canvas` calls map to the nearest palette entry;
+//! pixels with alpha below 128 are skipped. Blend modes are tracked but have no effect.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{bail, Context};
+
+use super::canvas::{BlendMode, BlitParams, Canvas, Color, IRect};
+use super::image::Image;
+use super::raster::ClipStack;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Palette {
+    colors: [Color; 256],
+    /// Entries actually defined; nearest-color search only looks at these.
+    len: usize,
+}
+
+impl Palette {
+    /// Up to 256 colors; the rest of the table is opaque black.
+    pub fn new(colors: &[Color]) -> Self {
+        let mut table = [Color(0, 0, 0, 255); 256];
+        let len = colors.len().min(256);
+        table[..len].copy_from_slice(&colors[..len]);
+        Self { colors: table, len: len.max(1) }
+    }
+
+    pub fn get(&self, index: u8) -> Color { self.colors[index as usize] }
+    pub fn set(&mut self, index: u8, color: Color) {
+        self.colors[index as usize] = color;
+        self.len = self.len.max(index as usize + 1);
+    }
+    /// The defined entries (at least one).
+    pub fn colors(&self) -> &[Color] { &self.colors[..self.len] }
+
+    /// Index of the closest defined entry (squared RGB distance).
+    pub fn nearest(&self, c: Color) -> u8 {
+        let mut best = (0usize, u32::MAX);
+        for (i, p) in self.colors().iter().enumerate() {
+            let d = |a: u8, b: u8| (a as i32 - b as i32).unsigned_abs().pow(2);
+            let dist = d(p.0, c.0) + d(p.1, c.1) + d(p.2, c.2);
+            if dist < best.1 { best = (i, dist); }
+        }
+        best.0 as u8
+    }
+
+    /// Lospec `.hex`: one `rrggbb` (or `rrggbbaa`) per line.
+    pub fn from_hex(text: &str) -> anyhow::Result<Self> {
+        let mut colors = Vec::new();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim().trim_start_matches('#');
+            if line.is_empty() { continue; }
+            colors.push(parse_hex(line).with_context(|| format!("line {}: bad color {line:?}", n + 1))?);
+        }
+        check_count(colors.len())?;
+        Ok(Self::new(&colors))
+    }
+
+    /// GIMP `.gpl`: `GIMP Palette` header, then `r g b [name]` lines.
+    pub fn from_gpl(text: &str) -> anyhow::Result<Self> {
+        let mut lines = text.lines();
+        if lines.next().map(str::trim) != Some("GIMP Palette") { bail!("missing 'GIMP Palette' header"); }
+        let mut colors = Vec::new();
+        for (n, line) in lines.enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') { continue; }
+            // header keys (`Name:`, `Columns:`); color names may contain colons too
+            let key = line.split_whitespace().next().is_some_and(|t| t.ends_with(':'));
+            if key && !line.starts_with(|c: char| c.is_ascii_digit()) { continue; }
+            let mut it = line.split_whitespace().map(str::parse::<u8>);
+            let mut next = || -> anyhow::Result<u8> {
+                it.next()
+                    .with_context(|| format!("line {}: expected 3 channels", n + 2))?
+                    .with_context(|| format!("line {}: bad channel", n + 2))
+            };
+            colors.push(Color(next()?, next()?, next()?, 255));
+        }
+        check_count(colors.len())?;
+        Ok(Self::new(&colors))
+    }
+
+    /// Load by extension (`.hex` or `.gpl`).
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_ascii_lowercase();
+        match ext.as_str() {
+            "hex" => Self::from_hex(&text),
+            "gpl" => Self::from_gpl(&text),
+            _ => bail!("unsupported palette format: {}", path.display()),
+        }
+        .with_context(|| format!("parsing {}", path.display()))
+    }
+}
+
+fn parse_hex(s: &str) -> anyhow::Result<Color> {
+    let byte = |i: usize| u8::from_str_radix(s.get(i..i + 2).unwrap_or("x"), 16);
+    match s.len() {
+        6 => Ok(Color(byte(0)?, byte(2)?, byte(4)?, 255)),
+        8 => Ok(Color(byte(0)?, byte(2)?, byte(4)?, byte(6)?)),
+        _ => bail!("expected rrggbb or rrggbbaa"),
+    }
+}
+
+fn check_count(n: usize) -> anyhow::Result<()> {
+    if n == 0 { bail!("palette has no colors"); }
+    if n > 256 { bail!("palette has {n} colors, at most 256 supported"); }
+    Ok(())
+}
+
+/// Rotates entries `start..=end` by `speed` steps per second (negative = backwards).
+#[derive(Clone, Copy, Debug)]
+pub struct PaletteCycle {
+    pub start: u8,
+    pub end: u8,
+    pub speed: f32,
+}
+
+type PresentFn = Box<dyn FnMut(&[u8], u32, u32) -> Result<(), String>>;
+
+pub struct IndexedCanvas {
+    width: u32,
+    height: u32,
+    indices: Vec<u8>,
+    palette: Palette,
+    cycles: Vec<PaletteCycle>,
+    time: f32,
+    blend: BlendMode,
+    clips: ClipStack,
+    nearest_cache: HashMap<Color, u8>,
+    resolved: Image,
+    on_present: Option<PresentFn>,
+}
+
+impl IndexedCanvas {
+    /// New canvas filled with index 0.
+    pub fn new(width: u32, height: u32, palette: Palette) -> Self {
+        Self {
+            width,
+            height,
+            indices: vec![0; width as usize * height as usize],
+            palette,
+            cycles: Vec::new(),
+            time: 0.0,
+            blend: BlendMode::default(),
+            clips: ClipStack::default(),
+            nearest_cache: HashMap::new(),
+            resolved: Image::new(width, height),
+            on_present: None,
+        }
+    }
+
+    /// Called with the resolved RGBA `(frame, width, height)` every time `present()` runs.
+    pub fn set_present_callback(
+        &mut self,
+        f: impl FnMut(&[u8], u32, u32) -> Result<(), String> + 'static,
+    ) {
+        self.on_present = Some(Box::new(f));
+    }
+
+    pub fn palette(&self) -> &Palette { &self.palette }
+
+    /// Swap the palette; existing pixels keep their indices and change color.
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+        self.nearest_cache.clear();
+    }
+
+    pub fn add_cycle(&mut self, cycle: PaletteCycle) { self.cycles.push(cycle); }
+    pub fn clear_cycles(&mut self) { self.cycles.clear(); }
+
+    /// Advance color cycling.
+    pub fn update(&mut self, dt: f32) { self.time += dt; }
+
+    pub fn indices(&self) -> &[u8] { &self.indices }
+    pub fn index(&self, x: i32, y: i32) -> Option<u8> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 { return None; }
+        Some(self.indices[y as usize * self.width as usize + x as usize])
+    }
+
+    /// Fill with a palette index directly (respects the clip).
+    pub fn fill_rect_index(&mut self, x: i32, y: i32, w: i32, h: i32, index: u8) {
+        let r = IRect::new(x, y, w, h).intersect(&self.clip_rect());
+        for yy in r.y..r.y + r.h {
+            let row = yy as usize * self.width as usize;
+            self.indices[row + r.x as usize..row + (r.x + r.w) as usize].fill(index);
+        }
+    }
+
+    pub fn set_index(&mut self, x: i32, y: i32, index: u8) { self.fill_rect_index(x, y, 1, 1, index); }
+
+    /// Palette with the cycling ranges applied at the current time.
+    pub fn display_palette(&self) -> Palette {
+        let mut out = self.palette.clone();
+        for c in &self.cycles {
+            let (lo, hi) = (c.start.min(c.end) as usize, c.start.max(c.end) as usize);
+            let n = (hi - lo + 1) as i64;
+            let shift = ((self.time * c.speed).floor() as i64).rem_euclid(n) as usize;
+            for i in 0..n as usize {
+                out.colors[lo + (i + shift) % n as usize] = self.palette.colors[lo + i];
+            }
+        }
+        out
+    }
+
+    /// Resolve indices to RGBA with the display palette (also done by `present()`).
+    pub fn resolve(&mut self) -> &Image {
+        let pal = self.display_palette();
+        for (px, &i) in self.resolved.pixels_mut().chunks_exact_mut(4).zip(&self.indices) {
+            let Color(r, g, b, a) = pal.colors[i as usize];
+            px.copy_from_slice(&[r, g, b, a]);
+        }
+        &self.resolved
+    }
+
+    /// The frame as of the last `resolve()` / `present()`, e.g. to blit onto a `PixelsCanvas`.
+    pub fn resolved(&self) -> &Image { &self.resolved }
+
+    fn map_color(&mut self, c: Color) -> Option<u8> {
+        if c.3 < 128 { return None; }
+        let key = Color(c.0, c.1, c.2, 255);
+        let pal = &self.palette;
+        Some(*self.nearest_cache.entry(key).or_insert_with(|| pal.nearest(key)))
+    }
+
+    fn bounds(&self) -> IRect { IRect::new(0, 0, self.width as i32, self.height as i32) }
+}
+
+impl Canvas for IndexedCanvas {
+    fn size(&self) -> (u32, u32) { (self.width, self.height) }
+
+    fn clear(&mut self, color: Color) {
+        let i = self.map_color(Color(color.0, color.1, color.2, 255)).unwrap_or(0);
+        let (w, h) = (self.width as i32, self.height as i32);
+        self.fill_rect_index(0, 0, w, h, i);
+    }
+
+    fn fill_rect(&mut self, x: i32, y: i32, w: i32, h: i32, color: Color) {
+        if let Some(i) = self.map_color(color) { self.fill_rect_index(x, y, w, h, i); }
+    }
+
+    fn blend_mode(&self) -> BlendMode { self.blend }
+    fn set_blend_mode(&mut self, mode: BlendMode) { self.blend = mode; }
+
+    fn push_clip(&mut self, rect: IRect) { self.clips.push(rect, self.bounds()); }
+    fn pop_clip(&mut self) { self.clips.pop(); }
+    fn clip_rect(&self) -> IRect { self.clips.current(self.bounds()) }
+
+    fn draw_image_region(&mut self, image: &Image, src: IRect, x: i32, y: i32, params: BlitParams) {
+        let clip = self.clip_rect();
+        for sy in 0..src.h {
+            let dy = y + sy;
+            if dy < clip.y || dy >= clip.y + clip.h { continue; }
+            for sx in 0..src.w {
+                let dx = x + sx;
+                if dx < clip.x || dx >= clip.x + clip.w { continue; }
+                let ix = if params.flip_x { src.x + src.w - 1 - sx } else { src.x + sx };
+                let iy = if params.flip_y { src.y + src.h - 1 - sy } else { src.y + sy };
+                let Some(c) = image.pixel(ix, iy) else { continue };
+                if let Some(i) = self.map_color(params.apply(c)) {
+                    self.indices[dy as usize * self.width as usize + dx as usize] = i;
+                }
+            }
+        }
+    }
+
+    fn present(&mut self) -> Result<(), String> {
+        self.resolve();
+        let (w, h) = (self.width, self.height);
+        match self.on_present.as_mut() {
+            Some(f) => f(self.resolved.pixels(), w, h),
+            None => Ok(()),
+        }
+    }
+}
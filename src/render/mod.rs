@@ -1,4 +1,15 @@
 pub mod canvas;
+pub mod capture;
+pub mod codec;
+pub mod draw_list;
+pub mod image;
+pub mod image_canvas;
+pub mod indexed;
+pub mod postfx;
+pub(crate) mod raster;
+pub mod scale;
+pub mod snapshot;
+pub mod target;
 
 #[cfg(feature = "render-pixels")]
 pub mod pixels_impl;
@@ -1,24 +1,45 @@
+//! Windowed canvas on top of `pixels` (wgpu).
+//! - Fixed logical resolution, resizable window, letterboxed to keep the aspect ratio
+//! - `ScaleMode::Integer` (crisp 2x/3x/...) or `ScaleMode::Fractional` (fills the window)
+//! - `window_to_canvas` maps physical cursor positions for `Input::set_mouse_pos`
+//! - Placement math (`render::scale`) is pure and available without the feature
+
 #[cfg(feature = "render-pixels")]
 use pixels::{Pixels, SurfaceTexture};
 
 #[cfg(feature = "render-pixels")]
-use winit::{event_loop::EventLoop, window::WindowBuilder, dpi::LogicalSize};
+use winit::{event::WindowEvent, event_loop::EventLoop, window::WindowBuilder, dpi::LogicalSize};
+
+use crate::math::Vec2;
 
-use super::canvas::{Canvas, Color};
+use super::canvas::{BlendMode, BlitParams, Canvas, Color, Framebuffer, IRect, ImageTransform};
+use super::image::Image;
+use super::raster::{ClipStack, Raster};
+use super::scale;
+pub use super::scale::ScaleMode;
 
 #[cfg(feature = "render-pixels")]
 pub struct PixelsCanvas {
     pixels: Pixels,
+    /// Logical frame we draw into; copied (and scaled, if fractional) on `present()`.
+    frame: Vec<u8>,
     width: u32,
     height: u32,
+    window_size: (u32, u32),
+    mode: ScaleMode,
+    /// Size of the `pixels` buffer: the canvas size, or the scaled size when fractional.
+    buffer_size: (u32, u32),
+    blend: BlendMode,
+    clips: ClipStack,
 }
 
 #[cfg(feature = "render-pixels")]
 impl PixelsCanvas {
-    pub fn new(width: u32, 
-               height: u32, 
-               scale: u32, 
-               title: &str) -> 
+    /// `width x height` canvas in a resizable window that opens at `scale`x.
+    pub fn new(width: u32,
+               height: u32,
+               scale: u32,
+               title: &str) ->
                anyhow::Result<(Self,
                winit::event_loop::EventLoop<()>,
                winit::window::Window)>
@@ -27,92 +48,145 @@ impl PixelsCanvas {
         let window = WindowBuilder::new()
             .with_title(title)
             .with_inner_size(LogicalSize::new((width*scale) as f64, (height*scale) as f64))
-            .with_resizable(false)
+            .with_min_inner_size(LogicalSize::new(width as f64, height as f64))
+            .with_resizable(true)
             .build(&event_loop)?;
 
-        let surface = SurfaceTexture::new(width*scale, height*scale, &window);
+        let size = window.inner_size();
+        let surface = SurfaceTexture::new(size.width, size.height, &window);
         let pixels = Pixels::new(width, height, surface)?;
-        Ok((Self { pixels, width, height }, event_loop, window))
+        Ok((Self {
+                pixels,
+                frame: vec![0; width as usize * height as usize * 4],
+                width,
+                height,
+                window_size: (size.width, size.height),
+                mode: ScaleMode::default(),
+                buffer_size: (width, height),
+                blend: BlendMode::default(),
+                clips: ClipStack::default(),
+            },
+            event_loop, window))
     }
-}
 
-#[cfg(feature = "render-pixels")]
-impl Canvas for PixelsCanvas {
-    fn size(&self) -> (u32, u32) { (self.width, self.height) }
+    pub fn scale_mode(&self) -> ScaleMode { self.mode }
 
-    fn clear(&mut self, color: Color) {
-        for px in self.pixels.frame_mut().chunks_exact_mut(4) {
-            px.copy_from_slice(&[color.0, color.1, color.2, color.3]);
-        }
+    pub fn set_scale_mode(&mut self, mode: ScaleMode) -> anyhow::Result<()> {
+        self.mode = mode;
+        self.fit_buffer()
     }
 
-    fn fill_rect (&mut self, x: i32, y: i32, w: i32, h: i32, color: Color) {
-        let (W, H) = (self.width as i32, self.height as i32);
-        let frame = self.pixels.frame_mut();
-        for yy in y.max(0)..(y+h).min(H) {
-            for xx in x.max(0)..(x+w).min(W) {
-                let idx = ((yy as u32 * self.width + xx as u32) * 4) as usize;
-                frame[idx..idx+4].copy_from_slice(&[color.0, color.1, color.2, color.3]);
+    /// Call with the new physical size on `WindowEvent::Resized` (0x0 = minimized, ignored).
+    pub fn resize(&mut self, width: u32, height: u32) -> anyhow::Result<()> {
+        if width == 0 || height == 0 { return Ok(()); }
+        self.window_size = (width, height);
+        self.pixels.resize_surface(width, height)?;
+        self.fit_buffer()
+    }
+
+    /// Handles `Resized` / `ScaleFactorChanged`; returns true if the event was a resize.
+    pub fn handle_window_event(&mut self, event: &WindowEvent) -> anyhow::Result<bool> {
+        match event {
+            WindowEvent::Resized(size) => self.resize(size.width, size.height).map(|_| true),
+            WindowEvent::ScaleFactorChanged { new_inner_size, .. } => {
+                self.resize(new_inner_size.width, new_inner_size.height).map(|_| true)
             }
+            _ => Ok(false),
         }
     }
 
-    fn draw_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: Color) {
-        // Bresenham
-        let (mut x, mut y) = (x1, y1);
-        let dx = (x2 - x1).abs();
-        let sx = if x1 < x2 { 1 } else { -1 };
-        let dy = -(y2 - y1).abs();
-        let sy = if y1 < y2 { 1 } else { -1 };
-        let mut err = dx + dy;
-
-        loop {
-            self.fill_rect(x, y, 1, 1, color);
-            if x == x2 && y == y2 { break; }
-            let e2 = 2 * err;
-            if e2 >= dy { err += dy; x += sx; }
-            if e2 <= dx { err += dx; y += sy; }
+    /// Physical window position (e.g. from `CursorMoved`) -> canvas coordinates,
+    /// clamped to the canvas so letterbox bars map to the nearest edge.
+    pub fn window_to_canvas(&self, pos: Vec2) -> Vec2 {
+        let (sx, sy, ox, oy) = self.placement();
+        Vec2::new(
+            ((pos.x - ox) / sx).clamp(0.0, self.width as f32 - 1.0),
+            ((pos.y - oy) / sy).clamp(0.0, self.height as f32 - 1.0),
+        )
+    }
+
+    /// Canvas pixels -> physical window position (top-left of the scaled pixel).
+    pub fn canvas_to_window(&self, pos: Vec2) -> Vec2 {
+        let (sx, sy, ox, oy) = self.placement();
+        Vec2::new(pos.x * sx + ox, pos.y * sy + oy)
+    }
+
+    /// (scale_x, scale_y, offset_x, offset_y) of the canvas inside the window.
+    fn placement(&self) -> (f32, f32, f32, f32) {
+        let r = scale::canvas_rect(self.window_size, (self.width, self.height), self.mode);
+        (r.w as f32 / self.width as f32, r.h as f32 / self.height as f32, r.x as f32, r.y as f32)
+    }
+
+    fn fit_buffer(&mut self) -> anyhow::Result<()> {
+        let size = scale::buffer_size(self.window_size, (self.width, self.height), self.mode);
+        if size != self.buffer_size {
+            self.pixels.resize_buffer(size.0, size.1)?;
+            self.buffer_size = size;
         }
+        Ok(())
     }
 
-    fn draw_circle(&mut self, cx: i32, cy: i32, radius: i32, color: Color) {
-        // Midpoint circle (outline)
-        if radius <= 0 { return; }
-        let mut x = 0;
-        let mut y = radius;
-        let mut d = 1 - radius;
-
-        // helper to plot 8-way symmetry
-        let mut plot = |px: i32, py: i32| { self.fill_rect(px, py, 1, 1, color); };
-
-        // initial cardinal points
-        plot(cx, cy + radius);
-        plot(cx, cy - radius);
-        plot(cx + radius, cy);
-        plot(cx - radius, cy);
-
-        while x < y {
-            if d < 0 {
-                d += 2 * x + 3;
-            } else {
-                d += 2 * (x - y) + 5;
-                y -= 1;
-            }
-            x += 1;
-
-            // 8 octants
-            plot(cx + x, cy + y);
-            plot(cx - x, cy + y);
-            plot(cx + x, cy - y);
-            plot(cx - x, cy - y);
-            plot(cx + y, cy + x);
-            plot(cx - y, cy + x);
-            plot(cx + y, cy - x);
-            plot(cx - y, cy - x);
+    fn bounds(&self) -> IRect { IRect::new(0, 0, self.width as i32, self.height as i32) }
+
+    fn raster(&mut self) -> Raster<'_> {
+        let clip = self.clip_rect();
+        Raster {
+            frame: &mut self.frame,
+            width: self.width,
+            blend: self.blend,
+            clip,
         }
     }
+}
+
+#[cfg(feature = "render-pixels")]
+impl Canvas for PixelsCanvas {
+    fn size(&self) -> (u32, u32) { (self.width, self.height) }
+
+    fn clear(&mut self, color: Color) { self.raster().clear(color); }
+
+    fn fill_rect (&mut self, x: i32, y: i32, w: i32, h: i32, color: Color) {
+        self.raster().fill_rect(x, y, w, h, color);
+    }
+
+    fn blend_mode(&self) -> BlendMode { self.blend }
+    fn set_blend_mode(&mut self, mode: BlendMode) { self.blend = mode; }
+
+    fn push_clip(&mut self, rect: IRect) { self.clips.push(rect, self.bounds()); }
+    fn pop_clip(&mut self) { self.clips.pop(); }
+    fn clip_rect(&self) -> IRect { self.clips.current(self.bounds()) }
+
+    // Straight into the frame instead of per-pixel fill_rect calls.
+    fn draw_image_region(&mut self, image: &Image, src: IRect, x: i32, y: i32, params: BlitParams) {
+        self.raster().blit(image, src, x, y, params);
+    }
+
+    fn draw_image_transformed(&mut self, image: &Image, src: IRect, xf: ImageTransform, params: BlitParams) {
+        self.raster().blit_transformed(image, src, &xf, &params);
+    }
 
     fn present(&mut self) -> Result<(), String> {
+        let (bw, bh) = (self.buffer_size.0 as usize, self.buffer_size.1 as usize);
+        let (w, h) = (self.width as usize, self.height as usize);
+        let dst = self.pixels.frame_mut();
+        if (bw, bh) == (w, h) {
+            dst.copy_from_slice(&self.frame);
+        } else {
+            // nearest-neighbor upscale for fractional mode
+            let cols: Vec<usize> = (0..bw).map(|x| (x * w / bw).min(w - 1) * 4).collect();
+            for (y, row) in dst.chunks_exact_mut(bw * 4).enumerate() {
+                let src = &self.frame[(y * h / bh).min(h - 1) * w * 4..][..w * 4];
+                for (px, &sx) in row.chunks_exact_mut(4).zip(&cols) {
+                    px.copy_from_slice(&src[sx..sx + 4]);
+                }
+            }
+        }
         self.pixels.render().map_err(|e| e.to_string())
     }
 }
+
+#[cfg(feature = "render-pixels")]
+impl Framebuffer for PixelsCanvas {
+    fn frame(&self) -> &[u8] { &self.frame }
+    fn frame_mut(&mut self) -> &mut [u8] { &mut self.frame }
+}
@@ -0,0 +1,336 @@
+//! CPU post-processing for `Framebuffer` canvases (run right before `present()`).
+//! - `PostChain`: ordered effects, each toggleable at runtime by name
+//! - `Crt` (scanlines + curvature), `Dither` (Bayer to a palette), `Vignette`,
+//!   `ChromaticAberration`, `Blur` (box / gaussian) and a cheap half-res `Bloom`
+//!
+//! Works on anything with a CPU frame: `ImageCanvas`, `PixelsCanvas`, `RenderTarget`
+//! (e.g. blur just the particle layer before compositing).
+
+use std::any::Any;
+
+use super::canvas::{Color, Framebuffer};
+use super::indexed::Palette;
+
+/// One full-frame pass over row-major RGBA8 pixels.
+pub trait Effect: Any {
+    /// Name used to toggle the effect in a `PostChain`.
+    fn name(&self) -> &'static str;
+    fn apply(&mut self, frame: &mut [u8], width: u32, height: u32);
+}
+
+struct Entry {
+    effect: Box<dyn Effect>,
+    enabled: bool,
+}
+
+#[derive(Default)]
+pub struct PostChain {
+    effects: Vec<Entry>,
+}
+
+impl PostChain {
+    pub fn new() -> Self { Self::default() }
+
+    /// Append an enabled effect; effects run in insertion order.
+    pub fn push(&mut self, effect: impl Effect) -> &mut Self {
+        self.effects.push(Entry { effect: Box::new(effect), enabled: true });
+        self
+    }
+
+    /// Enable/disable every effect called `name`; false if there is none.
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        let mut found = false;
+        for e in self.effects.iter_mut().filter(|e| e.effect.name() == name) {
+            e.enabled = enabled;
+            found = true;
+        }
+        found
+    }
+
+    /// Flip every effect called `name`; the first one's new state, `None` if there is none.
+    pub fn toggle(&mut self, name: &str) -> Option<bool> {
+        let mut state = None;
+        for e in self.effects.iter_mut().filter(|e| e.effect.name() == name) {
+            e.enabled = !e.enabled;
+            state = state.or(Some(e.enabled));
+        }
+        state
+    }
+
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.effects.iter().any(|e| e.enabled && e.effect.name() == name)
+    }
+
+    /// First effect of type `T`, to tweak its parameters at runtime.
+    pub fn get_mut<T: Effect>(&mut self) -> Option<&mut T> {
+        self.effects.iter_mut().find_map(|e| (e.effect.as_mut() as &mut dyn Any).downcast_mut::<T>())
+    }
+
+    /// Run every enabled effect over `fb`'s frame.
+    pub fn apply(&mut self, fb: &mut impl Framebuffer) {
+        let (w, h) = fb.size();
+        let frame = fb.frame_mut();
+        for e in self.effects.iter_mut().filter(|e| e.enabled) {
+            e.effect.apply(frame, w, h);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Effects
+// ---------------------------------------------------------------------------
+
+/// CRT look: darkened every other row plus barrel distortion (black outside the tube).
+pub struct Crt {
+    /// 0 = no scanlines, 1 = odd rows black.
+    pub scanlines: f32,
+    /// Barrel amount; 0 = flat, ~0.1 subtle, ~0.3 strong.
+    pub curvature: f32,
+    src: Vec<u8>,
+}
+
+impl Crt {
+    pub fn new(scanlines: f32, curvature: f32) -> Self { Self { scanlines, curvature, src: Vec::new() } }
+}
+
+impl Effect for Crt {
+    fn name(&self) -> &'static str { "crt" }
+
+    fn apply(&mut self, frame: &mut [u8], width: u32, height: u32) {
+        let (w, h) = (width as usize, height as usize);
+        if self.curvature > 0.0 {
+            self.src.clear();
+            self.src.extend_from_slice(frame);
+            let (hw, hh) = (w as f32 * 0.5, h as f32 * 0.5);
+            for y in 0..h {
+                for x in 0..w {
+                    let (u, v) = ((x as f32 + 0.5 - hw) / hw, (y as f32 + 0.5 - hh) / hh);
+                    let k = 1.0 + self.curvature * (u * u + v * v);
+                    let (sx, sy) = ((u * k * hw + hw).floor(), (v * k * hh + hh).floor());
+                    let i = (y * w + x) * 4;
+                    if sx < 0.0 || sy < 0.0 || sx >= w as f32 || sy >= h as f32 {
+                        frame[i..i + 3].fill(0);
+                    } else {
+                        let s = (sy as usize * w + sx as usize) * 4;
+                        frame[i..i + 4].copy_from_slice(&self.src[s..s + 4]);
+                    }
+                }
+            }
+        }
+        if self.scanlines > 0.0 {
+            let keep = ((1.0 - self.scanlines.clamp(0.0, 1.0)) * 256.0) as u32;
+            for row in frame.chunks_exact_mut(w * 4).skip(1).step_by(2) {
+                for px in row.chunks_exact_mut(4) {
+                    for c in &mut px[..3] { *c = ((*c as u32 * keep) >> 8) as u8; }
+                }
+            }
+        }
+    }
+}
+
+/// Ordered (4x4 Bayer) dithering to a fixed palette.
+pub struct Dither {
+    pub palette: Palette,
+    /// Threshold spread in 0..255 channel units; 0 = plain nearest color.
+    pub spread: f32,
+}
+
+impl Dither {
+    pub fn new(palette: Palette) -> Self { Self { palette, spread: 32.0 } }
+}
+
+const BAYER4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+impl Effect for Dither {
+    fn name(&self) -> &'static str { "dither" }
+
+    fn apply(&mut self, frame: &mut [u8], width: u32, _height: u32) {
+        let w = width as usize;
+        for (i, px) in frame.chunks_exact_mut(4).enumerate() {
+            let (x, y) = (i % w, i / w);
+            let t = ((BAYER4[y & 3][x & 3] as f32 + 0.5) / 16.0 - 0.5) * self.spread;
+            let ch = |c: u8| (c as f32 + t).round().clamp(0.0, 255.0) as u8;
+            let Color(r, g, b, _) = self.palette.get(self.palette.nearest(Color(ch(px[0]), ch(px[1]), ch(px[2]), 255)));
+            px[..3].copy_from_slice(&[r, g, b]);
+        }
+    }
+}
+
+/// Darkens toward the corners.
+pub struct Vignette {
+    /// 0 = off, 1 = corners fully black.
+    pub strength: f32,
+    /// Normalized distance (0 = center, 1 = corner) where darkening starts.
+    pub radius: f32,
+}
+
+impl Vignette {
+    pub fn new(strength: f32, radius: f32) -> Self { Self { strength, radius } }
+}
+
+impl Effect for Vignette {
+    fn name(&self) -> &'static str { "vignette" }
+
+    fn apply(&mut self, frame: &mut [u8], width: u32, height: u32) {
+        let (hw, hh) = (width as f32 * 0.5, height as f32 * 0.5);
+        let span = (1.0 - self.radius).max(1e-3);
+        for (i, px) in frame.chunks_exact_mut(4).enumerate() {
+            let (x, y) = ((i % width as usize) as f32 + 0.5, (i / width as usize) as f32 + 0.5);
+            let (u, v) = ((x - hw) / hw, (y - hh) / hh);
+            let d = ((u * u + v * v) * 0.5).sqrt();
+            let t = ((d - self.radius) / span).clamp(0.0, 1.0);
+            let keep = ((1.0 - self.strength * t * t * (3.0 - 2.0 * t)) * 256.0) as u32;
+            for c in &mut px[..3] { *c = ((*c as u32 * keep) >> 8) as u8; }
+        }
+    }
+}
+
+/// Splits red and blue outward from the center.
+pub struct ChromaticAberration {
+    /// Channel shift in pixels at the frame edges.
+    pub offset: f32,
+    src: Vec<u8>,
+}
+
+impl ChromaticAberration {
+    pub fn new(offset: f32) -> Self { Self { offset, src: Vec::new() } }
+}
+
+impl Effect for ChromaticAberration {
+    fn name(&self) -> &'static str { "chromatic_aberration" }
+
+    fn apply(&mut self, frame: &mut [u8], width: u32, height: u32) {
+        self.src.clear();
+        self.src.extend_from_slice(frame);
+        let (w, h) = (width as i32, height as i32);
+        let (hw, hh) = (w as f32 * 0.5, h as f32 * 0.5);
+        for y in 0..h {
+            for x in 0..w {
+                let (dx, dy) = ((x as f32 + 0.5 - hw) / hw * self.offset, (y as f32 + 0.5 - hh) / hh * self.offset);
+                let at = |sx: f32, sy: f32, c: usize| {
+                    let sx = (sx.round() as i32).clamp(0, w - 1);
+                    let sy = (sy.round() as i32).clamp(0, h - 1);
+                    self.src[(sy * w + sx) as usize * 4 + c]
+                };
+                let i = (y * w + x) as usize * 4;
+                frame[i] = at(x as f32 - dx, y as f32 - dy, 0);
+                frame[i + 2] = at(x as f32 + dx, y as f32 + dy, 2);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlurKind {
+    #[default]
+    Box,
+    /// Three box passes; close to a true gaussian at a fraction of the cost.
+    Gaussian,
+}
+
+pub struct Blur {
+    pub kind: BlurKind,
+    /// Box: half-width in pixels. Gaussian: roughly the standard deviation.
+    pub radius: u32,
+    tmp: Vec<u8>,
+}
+
+impl Blur {
+    pub fn new(kind: BlurKind, radius: u32) -> Self { Self { kind, radius, tmp: Vec::new() } }
+}
+
+impl Effect for Blur {
+    fn name(&self) -> &'static str { "blur" }
+
+    fn apply(&mut self, frame: &mut [u8], width: u32, height: u32) {
+        match self.kind {
+            BlurKind::Box => box_blur(frame, &mut self.tmp, width as usize, height as usize, self.radius as usize),
+            BlurKind::Gaussian => {
+                // box width for 3 passes matching sigma: sqrt(12 * sigma^2 / 3 + 1)
+                let sigma = self.radius as f32;
+                let r = (((4.0 * sigma * sigma + 1.0).sqrt() - 1.0) * 0.5).round() as usize;
+                for _ in 0..3 { box_blur(frame, &mut self.tmp, width as usize, height as usize, r.max(1)); }
+            }
+        }
+    }
+}
+
+/// Separable box blur over all four channels, edges clamped.
+fn box_blur(frame: &mut [u8], tmp: &mut Vec<u8>, w: usize, h: usize, r: usize) {
+    if r == 0 || w == 0 || h == 0 { return; }
+    tmp.resize(frame.len(), 0);
+    blur_pass(frame, tmp, w, h, r, 4, w * 4); // horizontal
+    blur_pass(tmp, frame, h, w, r, w * 4, 4); // vertical
+}
+
+/// Blur `n` samples `step` bytes apart, for `lines` lines starting `stride` bytes apart.
+fn blur_pass(src: &[u8], dst: &mut [u8], n: usize, lines: usize, r: usize, step: usize, stride: usize) {
+    let div = (2 * r + 1) as u32;
+    for line in 0..lines {
+        let base = line * stride;
+        for c in 0..4 {
+            let at = |i: isize| src[base + i.clamp(0, n as isize - 1) as usize * step + c] as u32;
+            let mut sum: u32 = (-(r as isize)..=r as isize).map(at).sum();
+            for i in 0..n {
+                dst[base + i * step + c] = ((sum + div / 2) / div) as u8;
+                sum += at(i as isize + r as isize + 1);
+                sum -= at(i as isize - r as isize);
+            }
+        }
+    }
+}
+
+/// Bright-pass, blurred at half resolution, added back on top.
+pub struct Bloom {
+    /// Luma (0..255) above which pixels glow.
+    pub threshold: u8,
+    pub intensity: f32,
+    /// Blur radius in half-res pixels.
+    pub radius: u32,
+    half: Vec<u8>,
+    tmp: Vec<u8>,
+}
+
+impl Bloom {
+    pub fn new(threshold: u8, intensity: f32, radius: u32) -> Self {
+        Self { threshold, intensity, radius, half: Vec::new(), tmp: Vec::new() }
+    }
+}
+
+impl Effect for Bloom {
+    fn name(&self) -> &'static str { "bloom" }
+
+    fn apply(&mut self, frame: &mut [u8], width: u32, height: u32) {
+        let (w, h) = (width as usize, height as usize);
+        let (hw, hh) = (w.div_ceil(2), h.div_ceil(2));
+        self.half.clear();
+        self.half.resize(hw * hh * 4, 0);
+
+        // bright-pass + 2x2 downsample
+        let t = self.threshold as u32;
+        for y in 0..hh {
+            for x in 0..hw {
+                let mut acc = [0u32; 3];
+                for (sx, sy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                    let (px, py) = ((2 * x + sx).min(w - 1), (2 * y + sy).min(h - 1));
+                    let p = &frame[(py * w + px) * 4..][..3];
+                    let luma = (p[0] as u32 * 54 + p[1] as u32 * 183 + p[2] as u32 * 19) >> 8;
+                    if luma > t { for c in 0..3 { acc[c] += p[c] as u32; } }
+                }
+                let d = &mut self.half[(y * hw + x) * 4..][..4];
+                d.copy_from_slice(&[(acc[0] / 4) as u8, (acc[1] / 4) as u8, (acc[2] / 4) as u8, 255]);
+            }
+        }
+        for _ in 0..2 { box_blur(&mut self.half, &mut self.tmp, hw, hh, self.radius as usize); }
+
+        // add back (nearest upsample)
+        let k = (self.intensity.max(0.0) * 256.0) as u32;
+        for y in 0..h {
+            for x in 0..w {
+                let g = &self.half[((y / 2) * hw + x / 2) * 4..][..3];
+                let p = &mut frame[(y * w + x) * 4..][..3];
+                for c in 0..3 { p[c] = (p[c] as u32 + ((g[c] as u32 * k) >> 8)).min(255) as u8; }
+            }
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! Shared software rasterizer for RGBA8 framebuffer backends.
+//! Backends keep their own state and hand out a short-lived `Raster` view.
+
+use crate::math::Vec2;
+
+use super::canvas::{BlendMode, BlitParams, Color, IRect, ImageTransform, Sampling};
+use super::image::Image;
+
+pub(crate) struct Raster<'a> {
+    pub frame: &'a mut [u8],
+    pub width: u32,
+    pub blend: BlendMode,
+    /// Already intersected with the frame bounds.
+    pub clip: IRect,
+}
+
+/// Nested clip rects; each push is intersected with the one below it.
+#[derive(Default)]
+pub(crate) struct ClipStack(Vec<IRect>);
+
+impl ClipStack {
+    pub fn push(&mut self, rect: IRect, bounds: IRect) {
+        let top = self.current(bounds);
+        self.0.push(top.intersect(&rect));
+    }
+
+    pub fn pop(&mut self) {
+        if self.0.pop().is_none() { log::warn!("pop_clip() without matching push_clip()"); }
+    }
+
+    pub fn current(&self, bounds: IRect) -> IRect {
+        self.0.last().map_or(bounds, |r| r.intersect(&bounds))
+    }
+}
+
+// Write one source color into one RGBA8 pixel.
+#[inline]
+fn put(px: &mut [u8], c: Color, mode: BlendMode) {
+    match mode {
+        BlendMode::Replace => px.copy_from_slice(&[c.0, c.1, c.2, c.3]),
+        BlendMode::Alpha if c.3 == 255 => px.copy_from_slice(&[c.0, c.1, c.2, c.3]),
+        // A fully transparent source leaves the frame untouched in every mode but Replace.
+        _ if c.3 == 0 => {}
+        _ => {
+            let out = mode.blend([px[0], px[1], px[2], px[3]], c);
+            px.copy_from_slice(&out);
+        }
+    }
+}
+
+impl Raster<'_> {
+    pub fn clear(&mut self, color: Color) {
+        let c = self.clip;
+        for yy in c.y..c.y + c.h {
+            let row = (yy as u32 * self.width) as usize * 4;
+            let span = &mut self.frame[row + c.x as usize * 4..row + (c.x + c.w) as usize * 4];
+            for px in span.chunks_exact_mut(4) {
+                px.copy_from_slice(&[color.0, color.1, color.2, color.3]);
+            }
+        }
+    }
+
+    pub fn fill_rect(&mut self, x: i32, y: i32, w: i32, h: i32, color: Color) {
+        let r = IRect::new(x, y, w, h).intersect(&self.clip);
+        if r.is_empty() { return; }
+
+        for yy in r.y..r.y + r.h {
+            let row = (yy as u32 * self.width) as usize * 4;
+            let span = &mut self.frame[row + r.x as usize * 4..row + (r.x + r.w) as usize * 4];
+            for px in span.chunks_exact_mut(4) {
+                put(px, color, self.blend);
+            }
+        }
+    }
+
+    pub fn blit(&mut self, image: &Image, src: IRect, x: i32, y: i32, params: BlitParams) {
+        let src = src.intersect(&image.bounds());
+        let dst = IRect::new(x, y, src.w, src.h).intersect(&self.clip);
+        if dst.is_empty() { return; }
+
+        let plain = params.tint == Color::WHITE && params.alpha == 255;
+        let iw = image.width() as usize;
+        let pixels = image.pixels();
+
+        for yy in dst.y..dst.y + dst.h {
+            let dy = yy - y;
+            let sy = if params.flip_y { src.y + src.h - 1 - dy } else { src.y + dy };
+            let row = (yy as u32 * self.width) as usize * 4;
+            for xx in dst.x..dst.x + dst.w {
+                let dx = xx - x;
+                let sx = if params.flip_x { src.x + src.w - 1 - dx } else { src.x + dx };
+                let si = (sy as usize * iw + sx as usize) * 4;
+                let s = &pixels[si..si + 4];
+                let mut c = Color(s[0], s[1], s[2], s[3]);
+                if !plain { c = params.apply(c); }
+
+                let di = row + xx as usize * 4;
+                put(&mut self.frame[di..di + 4], c, self.blend);
+            }
+        }
+    }
+
+    pub fn blit_transformed(&mut self, image: &Image, src: IRect, xf: &ImageTransform, params: &BlitParams) {
+        let (width, blend, clip) = (self.width, self.blend, self.clip);
+        let frame = &mut *self.frame;
+        for_each_transformed(image, src, xf, params, clip, |x, y, c| {
+            let i = (y as u32 * width + x as u32) as usize * 4;
+            put(&mut frame[i..i + 4], c, blend);
+        });
+    }
+}
+
+/// Inverse-maps every destination pixel (inside `clip`) covered by the transformed
+/// source region and calls `plot` with its final color. Shared by the trait default
+/// and the framebuffer backends.
+pub(crate) fn for_each_transformed(
+    image: &Image,
+    src: IRect,
+    xf: &ImageTransform,
+    params: &BlitParams,
+    clip: IRect,
+    mut plot: impl FnMut(i32, i32, Color),
+) {
+    let src = src.intersect(&image.bounds());
+    if src.is_empty() || xf.scale.x == 0.0 || xf.scale.y == 0.0 { return; }
+
+    let (sw, sh) = (src.w as f32, src.h as f32);
+    let (sin, cos) = xf.rotation.sin_cos();
+
+    // local (source-region) -> screen
+    let to_screen = |lx: f32, ly: f32| {
+        let (ax, ay) = ((lx - xf.pivot.x) * xf.scale.x, (ly - xf.pivot.y) * xf.scale.y);
+        (xf.pos.x + ax * cos - ay * sin, xf.pos.y + ax * sin + ay * cos)
+    };
+    let corners = [to_screen(0.0, 0.0), to_screen(sw, 0.0), to_screen(0.0, sh), to_screen(sw, sh)];
+    let min_x = corners.iter().map(|c| c.0).fold(f32::INFINITY, f32::min).floor() as i32;
+    let max_x = corners.iter().map(|c| c.0).fold(f32::NEG_INFINITY, f32::max).ceil() as i32;
+    let min_y = corners.iter().map(|c| c.1).fold(f32::INFINITY, f32::min).floor() as i32;
+    let max_y = corners.iter().map(|c| c.1).fold(f32::NEG_INFINITY, f32::max).ceil() as i32;
+    let area = IRect::new(min_x, min_y, max_x - min_x, max_y - min_y).intersect(&clip);
+    if area.is_empty() { return; }
+
+    // screen -> local, stepped incrementally along each row
+    let (inv_sx, inv_sy) = (1.0 / xf.scale.x, 1.0 / xf.scale.y);
+    let (du, dv) = (cos * inv_sx, -sin * inv_sy);
+
+    for y in area.y..area.y + area.h {
+        let dy = y as f32 + 0.5 - xf.pos.y;
+        let dx = area.x as f32 + 0.5 - xf.pos.x;
+        let mut u = (cos * dx + sin * dy) * inv_sx + xf.pivot.x;
+        let mut v = (-sin * dx + cos * dy) * inv_sy + xf.pivot.y;
+
+        for x in area.x..area.x + area.w {
+            if u >= 0.0 && v >= 0.0 && u < sw && v < sh {
+                let lu = if params.flip_x { sw - u } else { u };
+                let lv = if params.flip_y { sh - v } else { v };
+                let c = match xf.sampling {
+                    Sampling::Nearest => image.pixel(
+                        src.x + (lu as i32).min(src.w - 1),
+                        src.y + (lv as i32).min(src.h - 1),
+                    ),
+                    Sampling::Bilinear => Some(image.sample_bilinear(src, lu, lv)),
+                };
+                if let Some(c) = c { plot(x, y, params.apply(c)); }
+            }
+            u += du;
+            v += dv;
+        }
+    }
+}
+
+/// Even-odd scanline fill. Calls `span(y, x0, x1)` (x1 exclusive) for every run of
+/// pixels whose centers are inside the polygon, limited to rows/columns of `clip`.
+pub(crate) fn scan_polygon(points: &[Vec2], clip: IRect, mut span: impl FnMut(i32, i32, i32)) {
+    if points.len() < 3 || clip.is_empty() { return; }
+
+    let min_y = points.iter().map(|p| p.y).fold(f32::INFINITY, f32::min);
+    let max_y = points.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max);
+    let y0 = ((min_y - 0.5).ceil() as i32).max(clip.y);
+    let y1 = ((max_y - 0.5).ceil() as i32).min(clip.y + clip.h);
+
+    let mut xs: Vec<f32> = Vec::with_capacity(8);
+    for y in y0..y1 {
+        let sy = y as f32 + 0.5;
+        xs.clear();
+        for (i, a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
+            // half-open on y so shared vertices are counted once
+            if (a.y <= sy) != (b.y <= sy) {
+                xs.push(a.x + (sy - a.y) / (b.y - a.y) * (b.x - a.x));
+            }
+        }
+        xs.sort_by(f32::total_cmp);
+
+        for pair in xs.chunks_exact(2) {
+            // pixels whose centers fall in [left, right)
+            let x0 = ((pair[0] - 0.5).ceil() as i32).max(clip.x);
+            let x1 = ((pair[1] - 0.5).ceil() as i32).min(clip.x + clip.w);
+            if x0 < x1 { span(y, x0, x1); }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! Where a fixed-resolution canvas lands inside a resizable window.
+//! - `ScaleMode::Integer` (crisp 2x/3x/...) or `ScaleMode::Fractional` (fills the window)
+//! - Letterboxed and centered; a window smaller than the canvas shows it at 1x, cropped
+//!
+//! Pure math, shared by `PixelsCanvas` and usable without the `render-pixels` feature.
+
+use super::canvas::IRect;
+
+/// How the logical canvas is scaled up to the window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScaleMode {
+    /// Largest whole-number scale that fits; every canvas pixel is the same size.
+    #[default]
+    Integer,
+    /// Largest scale that fits, nearest-neighbor; fills more of the window.
+    Fractional,
+}
+
+/// Size of the texture the canvas is uploaded as: the canvas itself, or pre-scaled
+/// (never below 1x) when fractional.
+pub fn buffer_size(window: (u32, u32), canvas: (u32, u32), mode: ScaleMode) -> (u32, u32) {
+    match mode {
+        ScaleMode::Integer => canvas,
+        ScaleMode::Fractional => {
+            let (ww, wh) = (window.0 as f32, window.1 as f32);
+            let s = (ww / canvas.0 as f32).min(wh / canvas.1 as f32).max(1.0);
+            ((canvas.0 as f32 * s).round() as u32, (canvas.1 as f32 * s).round() as u32)
+        }
+    }
+}
+
+/// Window-space rect (physical pixels) the canvas covers. May start at negative
+/// offsets when the window is smaller than the canvas.
+pub fn canvas_rect(window: (u32, u32), canvas: (u32, u32), mode: ScaleMode) -> IRect {
+    let (bw, bh) = buffer_size(window, canvas, mode);
+    let (ww, wh) = (window.0 as f32, window.1 as f32);
+    // the buffer is scaled by a whole number (at least 1) and centered
+    let k = (ww / bw as f32).min(wh / bh as f32).floor().max(1.0) as u32;
+    let (w, h) = (bw * k, bh * k);
+    let x = ((ww - w as f32) / 2.0).floor() as i32;
+    let y = ((wh - h as f32) / 2.0).floor() as i32;
+    IRect::new(x, y, w as i32, h as i32)
+}
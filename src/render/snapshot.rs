@@ -0,0 +1,99 @@
+//! Golden-image snapshot checks for framebuffer canvases.
+//! - References are PNGs checked in next to the tests
+//! - `DANIENGINE_UPDATE_SNAPSHOTS=1` writes the references instead of comparing;
+//!   otherwise a missing reference is an error
+//! - On mismatch, `<name>.actual.png` and `<name>.diff.png` land beside the reference
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, bail};
+
+use super::canvas::Framebuffer;
+use super::codec;
+
+pub const UPDATE_ENV: &str = "DANIENGINE_UPDATE_SNAPSHOTS";
+
+pub struct SnapshotDiff {
+    /// Pixels where any channel differs by more than the tolerance.
+    pub mismatched: usize,
+    /// Largest per-channel difference seen anywhere.
+    pub max_delta: u8,
+    /// RGBA image: mismatches in red, matching pixels as dimmed grayscale.
+    pub diff: Vec<u8>,
+}
+
+/// Per-channel comparison of two RGBA8 frames. If the lengths differ, every pixel
+/// present in only one of them counts as mismatched (delta 255).
+pub fn compare_rgba(expected: &[u8], actual: &[u8], tolerance: u8) -> SnapshotDiff {
+    let mut mismatched = expected.len().saturating_sub(actual.len()) / 4;
+    let mut max_delta = if expected.len() == actual.len() { 0 } else { 255 };
+    let mut diff = Vec::with_capacity(actual.len());
+
+    for (i, a) in actual.chunks_exact(4).enumerate() {
+        let delta = expected.get(i * 4..i * 4 + 4).map(|e| (0..4).map(|c| e[c].abs_diff(a[c])).max().unwrap_or(0));
+        max_delta = max_delta.max(delta.unwrap_or(255));
+        if delta.is_none_or(|d| d > tolerance) {
+            mismatched += 1;
+            diff.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            let luma = ((a[0] as u32 * 3 + a[1] as u32 * 6 + a[2] as u32) / 10 / 3) as u8;
+            diff.extend_from_slice(&[luma, luma, luma, 255]);
+        }
+    }
+
+    SnapshotDiff { mismatched, max_delta, diff }
+}
+
+/// Compares the frame against the reference PNG at `reference`.
+pub fn check_snapshot(fb: &impl Framebuffer, reference: impl AsRef<Path>, tolerance: u8) -> anyhow::Result<()> {
+    let reference = reference.as_ref();
+    let (w, h) = fb.size();
+    let actual = fb.frame();
+
+    let update = std::env::var(UPDATE_ENV).is_ok_and(|v| v == "1");
+    if !update && !reference.exists() {
+        codec::save_rgba(sibling(reference, "actual"), w, h, actual).ok();
+        bail!("snapshot {} is missing; run with {UPDATE_ENV}=1 to create it", reference.display());
+    }
+    if update {
+        if let Some(dir) = reference.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+        }
+        log::info!("writing snapshot {}", reference.display());
+        return codec::save_rgba(reference, w, h, actual);
+    }
+
+    let bytes = std::fs::read(reference).with_context(|| format!("reading {}", reference.display()))?;
+    let (rw, rh, expected) = codec::decode_png_rgba(&bytes)
+        .with_context(|| format!("decoding {}", reference.display()))?;
+
+    if (rw, rh) != (w, h) {
+        codec::save_rgba(sibling(reference, "actual"), w, h, actual)?;
+        bail!("snapshot {}: size {}x{} != reference {}x{}", reference.display(), w, h, rw, rh);
+    }
+
+    let result = compare_rgba(&expected, actual, tolerance);
+    if result.mismatched > 0 {
+        codec::save_rgba(sibling(reference, "actual"), w, h, actual)?;
+        codec::save_rgba(sibling(reference, "diff"), w, h, &result.diff)?;
+        bail!(
+            "snapshot {}: {} pixel(s) differ (max delta {}, tolerance {})",
+            reference.display(), result.mismatched, result.max_delta, tolerance
+        );
+    }
+    Ok(())
+}
+
+/// `check_snapshot` for use inside `#[test]`s; panics with the mismatch report.
+#[track_caller]
+pub fn assert_snapshot(fb: &impl Framebuffer, reference: impl AsRef<Path>, tolerance: u8) {
+    if let Err(e) = check_snapshot(fb, reference, tolerance) {
+        panic!("{e:#}");
+    }
+}
+
+// foo/bar.png -> foo/bar.<tag>.png
+fn sibling(reference: &Path, tag: &str) -> PathBuf {
+    let stem = reference.file_stem().and_then(|s| s.to_str()).unwrap_or("snapshot");
+    reference.with_file_name(format!("{stem}.{tag}.png"))
+}
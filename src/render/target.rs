@@ -0,0 +1,155 @@
+//! Offscreen render targets and layer compositing.
+//! - `RenderTarget`: an offscreen `Canvas` plus how it composites (opacity, blend, scale)
+//! - `Layers`: named targets drawn back-to-front onto any canvas (incl. `PixelsCanvas`)
+//!
+//! Targets start transparent and `Alpha` blending onto them keeps straight alpha,
+//! so translucent content composites correctly. For additive glow, clear the
+//! target to opaque black and composite it with `BlendMode::Additive`.
+
+use super::canvas::{BlendMode, BlitParams, Canvas, Color, Framebuffer, IRect, ImageTransform, Sampling};
+use super::image::Image;
+use super::image_canvas::ImageCanvas;
+use crate::math::Vec2;
+
+pub struct RenderTarget {
+    canvas: ImageCanvas,
+    /// Multiplied into the target's own alpha when compositing.
+    pub opacity: u8,
+    /// Blend used against the destination.
+    pub blend: BlendMode,
+    /// Destination pixels per target pixel, e.g. 2.0 for a half-res layer or 3.0 for a 3x pixel-art layer.
+    pub scale: f32,
+    /// Top-left corner on the destination.
+    pub offset: (i32, i32),
+    /// How a scaled target is sampled.
+    pub sampling: Sampling,
+    pub visible: bool,
+}
+
+impl RenderTarget {
+    /// Transparent target composited 1:1 with `Alpha` blending.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            canvas: ImageCanvas::new(width, height),
+            opacity: 255,
+            blend: BlendMode::Alpha,
+            scale: 1.0,
+            offset: (0, 0),
+            sampling: Sampling::Nearest,
+            visible: true,
+        }
+    }
+
+    /// Target that covers a `dst_w x dst_h` destination at `scale` (rounded up).
+    /// A non-positive or non-finite `scale` falls back to 1.0.
+    pub fn scaled(dst_w: u32, dst_h: u32, scale: f32) -> Self {
+        let scale = if scale > 0.0 && scale.is_finite() {
+            scale
+        } else {
+            log::warn!("RenderTarget::scaled({dst_w}, {dst_h}, {scale}): scale must be positive, using 1.0");
+            1.0
+        };
+        let w = (dst_w as f32 / scale).ceil() as u32;
+        let h = (dst_h as f32 / scale).ceil() as u32;
+        Self { scale, ..Self::new(w, h) }
+    }
+
+    pub fn image(&self) -> &Image { self.canvas.image() }
+
+    /// Reallocate (transparent) if the size changed, e.g. after a window resize.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if self.canvas.size() != (width, height) {
+            self.canvas = ImageCanvas::new(width, height);
+        }
+    }
+
+    /// Draw this target onto `dst` using its opacity, blend mode, scale and offset.
+    pub fn composite_onto(&self, dst: &mut impl Canvas) {
+        if !self.visible || self.opacity == 0 { return; }
+        let params = BlitParams { alpha: self.opacity, ..BlitParams::default() };
+        let (x, y) = self.offset;
+        let img = self.canvas.image();
+        dst.with_blend_mode(self.blend, |c| {
+            if self.scale == 1.0 {
+                c.draw_image_region(img, img.bounds(), x, y, params);
+            } else {
+                let xf = ImageTransform {
+                    pos: Vec2::new(x as f32, y as f32),
+                    scale: Vec2::new(self.scale, self.scale),
+                    sampling: self.sampling,
+                    ..ImageTransform::default()
+                };
+                c.draw_image_transformed(img, img.bounds(), xf, params);
+            }
+        });
+    }
+}
+
+impl Canvas for RenderTarget {
+    fn size(&self) -> (u32, u32) { self.canvas.size() }
+    fn clear(&mut self, color: Color) { self.canvas.clear(color); }
+    fn fill_rect(&mut self, x: i32, y: i32, w: i32, h: i32, color: Color) { self.canvas.fill_rect(x, y, w, h, color); }
+
+    /// Nothing to show on its own; use `composite_onto`.
+    fn present(&mut self) -> Result<(), String> { Ok(()) }
+
+    fn blend_mode(&self) -> BlendMode { self.canvas.blend_mode() }
+    fn set_blend_mode(&mut self, mode: BlendMode) { self.canvas.set_blend_mode(mode); }
+
+    fn push_clip(&mut self, rect: IRect) { self.canvas.push_clip(rect); }
+    fn pop_clip(&mut self) { self.canvas.pop_clip(); }
+    fn clip_rect(&self) -> IRect { self.canvas.clip_rect() }
+
+    fn draw_image_region(&mut self, image: &Image, src: IRect, x: i32, y: i32, params: BlitParams) {
+        self.canvas.draw_image_region(image, src, x, y, params);
+    }
+
+    fn draw_image_transformed(&mut self, image: &Image, src: IRect, xf: ImageTransform, params: BlitParams) {
+        self.canvas.draw_image_transformed(image, src, xf, params);
+    }
+}
+
+impl Framebuffer for RenderTarget {
+    fn frame(&self) -> &[u8] { self.canvas.frame() }
+    fn frame_mut(&mut self) -> &mut [u8] { self.canvas.frame_mut() }
+}
+
+/// Named render targets composited in insertion order (first = bottom).
+#[derive(Default)]
+pub struct Layers {
+    layers: Vec<(&'static str, RenderTarget)>,
+}
+
+impl Layers {
+    pub fn new() -> Self { Self::default() }
+
+    /// Append `target` on top; replaces an existing layer with the same name in place.
+    pub fn add(&mut self, name: &'static str, target: RenderTarget) {
+        match self.layers.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, t)) => *t = target,
+            None => self.layers.push((name, target)),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RenderTarget> {
+        self.layers.iter().find(|(n, _)| *n == name).map(|(_, t)| t)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut RenderTarget> {
+        self.layers.iter_mut().find(|(n, _)| *n == name).map(|(_, t)| t)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&'static str, &mut RenderTarget)> {
+        self.layers.iter_mut().map(|(n, t)| (*n, t))
+    }
+
+    /// Clear every layer to transparent.
+    pub fn clear_all(&mut self) {
+        for (_, t) in &mut self.layers { t.clear(Color(0, 0, 0, 0)); }
+    }
+
+    /// Composite all visible layers bottom-to-top onto `dst`.
+    pub fn composite_onto(&self, dst: &mut impl Canvas) {
+        for (_, t) in &self.layers { t.composite_onto(dst); }
+    }
+}
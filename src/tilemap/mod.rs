@@ -0,0 +1,285 @@
+//! Tile maps.
+//! - `Tileset`: grid of tiles cut from one image, optional animated tiles
+//! - `Tilemap`: fixed-size grid, several named layers, tiles referenced by global id (gid)
+//! - Per-tile flip flags (horizontal, vertical, diagonal = transpose, as in Tiled)
+//! - `draw` culls to the canvas clip rect, so only visible tiles are blitted
+//!
+//! Gid 0 is an empty cell; each tileset owns `first_gid .. first_gid + tile_count`.
+//! Draw through a `CameraCanvas` for zoom/rotation, or pass a plain top-left offset.
+//! Maps made in Tiled load with `tiled::TiledMap::load`.
+
+use std::collections::HashMap;
+
+use crate::animation::MIN_FRAME_TIME;
+use crate::math::Vec2;
+use crate::render::canvas::{BlitParams, Canvas, IRect, ImageTransform};
+use crate::render::image::Image;
+
+pub mod tiled;
+
+bitflags::bitflags! {
+    #[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct TileFlags: u8 {
+        const FLIP_X    = 0b001;
+        const FLIP_Y    = 0b010;
+        /// Swap x and y (applied before the other flips).
+        const FLIP_DIAG = 0b100;
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Tile {
+    pub gid: u32,
+    pub flags: TileFlags,
+}
+
+impl Tile {
+    pub const EMPTY: Tile = Tile { gid: 0, flags: TileFlags::empty() };
+    pub fn new(gid: u32) -> Self { Self { gid, flags: TileFlags::empty() } }
+    pub fn with_flags(self, flags: TileFlags) -> Self { Self { flags, ..self } }
+    pub fn is_empty(&self) -> bool { self.gid == 0 }
+}
+
+/// One frame of an animated tile: which tile (local id) and for how long (seconds).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TileFrame {
+    pub tile: u32,
+    pub duration: f32,
+}
+
+#[derive(Clone, Debug)]
+pub struct Tileset {
+    pub name: String,
+    pub image: Image,
+    pub tile_w: i32,
+    pub tile_h: i32,
+    /// Pixels around the whole grid and between tiles.
+    pub margin: i32,
+    pub spacing: i32,
+    pub columns: i32,
+    pub tile_count: u32,
+    /// 0 = assigned by `Tilemap::add_tileset`.
+    pub first_gid: u32,
+    animations: HashMap<u32, Vec<TileFrame>>,
+}
+
+impl Tileset {
+    /// Tiles packed edge to edge, row by row.
+    pub fn new(name: &str, image: Image, tile_w: i32, tile_h: i32) -> Self {
+        Self::with_spacing(name, image, tile_w, tile_h, 0, 0)
+    }
+
+    pub fn with_spacing(name: &str, image: Image, tile_w: i32, tile_h: i32, margin: i32, spacing: i32) -> Self {
+        let (tw, th) = (tile_w.max(1), tile_h.max(1));
+        let fit = |size: u32, tile: i32| ((size as i32 - 2 * margin + spacing) / (tile + spacing)).max(0);
+        let (columns, rows) = (fit(image.width(), tw), fit(image.height(), th));
+        Self {
+            name: name.to_string(),
+            image,
+            tile_w: tw,
+            tile_h: th,
+            margin,
+            spacing,
+            columns,
+            tile_count: (columns * rows) as u32,
+            first_gid: 0,
+            animations: HashMap::new(),
+        }
+    }
+
+    pub fn contains(&self, gid: u32) -> bool { gid >= self.first_gid && gid - self.first_gid < self.tile_count }
+
+    /// Source rect of a local tile id.
+    pub fn region(&self, local: u32) -> IRect {
+        let cols = self.columns.max(1) as u32;
+        let (c, r) = ((local % cols) as i32, (local / cols) as i32);
+        IRect::new(
+            self.margin + c * (self.tile_w + self.spacing),
+            self.margin + r * (self.tile_h + self.spacing),
+            self.tile_w,
+            self.tile_h,
+        )
+    }
+
+    /// Make local tile `local` cycle through `frames` (empty removes the animation).
+    pub fn set_animation(&mut self, local: u32, frames: Vec<TileFrame>) {
+        if frames.is_empty() { self.animations.remove(&local); } else { self.animations.insert(local, frames); }
+    }
+
+    pub fn animation(&self, local: u32) -> Option<&[TileFrame]> { self.animations.get(&local).map(Vec::as_slice) }
+
+    /// Local tile shown for `local` at `time` seconds.
+    pub fn animated(&self, local: u32, time: f32) -> u32 {
+        let Some(frames) = self.animations.get(&local) else { return local };
+        let total: f32 = frames.iter().map(|f| f.duration.max(MIN_FRAME_TIME)).sum();
+        let mut t = time.rem_euclid(total);
+        for f in frames {
+            let d = f.duration.max(MIN_FRAME_TIME);
+            if t < d { return f.tile; }
+            t -= d;
+        }
+        frames[frames.len() - 1].tile
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TileLayer {
+    pub name: String,
+    pub visible: bool,
+    /// Multiplied into the tiles' alpha.
+    pub opacity: u8,
+    /// Pixel offset of the whole layer.
+    pub offset: (i32, i32),
+    width: i32,
+    height: i32,
+    tiles: Vec<Tile>,
+}
+
+impl TileLayer {
+    pub fn new(name: &str, width: i32, height: i32) -> Self {
+        let (w, h) = (width.max(0), height.max(0));
+        Self {
+            name: name.to_string(),
+            visible: true,
+            opacity: 255,
+            offset: (0, 0),
+            width: w,
+            height: h,
+            tiles: vec![Tile::EMPTY; (w * h) as usize],
+        }
+    }
+
+    pub fn size(&self) -> (i32, i32) { (self.width, self.height) }
+    pub fn tiles(&self) -> &[Tile] { &self.tiles }
+    pub fn tiles_mut(&mut self) -> &mut [Tile] { &mut self.tiles }
+
+    pub fn get(&self, x: i32, y: i32) -> Option<Tile> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height { return None; }
+        Some(self.tiles[(y * self.width + x) as usize])
+    }
+
+    /// Ignored outside the layer.
+    pub fn set(&mut self, x: i32, y: i32, tile: Tile) {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height { return; }
+        self.tiles[(y * self.width + x) as usize] = tile;
+    }
+
+    pub fn fill(&mut self, tile: Tile) { self.tiles.fill(tile); }
+}
+
+#[derive(Clone, Debug)]
+pub struct Tilemap {
+    pub width: i32,
+    pub height: i32,
+    pub tile_w: i32,
+    pub tile_h: i32,
+    tilesets: Vec<Tileset>,
+    layers: Vec<TileLayer>,
+    time: f32,
+}
+
+impl Tilemap {
+    /// `width x height` tiles of `tile_w x tile_h` pixels, no layers yet.
+    pub fn new(width: i32, height: i32, tile_w: i32, tile_h: i32) -> Self {
+        Self { width, height, tile_w: tile_w.max(1), tile_h: tile_h.max(1), tilesets: Vec::new(), layers: Vec::new(), time: 0.0 }
+    }
+
+    /// Add a tileset; returns its first gid (assigned after the existing ones if 0).
+    pub fn add_tileset(&mut self, mut tileset: Tileset) -> u32 {
+        if tileset.first_gid == 0 {
+            tileset.first_gid = self.tilesets.iter().map(|t| t.first_gid + t.tile_count).max().unwrap_or(1);
+        }
+        let gid = tileset.first_gid;
+        self.tilesets.push(tileset);
+        gid
+    }
+
+    pub fn tilesets(&self) -> &[Tileset] { &self.tilesets }
+    pub fn tilesets_mut(&mut self) -> &mut [Tileset] { &mut self.tilesets }
+
+    /// Tileset owning `gid`, if any.
+    pub fn tileset_for(&self, gid: u32) -> Option<&Tileset> { self.tilesets.iter().find(|t| t.contains(gid)) }
+
+    /// Add an empty layer the size of the map (drawn above the existing ones); returns its index.
+    pub fn add_layer(&mut self, name: &str) -> usize {
+        self.layers.push(TileLayer::new(name, self.width, self.height));
+        self.layers.len() - 1
+    }
+
+    pub fn push_layer(&mut self, layer: TileLayer) -> usize {
+        self.layers.push(layer);
+        self.layers.len() - 1
+    }
+
+    pub fn layers(&self) -> &[TileLayer] { &self.layers }
+    pub fn layers_mut(&mut self) -> &mut [TileLayer] { &mut self.layers }
+    pub fn layer(&self, name: &str) -> Option<&TileLayer> { self.layers.iter().find(|l| l.name == name) }
+    pub fn layer_mut(&mut self, name: &str) -> Option<&mut TileLayer> { self.layers.iter_mut().find(|l| l.name == name) }
+
+    /// Advance animated tiles.
+    pub fn update(&mut self, dt: f32) { self.time += dt; }
+
+    /// Map size in pixels.
+    pub fn pixel_size(&self) -> (i32, i32) { (self.width * self.tile_w, self.height * self.tile_h) }
+
+    /// Cell containing a world position (may be outside the map).
+    pub fn world_to_tile(&self, pos: Vec2) -> (i32, i32) {
+        ((pos.x / self.tile_w as f32).floor() as i32, (pos.y / self.tile_h as f32).floor() as i32)
+    }
+
+    /// Top-left of a cell in world pixels.
+    pub fn tile_to_world(&self, x: i32, y: i32) -> Vec2 {
+        Vec2::new((x * self.tile_w) as f32, (y * self.tile_h) as f32)
+    }
+
+    /// Draw all visible layers. `offset` is the world position shown at the canvas origin
+    /// (use `Vec2::default()` when drawing through a `CameraCanvas`).
+    pub fn draw(&self, canvas: &mut impl Canvas, offset: Vec2) {
+        for i in 0..self.layers.len() {
+            if self.layers[i].visible { self.draw_layer(canvas, i, offset); }
+        }
+    }
+
+    /// Draw one layer (even if hidden); out-of-range indices draw nothing.
+    pub fn draw_layer(&self, canvas: &mut impl Canvas, index: usize, offset: Vec2) {
+        let Some(layer) = self.layers.get(index) else { return };
+        let (ox, oy) = (layer.offset.0 - offset.x.round() as i32, layer.offset.1 - offset.y.round() as i32);
+        let (tw, th) = (self.tile_w, self.tile_h);
+
+        // visible cells, widened for tilesets with tiles larger than the grid: those extend
+        // right of their cell and (bottom-aligned) above it, so cells left of / below the clip can spill in
+        let clip = canvas.clip_rect();
+        let extra_w = self.tilesets.iter().map(|t| t.tile_w - tw).max().unwrap_or(0).max(0);
+        let extra_h = self.tilesets.iter().map(|t| t.tile_h - th).max().unwrap_or(0).max(0);
+        let x0 = ((clip.x - extra_w - ox) as f32 / tw as f32).floor() as i32;
+        let y0 = ((clip.y - oy) as f32 / th as f32).floor() as i32;
+        let x1 = ((clip.x + clip.w - ox) as f32 / tw as f32).ceil() as i32;
+        let y1 = ((clip.y + clip.h + extra_h - oy) as f32 / th as f32).ceil() as i32;
+
+        let params = BlitParams { alpha: layer.opacity, ..BlitParams::default() };
+        for ty in y0.max(0)..y1.min(layer.height) {
+            for tx in x0.max(0)..x1.min(layer.width) {
+                let tile = layer.tiles[(ty * layer.width + tx) as usize];
+                if tile.is_empty() { continue; }
+                let Some(set) = self.tileset_for(tile.gid) else { continue };
+                let src = set.region(set.animated(tile.gid - set.first_gid, self.time));
+                let (x, y) = (ox + tx * tw, oy + ty * th + th - set.tile_h);
+                draw_tile(canvas, &set.image, src, x, y, tile.flags, params);
+            }
+        }
+    }
+}
+
+fn draw_tile(canvas: &mut impl Canvas, image: &Image, src: IRect, x: i32, y: i32, flags: TileFlags, params: BlitParams) {
+    let (h, v) = (flags.contains(TileFlags::FLIP_X), flags.contains(TileFlags::FLIP_Y));
+    if !flags.contains(TileFlags::FLIP_DIAG) {
+        canvas.draw_image_region(image, src, x, y, BlitParams { flip_x: h, flip_y: v, ..params });
+        return;
+    }
+    // transpose, then flip = a quarter turn clockwise of the source mirrored on the other axis
+    let xf = ImageTransform {
+        rotation: std::f32::consts::FRAC_PI_2,
+        ..ImageTransform::centered(x as f32 + src.w as f32 * 0.5, y as f32 + src.h as f32 * 0.5, src.w as u32, src.h as u32)
+    };
+    canvas.draw_image_transformed(image, src, xf, BlitParams { flip_x: v, flip_y: !h, ..params });
+}
@@ -0,0 +1,552 @@
+//! Tiled map import: `.tmj` (JSON) and `.tmx` (XML).
+//! - Orthogonal, finite maps; tile layers in CSV, XML `<tile>`, or base64 (raw / zlib / gzip)
+//! - Tilesets embedded or external (`.tsj` / `.tsx`), one image each, with tile animations
+//! - Object layers: rects, ellipses, points, polygons, polylines, tile objects, custom properties
+//! - Group layers are flattened (offsets add up, opacity multiplies)
+//!
+//! TMX is first converted to the TMJ layout, so both formats share one builder.
+//! Objects can become `physics::Body`s (`to_body`) or particle bursts (`emit`).
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::{bail, ensure, Context};
+use base64::Engine;
+use serde_json::{json, Map, Value};
+
+use super::{Tile, TileFlags, TileFrame, TileLayer, Tilemap, Tileset};
+use crate::math::Vec2;
+use crate::particles::{EmitterConfig, ParticleSystem};
+use crate::physics::{Aabb, Body};
+use crate::render::canvas::Color;
+use crate::render::codec;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    /// Also `file` paths and anything else stored as text.
+    String(String),
+    Color(Color),
+}
+
+impl PropertyValue {
+    pub fn as_f32(&self) -> Option<f32> {
+        match *self {
+            PropertyValue::Int(i) => Some(i as f32),
+            PropertyValue::Float(f) => Some(f as f32),
+            _ => None,
+        }
+    }
+    pub fn as_bool(&self) -> Option<bool> { if let PropertyValue::Bool(b) = *self { Some(b) } else { None } }
+    pub fn as_str(&self) -> Option<&str> { if let PropertyValue::String(s) = self { Some(s) } else { None } }
+    pub fn as_color(&self) -> Option<Color> { if let PropertyValue::Color(c) = *self { Some(c) } else { None } }
+}
+
+pub type Properties = HashMap<String, PropertyValue>;
+
+/// Object geometry; points are relative to the object's position.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    Rect { w: f32, h: f32 },
+    Ellipse { w: f32, h: f32 },
+    Point,
+    Polygon(Vec<Vec2>),
+    Polyline(Vec<Vec2>),
+}
+
+#[derive(Clone, Debug)]
+pub struct TiledObject {
+    pub id: u32,
+    pub name: String,
+    /// Tiled's "class" (older versions: "type").
+    pub class: String,
+    /// Name of the object layer it came from.
+    pub layer: String,
+    /// Top-left for rects/ellipses, bottom-left for tile objects, origin for points/polygons.
+    pub pos: Vec2,
+    /// Degrees, clockwise around `pos`.
+    pub rotation: f32,
+    pub shape: Shape,
+    /// Set for tile objects.
+    pub tile: Option<Tile>,
+    pub visible: bool,
+    pub properties: Properties,
+}
+
+impl TiledObject {
+    pub fn property(&self, name: &str) -> Option<&PropertyValue> { self.properties.get(name) }
+
+    /// World-space bounds, rotation included.
+    pub fn aabb(&self) -> Aabb {
+        let local: Vec<Vec2> = match &self.shape {
+            // tile objects hang up from their anchor
+            Shape::Rect { w, h } if self.tile.is_some() => vec![Vec2::new(0.0, -h), Vec2::new(*w, 0.0)],
+            Shape::Rect { w, h } | Shape::Ellipse { w, h } => vec![Vec2::new(0.0, 0.0), Vec2::new(*w, *h)],
+            // an empty point list behaves like a point
+            Shape::Point => vec![Vec2::new(0.0, 0.0)],
+            Shape::Polygon(p) | Shape::Polyline(p) if p.is_empty() => vec![Vec2::new(0.0, 0.0)],
+            Shape::Polygon(p) | Shape::Polyline(p) => p.clone(),
+        };
+        // all four corners of the local box, so rotation is covered
+        let (mut lo, mut hi) = (Vec2::new(f32::MAX, f32::MAX), Vec2::new(f32::MIN, f32::MIN));
+        for p in &local { lo = Vec2::new(lo.x.min(p.x), lo.y.min(p.y)); hi = Vec2::new(hi.x.max(p.x), hi.y.max(p.y)); }
+        let (s, c) = self.rotation.to_radians().sin_cos();
+        let (mut min, mut max) = (Vec2::new(f32::MAX, f32::MAX), Vec2::new(f32::MIN, f32::MIN));
+        for p in [lo, Vec2::new(hi.x, lo.y), Vec2::new(lo.x, hi.y), hi] {
+            let q = Vec2::new(self.pos.x + p.x * c - p.y * s, self.pos.y + p.x * s + p.y * c);
+            min = Vec2::new(min.x.min(q.x), min.y.min(q.y));
+            max = Vec2::new(max.x.max(q.x), max.y.max(q.y));
+        }
+        Aabb { x: min.x, y: min.y, w: max.x - min.x, h: max.y - min.y }
+    }
+
+    pub fn center(&self) -> Vec2 {
+        let b = self.aabb();
+        Vec2::new(b.x + b.w * 0.5, b.y + b.h * 0.5)
+    }
+
+    /// Body covering `aabb()`; velocity from the `vx` / `vy` properties (default 0).
+    pub fn to_body(&self) -> Body {
+        let b = self.aabb();
+        let prop = |n: &str| self.property(n).and_then(PropertyValue::as_f32).unwrap_or(0.0);
+        Body { pos: Vec2::new(b.x, b.y), vel: Vec2::new(prop("vx"), prop("vy")), size: Vec2::new(b.w, b.h) }
+    }
+
+    /// `base` with any fields overridden by properties of the same name
+    /// (`count`, `speed_min`, ..., `start_color`, `end_color`; angles in degrees).
+    pub fn emitter_config(&self, base: EmitterConfig) -> EmitterConfig {
+        let mut cfg = base;
+        let f = |n: &str| self.property(n).and_then(PropertyValue::as_f32);
+        if let Some(v) = f("count") { cfg.count = v.max(0.0) as usize; }
+        for (name, field) in [
+            ("speed_min", &mut cfg.speed_min),
+            ("speed_max", &mut cfg.speed_max),
+            ("life_min", &mut cfg.life_min),
+            ("life_max", &mut cfg.life_max),
+            ("size_min", &mut cfg.size_min),
+            ("size_max", &mut cfg.size_max),
+        ] {
+            if let Some(v) = f(name) { *field = v; }
+        }
+        if let Some(v) = f("spread") { cfg.spread_radians = v.to_radians(); }
+        if let Some(v) = f("direction") { cfg.base_direction = v.to_radians(); }
+        if let Some(c) = self.property("start_color").and_then(PropertyValue::as_color) { cfg.start_color = c; }
+        if let Some(c) = self.property("end_color").and_then(PropertyValue::as_color) { cfg.end_color = c; }
+        cfg
+    }
+
+    /// Burst from the object's center using `emitter_config(base)`.
+    pub fn emit(&self, particles: &mut ParticleSystem, base: EmitterConfig) {
+        let c = self.center();
+        particles.emit_burst([c.x, c.y], self.emitter_config(base));
+    }
+}
+
+pub struct TiledMap {
+    pub tilemap: Tilemap,
+    pub objects: Vec<TiledObject>,
+    pub properties: Properties,
+}
+
+impl TiledMap {
+    /// Load by extension (`.tmj` / `.json` or `.tmx`); tileset files and images are
+    /// resolved relative to the map.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_ascii_lowercase();
+        match ext.as_str() {
+            "tmj" | "json" => Self::from_tmj(&text, dir),
+            "tmx" => Self::from_tmx(&text, dir),
+            _ => bail!("unsupported map format"),
+        }
+        .with_context(|| format!("loading {}", path.display()))
+    }
+
+    /// Parse a `.tmj` document; `dir` is where relative paths start.
+    pub fn from_tmj(json: &str, dir: &Path) -> anyhow::Result<Self> {
+        let root: Value = serde_json::from_str(json).context("invalid JSON")?;
+        build_map(&root, dir)
+    }
+
+    /// Parse a `.tmx` document; `dir` is where relative paths start.
+    pub fn from_tmx(xml: &str, dir: &Path) -> anyhow::Result<Self> {
+        let doc = roxmltree::Document::parse(xml).context("invalid XML")?;
+        let root = doc.root_element();
+        ensure!(root.has_tag_name("map"), "root element is <{}>, expected <map>", root.tag_name().name());
+        build_map(&tmx_map(root)?, dir)
+    }
+
+    /// Objects whose class is `class`.
+    pub fn objects_of_class<'a>(&'a self, class: &'a str) -> impl Iterator<Item = &'a TiledObject> + 'a {
+        self.objects.iter().filter(move |o| o.class == class)
+    }
+
+    /// Bodies for every object of `class` (e.g. `"solid"`).
+    pub fn bodies(&self, class: &str) -> Vec<Body> { self.objects_of_class(class).map(TiledObject::to_body).collect() }
+}
+
+/* ------------------------------ builder (TMJ layout) ------------------------------ */
+
+// Lenient readers: TMX-converted values are strings, TMJ values are typed.
+fn num(v: &Value) -> Option<f64> { v.as_f64().or_else(|| v.as_str()?.trim().parse().ok()) }
+fn int(v: &Value) -> Option<i64> { num(v).map(|n| n as i64) }
+fn text(v: &Value) -> String {
+    match v {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        v => v.to_string(),
+    }
+}
+fn flag(v: &Value, default: bool) -> bool {
+    match v {
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64() != Some(0.0),
+        Value::String(s) => !matches!(s.as_str(), "0" | "false"),
+        _ => default,
+    }
+}
+
+fn build_map(root: &Value, dir: &Path) -> anyhow::Result<TiledMap> {
+    let orientation = root["orientation"].as_str().unwrap_or("orthogonal");
+    ensure!(orientation == "orthogonal", "{orientation} maps are not supported");
+    ensure!(!flag(&root["infinite"], false), "infinite maps are not supported");
+    let dim = |k: &str| int(&root[k]).map(|n| n as i32).with_context(|| format!("map is missing {k}"));
+    let mut tilemap = Tilemap::new(dim("width")?, dim("height")?, dim("tilewidth")?, dim("tileheight")?);
+
+    for (i, ts) in root["tilesets"].as_array().into_iter().flatten().enumerate() {
+        let first_gid = int(&ts["firstgid"]).with_context(|| format!("tileset {i} has no firstgid"))? as u32;
+        let tileset = match ts["source"].as_str() {
+            Some(src) => load_external_tileset(&dir.join(src))?,
+            None => build_tileset(ts, dir)?,
+        };
+        tilemap.add_tileset(Tileset { first_gid, ..tileset });
+    }
+
+    let mut objects = Vec::new();
+    add_layers(&mut tilemap, &mut objects, root, LayerParent { offset: (0.0, 0.0), opacity: 1.0, visible: true })?;
+    Ok(TiledMap { tilemap, objects, properties: properties(&root["properties"]) })
+}
+
+fn load_external_tileset(path: &Path) -> anyhow::Result<Tileset> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let value = if text.trim_start().starts_with('<') {
+        let doc = roxmltree::Document::parse(&text).context("invalid XML")?;
+        tmx_tileset(doc.root_element())?
+    } else {
+        serde_json::from_str(&text).context("invalid JSON")?
+    };
+    build_tileset(&value, dir).with_context(|| format!("tileset {}", path.display()))
+}
+
+fn build_tileset(ts: &Value, dir: &Path) -> anyhow::Result<Tileset> {
+    let name = text(&ts["name"]);
+    let image = ts["image"].as_str().with_context(|| format!("tileset {name:?}: image collections are not supported"))?;
+    let image = codec::load_image(dir.join(image))?;
+    let get = |k: &str| int(&ts[k]).map(|n| n as i32);
+    let (tw, th) = (get("tilewidth").context("tileset has no tilewidth")?, get("tileheight").context("tileset has no tileheight")?);
+    let mut set = Tileset::with_spacing(&name, image, tw, th, get("margin").unwrap_or(0), get("spacing").unwrap_or(0));
+    if let Some(c) = get("columns").filter(|&c| c > 0) { set.columns = c; }
+    if let Some(n) = get("tilecount") { set.tile_count = n.max(0) as u32; }
+
+    for tile in ts["tiles"].as_array().into_iter().flatten() {
+        let Some(id) = int(&tile["id"]) else { continue };
+        let frames: Vec<TileFrame> = tile["animation"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|f| Some(TileFrame { tile: int(&f["tileid"])? as u32, duration: num(&f["duration"])? as f32 / 1000.0 }))
+            .collect();
+        set.set_animation(id as u32, frames);
+    }
+    Ok(set)
+}
+
+#[derive(Clone, Copy)]
+struct LayerParent {
+    offset: (f32, f32),
+    opacity: f32,
+    visible: bool,
+}
+
+fn add_layers(map: &mut Tilemap, objects: &mut Vec<TiledObject>, parent: &Value, inherited: LayerParent) -> anyhow::Result<()> {
+    for layer in parent["layers"].as_array().into_iter().flatten() {
+        let name = text(&layer["name"]);
+        let here = LayerParent {
+            offset: (
+                inherited.offset.0 + num(&layer["offsetx"]).unwrap_or(0.0) as f32,
+                inherited.offset.1 + num(&layer["offsety"]).unwrap_or(0.0) as f32,
+            ),
+            opacity: inherited.opacity * num(&layer["opacity"]).unwrap_or(1.0) as f32,
+            visible: inherited.visible && flag(&layer["visible"], true),
+        };
+        match layer["type"].as_str().unwrap_or("") {
+            "tilelayer" => {
+                let (w, h) = (int(&layer["width"]).unwrap_or(map.width as i64) as i32, int(&layer["height"]).unwrap_or(map.height as i64) as i32);
+                let gids = layer_gids(layer).with_context(|| format!("layer {name:?}"))?;
+                ensure!(gids.len() == (w * h) as usize, "layer {name:?}: {} tiles for a {w}x{h} layer", gids.len());
+                let mut out = TileLayer::new(&name, w, h);
+                for (dst, &raw) in out.tiles_mut().iter_mut().zip(&gids) { *dst = tile_from_gid(raw); }
+                out.visible = here.visible;
+                out.opacity = (here.opacity.clamp(0.0, 1.0) * 255.0).round() as u8;
+                out.offset = (here.offset.0.round() as i32, here.offset.1.round() as i32);
+                map.push_layer(out);
+            }
+            "objectgroup" => {
+                for o in layer["objects"].as_array().into_iter().flatten() {
+                    let mut obj = build_object(o, &name)?;
+                    obj.pos = Vec2::new(obj.pos.x + here.offset.0, obj.pos.y + here.offset.1);
+                    obj.visible &= here.visible;
+                    objects.push(obj);
+                }
+            }
+            "group" => add_layers(map, objects, layer, here)?,
+            // image layers have no engine equivalent here
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Tiled stores flips in the top bits of each gid.
+fn tile_from_gid(raw: u32) -> Tile {
+    let mut flags = TileFlags::empty();
+    flags.set(TileFlags::FLIP_X, raw & 0x8000_0000 != 0);
+    flags.set(TileFlags::FLIP_Y, raw & 0x4000_0000 != 0);
+    flags.set(TileFlags::FLIP_DIAG, raw & 0x2000_0000 != 0);
+    Tile { gid: raw & 0x0FFF_FFFF, flags }
+}
+
+fn layer_gids(layer: &Value) -> anyhow::Result<Vec<u32>> {
+    if layer["chunks"].is_array() { bail!("chunked (infinite) layers are not supported"); }
+    match &layer["data"] {
+        Value::Array(a) => a.iter().map(|v| num(v).map(|n| n as u32).context("bad gid")).collect(),
+        Value::String(s) => {
+            let encoding = layer["encoding"].as_str().unwrap_or("csv");
+            match encoding {
+                "csv" => parse_csv(s),
+                "base64" => {
+                    let compression = layer["compression"].as_str().unwrap_or("");
+                    decode_base64(s, compression)
+                }
+                e => bail!("unsupported encoding {e:?}"),
+            }
+        }
+        _ => bail!("missing tile data"),
+    }
+}
+
+fn parse_csv(s: &str) -> anyhow::Result<Vec<u32>> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(|t| t.parse().with_context(|| format!("bad gid {t:?}")))
+        .collect()
+}
+
+fn decode_base64(s: &str, compression: &str) -> anyhow::Result<Vec<u32>> {
+    let clean: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+    let raw = base64::engine::general_purpose::STANDARD.decode(clean).context("bad base64")?;
+    let bytes = match compression {
+        "" => raw,
+        "zlib" => inflate(flate2::read::ZlibDecoder::new(&raw[..]))?,
+        "gzip" => inflate(flate2::read::GzDecoder::new(&raw[..]))?,
+        c => bail!("unsupported compression {c:?}"),
+    };
+    ensure!(bytes.len() % 4 == 0, "tile data is not a whole number of gids");
+    Ok(bytes.chunks_exact(4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect())
+}
+
+fn inflate(mut r: impl Read) -> anyhow::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    r.read_to_end(&mut out).context("decompressing tile data")?;
+    Ok(out)
+}
+
+fn build_object(o: &Value, layer: &str) -> anyhow::Result<TiledObject> {
+    let f = |k: &str| num(&o[k]).unwrap_or(0.0) as f32;
+    let points = |k: &str| -> Vec<Vec2> {
+        o[k].as_array().into_iter().flatten().map(|p| Vec2::new(num(&p["x"]).unwrap_or(0.0) as f32, num(&p["y"]).unwrap_or(0.0) as f32)).collect()
+    };
+    let shape = if flag(&o["point"], false) {
+        Shape::Point
+    } else if flag(&o["ellipse"], false) {
+        Shape::Ellipse { w: f("width"), h: f("height") }
+    } else if o["polygon"].is_array() {
+        Shape::Polygon(points("polygon"))
+    } else if o["polyline"].is_array() {
+        Shape::Polyline(points("polyline"))
+    } else {
+        Shape::Rect { w: f("width"), h: f("height") }
+    };
+    let class = if o["class"].is_null() { text(&o["type"]) } else { text(&o["class"]) };
+    Ok(TiledObject {
+        id: int(&o["id"]).unwrap_or(0) as u32,
+        name: text(&o["name"]),
+        class,
+        layer: layer.to_string(),
+        pos: Vec2::new(f("x"), f("y")),
+        rotation: f("rotation"),
+        shape,
+        tile: num(&o["gid"]).map(|g| tile_from_gid(g as u32)),
+        visible: flag(&o["visible"], true),
+        properties: properties(&o["properties"]),
+    })
+}
+
+fn properties(v: &Value) -> Properties {
+    let mut out = Properties::new();
+    for p in v.as_array().into_iter().flatten() {
+        let name = text(&p["name"]);
+        let value = &p["value"];
+        let parsed = match p["type"].as_str().unwrap_or("string") {
+            "bool" => PropertyValue::Bool(flag(value, false)),
+            "int" | "object" => PropertyValue::Int(int(value).unwrap_or(0)),
+            "float" => PropertyValue::Float(num(value).unwrap_or(0.0)),
+            "color" => match parse_color(&text(value)) {
+                Some(c) => PropertyValue::Color(c),
+                None => continue,
+            },
+            // custom class values are nested objects; not supported
+            "class" => continue,
+            _ => PropertyValue::String(text(value)),
+        };
+        out.insert(name, parsed);
+    }
+    out
+}
+
+/// Tiled colors are `#AARRGGBB` or `#RRGGBB`.
+fn parse_color(s: &str) -> Option<Color> {
+    let hex = s.trim_start_matches('#');
+    let byte = |i: usize| hex.get(i..i + 2).and_then(|b| u8::from_str_radix(b, 16).ok());
+    match hex.len() {
+        6 => Some(Color(byte(0)?, byte(2)?, byte(4)?, 255)),
+        8 => Some(Color(byte(2)?, byte(4)?, byte(6)?, byte(0)?)),
+        _ => None,
+    }
+}
+
+/* ------------------------------ TMX -> TMJ layout ------------------------------ */
+
+type Node<'a, 'i> = roxmltree::Node<'a, 'i>;
+
+fn attrs(node: Node) -> Map<String, Value> {
+    node.attributes().map(|a| (a.name().to_string(), Value::String(a.value().to_string()))).collect()
+}
+
+fn child<'a, 'i>(node: Node<'a, 'i>, tag: &str) -> Option<Node<'a, 'i>> {
+    node.children().find(|c| c.has_tag_name(tag))
+}
+
+fn tmx_map(map: Node) -> anyhow::Result<Value> {
+    let mut out = attrs(map);
+    let tilesets = map.children().filter(|c| c.has_tag_name("tileset")).map(tmx_tileset).collect::<anyhow::Result<Vec<_>>>()?;
+    out.insert("tilesets".into(), Value::Array(tilesets));
+    out.insert("layers".into(), Value::Array(tmx_layers(map)?));
+    out.insert("properties".into(), tmx_properties(map));
+    Ok(Value::Object(out))
+}
+
+fn tmx_tileset(ts: Node) -> anyhow::Result<Value> {
+    let mut out = attrs(ts);
+    if let Some(img) = child(ts, "image") {
+        out.insert("image".into(), Value::String(img.attribute("source").unwrap_or_default().to_string()));
+    }
+    let tiles: Vec<Value> = ts
+        .children()
+        .filter(|c| c.has_tag_name("tile"))
+        .map(|t| {
+            let frames: Vec<Value> = child(t, "animation")
+                .into_iter()
+                .flat_map(|a| a.children().filter(|f| f.has_tag_name("frame")))
+                .map(|f| Value::Object(attrs(f)))
+                .collect();
+            json!({ "id": t.attribute("id").unwrap_or_default(), "animation": frames })
+        })
+        .collect();
+    out.insert("tiles".into(), Value::Array(tiles));
+    Ok(Value::Object(out))
+}
+
+fn tmx_layers(parent: Node) -> anyhow::Result<Vec<Value>> {
+    let mut layers = Vec::new();
+    for node in parent.children().filter(|c| c.is_element()) {
+        let mut out = attrs(node);
+        let kind = match node.tag_name().name() {
+            "layer" => {
+                let data = child(node, "data").context("<layer> without <data>")?;
+                if child(data, "chunk").is_some() { bail!("chunked (infinite) layers are not supported"); }
+                match data.attribute("encoding") {
+                    Some(enc) => {
+                        out.insert("encoding".into(), enc.into());
+                        out.insert("compression".into(), data.attribute("compression").unwrap_or("").into());
+                        out.insert("data".into(), data.text().unwrap_or("").into());
+                    }
+                    None => {
+                        let gids: Vec<Value> = data
+                            .children()
+                            .filter(|t| t.has_tag_name("tile"))
+                            .map(|t| t.attribute("gid").unwrap_or("0").into())
+                            .collect();
+                        out.insert("data".into(), Value::Array(gids));
+                    }
+                }
+                "tilelayer"
+            }
+            "objectgroup" => {
+                let objects: Vec<Value> = node.children().filter(|c| c.has_tag_name("object")).map(tmx_object).collect();
+                out.insert("objects".into(), Value::Array(objects));
+                "objectgroup"
+            }
+            "group" => {
+                out.insert("layers".into(), Value::Array(tmx_layers(node)?));
+                "group"
+            }
+            "imagelayer" => "imagelayer",
+            _ => continue,
+        };
+        out.insert("type".into(), kind.into());
+        out.insert("properties".into(), tmx_properties(node));
+        layers.push(Value::Object(out));
+    }
+    Ok(layers)
+}
+
+fn tmx_object(o: Node) -> Value {
+    let mut out = attrs(o);
+    if child(o, "point").is_some() { out.insert("point".into(), true.into()); }
+    if child(o, "ellipse").is_some() { out.insert("ellipse".into(), true.into()); }
+    for kind in ["polygon", "polyline"] {
+        if let Some(p) = child(o, kind) {
+            let points: Vec<Value> = p
+                .attribute("points")
+                .unwrap_or("")
+                .split_whitespace()
+                .filter_map(|pair| pair.split_once(','))
+                .map(|(x, y)| json!({ "x": x, "y": y }))
+                .collect();
+            out.insert(kind.into(), Value::Array(points));
+        }
+    }
+    out.insert("properties".into(), tmx_properties(o));
+    Value::Object(out)
+}
+
+fn tmx_properties(node: Node) -> Value {
+    let props = child(node, "properties")
+        .into_iter()
+        .flat_map(|p| p.children().filter(|c| c.has_tag_name("property")))
+        .map(|p| {
+            // multi-line strings are stored as the element's text
+            let value = p.attribute("value").or_else(|| p.text()).unwrap_or("");
+            json!({ "name": p.attribute("name").unwrap_or(""), "type": p.attribute("type").unwrap_or("string"), "value": value })
+        })
+        .collect();
+    Value::Array(props)
+}
@@ -0,0 +1,484 @@
+//! Bitmap fonts.
+//! - Glyphs are regions of white-on-transparent atlas images, tinted when drawn
+//! - Variable advances, per-glyph offsets and kerning pairs
+//! - Loaders: BMFont text `.fnt` (+ page images) and PSF v1/v2 console fonts
+//! - `Font::builtin()`: the original 3x5 pixel font, now covering printable ASCII
+//!
+//! Missing characters fall back to their uppercase / unaccented Latin-1 base letter
+//! (so the uppercase-only built-in font still reads lowercase and accented text),
+//! then to a box.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use anyhow::{bail, ensure, Context};
+
+use crate::math::Vec2;
+use crate::render::canvas::{BlitParams, Canvas, Color, IRect, ImageTransform};
+use crate::render::codec;
+use crate::render::image::Image;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Glyph {
+    /// Index into the font's page images.
+    pub page: usize,
+    /// Pixels of the glyph in its page.
+    pub src: IRect,
+    /// Where `src` is drawn relative to the pen (top of the line).
+    pub offset: (i32, i32),
+    /// How far the pen moves after this glyph.
+    pub advance: i32,
+}
+
+#[derive(Clone, Debug)]
+pub struct Font {
+    pages: Vec<Image>,
+    glyphs: HashMap<char, Glyph>,
+    kerning: HashMap<(char, char), i32>,
+    line_height: i32,
+    base: i32,
+    fallback: Glyph,
+}
+
+impl Font {
+    /// Assemble a font from already-loaded parts. Characters not in `glyphs` use `fallback`.
+    pub fn new(pages: Vec<Image>, glyphs: HashMap<char, Glyph>, line_height: i32, base: i32, fallback: Glyph) -> Self {
+        Self { pages, glyphs, kerning: HashMap::new(), line_height, base, fallback }
+    }
+
+    /// The 3x5 pixel font (6px line height, 4px advance), shared and built once.
+    pub fn builtin() -> &'static Font {
+        static FONT: OnceLock<Font> = OnceLock::new();
+        FONT.get_or_init(build_builtin)
+    }
+
+    /// Distance between baselines of consecutive lines, in pixels at scale 1.
+    pub fn line_height(&self) -> i32 { self.line_height }
+    /// Height of capitals above the baseline (from the top of the line).
+    pub fn base(&self) -> i32 { self.base }
+
+    pub fn set_kerning(&mut self, first: char, second: char, amount: i32) {
+        if amount == 0 { self.kerning.remove(&(first, second)); } else { self.kerning.insert((first, second), amount); }
+    }
+
+    pub fn kerning(&self, first: char, second: char) -> i32 {
+        self.kerning.get(&(first, second)).copied().unwrap_or(0)
+    }
+
+    /// True if `ch` has its own glyph (no fallback).
+    pub fn has_glyph(&self, ch: char) -> bool { self.glyphs.contains_key(&ch) }
+
+    /// Glyph for `ch`, following the fallback chain described in the module docs.
+    pub fn glyph(&self, ch: char) -> &Glyph {
+        self.glyphs.get(&ch)
+            .or_else(|| fold_latin1(ch).and_then(|c| self.glyphs.get(&c)))
+            .unwrap_or(&self.fallback)
+    }
+
+    /// Pen advance for `ch` followed by `next` (kerning included).
+    pub fn advance(&self, ch: char, next: Option<char>) -> i32 {
+        self.glyph(ch).advance + next.map_or(0, |n| self.kerning(ch, n))
+    }
+
+    /// Ink width of a single line in pixels at scale 1 (trailing spacing and whitespace excluded).
+    pub fn measure_line(&self, text: &str) -> i32 { self.measure_chars(text.chars()) }
+
+    /// `measure_line` over any sequence of characters.
+    pub fn measure_chars(&self, chars: impl IntoIterator<Item = char>) -> i32 {
+        let (mut pen, mut right) = (0, 0);
+        let mut chars = chars.into_iter().peekable();
+        while let Some(ch) = chars.next() {
+            let g = self.glyph(ch);
+            // whitespace has no ink, even where its glyph cell isn't empty (the built-in space)
+            if g.src.w > 0 && !ch.is_whitespace() { right = right.max(pen + g.offset.0 + g.src.w); }
+            pen += self.advance(ch, chars.peek().copied());
+        }
+        right
+    }
+
+    /// Draw one line with its top-left at `(x, y)`, glyph pixels scaled by `scale`.
+    /// Returns the pen position after the last glyph.
+    pub fn draw_line(&self, canvas: &mut impl Canvas, x: i32, y: i32, text: &str, color: Color, scale: u32) -> i32 {
+        let s = scale.max(1) as i32;
+        let params = BlitParams { tint: color, ..BlitParams::default() };
+        let mut pen = x;
+        let mut chars = text.chars().peekable();
+        while let Some(ch) = chars.next() {
+            let g = self.glyph(ch);
+            if !g.src.is_empty() {
+                let (gx, gy) = (pen + g.offset.0 * s, y + g.offset.1 * s);
+                let page = &self.pages[g.page];
+                if s == 1 {
+                    canvas.draw_image_region(page, g.src, gx, gy, params);
+                } else {
+                    let xf = ImageTransform {
+                        scale: Vec2::new(s as f32, s as f32),
+                        ..ImageTransform::at(gx as f32, gy as f32)
+                    };
+                    canvas.draw_image_transformed(page, g.src, xf, params);
+                }
+            }
+            pen += self.advance(ch, chars.peek().copied()) * s;
+        }
+        pen
+    }
+
+    // ---------------------------------------------------------------------
+    // BMFont (AngelCode text format)
+    // ---------------------------------------------------------------------
+
+    /// Parse a text `.fnt` descriptor; `pages` are the page images in `page id` order.
+    pub fn from_bmfont(fnt: &str, pages: Vec<Image>) -> anyhow::Result<Self> {
+        let mut glyphs = HashMap::new();
+        let mut kerning = HashMap::new();
+        let (mut line_height, mut base) = (None, None);
+
+        for (n, line) in fnt.lines().enumerate() {
+            let mut tokens = split_fnt_line(line);
+            let Some(tag) = tokens.next() else { continue };
+            let kv: HashMap<&str, &str> = tokens.filter_map(|t| t.split_once('=')).collect();
+            let num = |key: &str| -> anyhow::Result<i32> {
+                kv.get(key)
+                    .with_context(|| format!("line {}: missing {key}", n + 1))?
+                    .parse()
+                    .with_context(|| format!("line {}: bad {key}", n + 1))
+            };
+            match tag {
+                "common" => {
+                    line_height = Some(num("lineHeight")?);
+                    base = Some(num("base")?);
+                }
+                "char" => {
+                    let id = num("id")?;
+                    let Some(ch) = u32::try_from(id).ok().and_then(char::from_u32) else { continue };
+                    let page = num("page").unwrap_or(0) as usize;
+                    ensure!(page < pages.len(), "line {}: char {id} is on page {page}, only {} loaded", n + 1, pages.len());
+                    let src = IRect::new(num("x")?, num("y")?, num("width")?, num("height")?);
+                    ensure!(
+                        src.intersect(&pages[page].bounds()) == src,
+                        "line {}: char {id} lies outside its page image", n + 1
+                    );
+                    glyphs.insert(ch, Glyph { page, src, offset: (num("xoffset")?, num("yoffset")?), advance: num("xadvance")? });
+                }
+                "kerning" => {
+                    let (a, b) = (num("first")?, num("second")?);
+                    if let (Some(a), Some(b)) = (char::from_u32(a as u32), char::from_u32(b as u32)) {
+                        kerning.insert((a, b), num("amount")?);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let (Some(line_height), Some(base)) = (line_height, base) else { bail!("missing 'common' line") };
+        ensure!(!glyphs.is_empty(), "font has no chars");
+        let fallback = glyphs.get(&'?').copied().unwrap_or(Glyph {
+            page: 0,
+            src: IRect::default(),
+            offset: (0, 0),
+            advance: line_height / 2,
+        });
+        Ok(Self { pages, glyphs, kerning, line_height, base, fallback })
+    }
+
+    /// Load a text `.fnt` and the page images it names (relative to the `.fnt`).
+    pub fn load_bmfont(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+        if bytes.starts_with(b"BMF") { bail!("{}: binary BMFont is not supported, export as text", path.display()); }
+        if bytes.starts_with(b"<?xml") || bytes.starts_with(b"<font") {
+            bail!("{}: XML BMFont is not supported, export as text", path.display());
+        }
+        let text = String::from_utf8(bytes).with_context(|| format!("{} is not UTF-8", path.display()))?;
+
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let mut files: Vec<(i32, String)> = Vec::new();
+        for line in text.lines() {
+            let mut tokens = split_fnt_line(line);
+            if tokens.next() != Some("page") { continue; }
+            let kv: HashMap<&str, &str> = tokens.filter_map(|t| t.split_once('=')).collect();
+            let id = kv.get("id").and_then(|v| v.parse().ok()).context("page without id")?;
+            let file = kv.get("file").context("page without file")?.trim_matches('"').to_string();
+            files.push((id, file));
+        }
+        files.sort();
+        let pages = files
+            .iter()
+            .map(|(_, f)| codec::load_image(dir.join(f)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Self::from_bmfont(&text, pages).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    // ---------------------------------------------------------------------
+    // PSF (Linux console fonts)
+    // ---------------------------------------------------------------------
+
+    /// Parse a PSF1 or PSF2 font. Without a unicode table, glyph `i` is mapped to
+    /// the character with code point `i` (ASCII/Latin-1 order).
+    pub fn from_psf(bytes: &[u8]) -> anyhow::Result<Self> {
+        let u32_at = |i: usize| -> anyhow::Result<usize> {
+            let b = bytes.get(i..i + 4).context("truncated PSF header")?;
+            Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+        };
+
+        let (count, width, height, bytes_per_glyph, data, table) = if bytes.starts_with(&[0x36, 0x04]) {
+            ensure!(bytes.len() >= 4, "truncated PSF1 header");
+            let mode = bytes[2];
+            let height = bytes[3] as usize;
+            let count = if mode & 0x01 != 0 { 512 } else { 256 };
+            let end = 4 + count * height;
+            ensure!(bytes.len() >= end, "truncated PSF1 glyph data");
+            let table = if mode & 0x06 != 0 { Some(UnicodeTable::Psf1(&bytes[end..])) } else { None };
+            (count, 8, height, height, &bytes[4..end], table)
+        } else if bytes.starts_with(&[0x72, 0xb5, 0x4a, 0x86]) {
+            let header = u32_at(8)?;
+            let flags = u32_at(12)?;
+            let count = u32_at(16)?;
+            let per = u32_at(20)?;
+            let (height, width) = (u32_at(24)?, u32_at(28)?);
+            ensure!(per >= height * width.div_ceil(8), "PSF2 glyph size too small");
+            let end = header.checked_add(count.checked_mul(per).context("PSF2 too large")?).context("PSF2 too large")?;
+            ensure!(bytes.len() >= end, "truncated PSF2 glyph data");
+            let table = if flags & 0x01 != 0 { Some(UnicodeTable::Psf2(&bytes[end..])) } else { None };
+            (count, width, height, per, &bytes[header..end], table)
+        } else {
+            bail!("not a PSF font");
+        };
+        ensure!(width > 0 && height > 0 && width <= 256 && height <= 256, "unsupported PSF glyph size {width}x{height}");
+
+        // 16 glyphs per atlas row
+        let cols = 16usize;
+        let rows = count.div_ceil(cols);
+        let mut atlas = Image::new((cols * width) as u32, (rows * height) as u32);
+        let row_bytes = width.div_ceil(8);
+        for g in 0..count {
+            let glyph = &data[g * bytes_per_glyph..][..bytes_per_glyph];
+            let (ox, oy) = ((g % cols * width) as i32, (g / cols * height) as i32);
+            for y in 0..height {
+                for x in 0..width {
+                    if glyph[y * row_bytes + x / 8] & (0x80 >> (x % 8)) != 0 {
+                        atlas.set_pixel(ox + x as i32, oy + y as i32, Color::WHITE);
+                    }
+                }
+            }
+        }
+
+        let rect = |g: usize| IRect::new((g % cols * width) as i32, (g / cols * height) as i32, width as i32, height as i32);
+        let glyph = |g: usize| Glyph { page: 0, src: rect(g), offset: (0, 0), advance: width as i32 };
+        let mut glyphs = HashMap::new();
+        match table {
+            Some(t) => t.for_each(count, |g, ch| { glyphs.entry(ch).or_insert(glyph(g)); })?,
+            None => {
+                for g in 0..count {
+                    if let Some(ch) = char::from_u32(g as u32) { glyphs.insert(ch, glyph(g)); }
+                }
+            }
+        }
+        let fallback = glyphs.get(&'?').copied().unwrap_or(glyph(0));
+        let h = height as i32;
+        Ok(Self { pages: vec![atlas], glyphs, kerning: HashMap::new(), line_height: h, base: h, fallback })
+    }
+
+    pub fn load_psf(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+        Self::from_psf(&bytes).with_context(|| format!("parsing {}", path.display()))
+    }
+}
+
+/// BMFont lines are `tag key=value key="quoted value" ...`.
+fn split_fnt_line(line: &str) -> impl Iterator<Item = &str> {
+    let mut rest = line.trim();
+    std::iter::from_fn(move || {
+        rest = rest.trim_start();
+        if rest.is_empty() { return None; }
+        let mut in_quotes = false;
+        let end = rest
+            .char_indices()
+            .find(|&(_, c)| {
+                if c == '"' { in_quotes = !in_quotes; }
+                c.is_whitespace() && !in_quotes
+            })
+            .map_or(rest.len(), |(i, _)| i);
+        let (tok, tail) = rest.split_at(end);
+        rest = tail;
+        Some(tok)
+    })
+}
+
+enum UnicodeTable<'a> {
+    /// u16 LE code points per glyph, 0xFFFE starts sequences, 0xFFFF ends the glyph.
+    Psf1(&'a [u8]),
+    /// UTF-8 per glyph, 0xFE starts sequences, 0xFF ends the glyph.
+    Psf2(&'a [u8]),
+}
+
+impl UnicodeTable<'_> {
+    /// Calls `f(glyph_index, char)` for every single-character mapping.
+    fn for_each(&self, count: usize, mut f: impl FnMut(usize, char)) -> anyhow::Result<()> {
+        match *self {
+            UnicodeTable::Psf1(t) => {
+                let mut words = t.chunks_exact(2).map(|w| u16::from_le_bytes([w[0], w[1]]));
+                for g in 0..count {
+                    let mut in_seq = false;
+                    loop {
+                        match words.next().context("truncated PSF1 unicode table")? {
+                            0xFFFF => break,
+                            0xFFFE => in_seq = true,
+                            u if !in_seq => if let Some(c) = char::from_u32(u as u32) { f(g, c) },
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            UnicodeTable::Psf2(t) => {
+                let mut entries = t.split(|&b| b == 0xFF);
+                for g in 0..count {
+                    let entry = entries.next().context("truncated PSF2 unicode table")?;
+                    // the part before the first 0xFE holds single code points
+                    let singles = entry.split(|&b| b == 0xFE).next().unwrap_or(&[]);
+                    for c in String::from_utf8_lossy(singles).chars() {
+                        if c != char::REPLACEMENT_CHARACTER { f(g, c); }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Uppercase / unaccented stand-in for `ch`, if different.
+fn fold_latin1(ch: char) -> Option<char> {
+    let up = ch.to_uppercase().next()?;
+    let base = match up {
+        'À'..='Æ' => 'A',
+        'Ç' => 'C',
+        'È'..='Ë' => 'E',
+        'Ì'..='Ï' => 'I',
+        'Ð' => 'D',
+        'Ñ' => 'N',
+        'Ò'..='Ö' | 'Ø' => 'O',
+        'Ù'..='Ü' => 'U',
+        'Ý' | 'Ÿ' => 'Y',
+        'Þ' => 'P',
+        '¡' => '!',
+        '¿' => '?',
+        '×' => 'X',
+        '«' => '<',
+        '»' => '>',
+        '\u{a0}' => ' ',
+        c => c,
+    };
+    (base != ch).then_some(base)
+}
+
+/* ------------------------------ built-in 3x5 ------------------------------ */
+
+// 3x5 glyphs encoded as 3-bit rows (MSB at left).
+const GLYPH_W: i32 = 3;
+const GLYPH_H: i32 = 5;
+const BOX: [u8; 5] = [0b111, 0b101, 0b101, 0b101, 0b111];
+
+const BUILTIN_GLYPHS: &[(char, [u8; 5])] = &[
+    (' ', [0, 0, 0, 0, 0]),
+    ('!', [0b010, 0b010, 0b010, 0, 0b010]),
+    ('"', [0b101, 0b101, 0, 0, 0]),
+    ('#', [0b101, 0b111, 0b101, 0b111, 0b101]),
+    ('$', [0b011, 0b110, 0b010, 0b011, 0b110]),
+    ('%', [0b101, 0b001, 0b010, 0b100, 0b101]),
+    ('&', [0b010, 0b101, 0b010, 0b101, 0b011]),
+    ('\'', [0b010, 0b010, 0, 0, 0]),
+    ('(', [0b001, 0b010, 0b010, 0b010, 0b001]),
+    (')', [0b100, 0b010, 0b010, 0b010, 0b100]),
+    ('*', [0b101, 0b010, 0b111, 0b010, 0b101]),
+    ('+', [0b010, 0b010, 0b111, 0b010, 0b010]),
+    (',', [0, 0, 0, 0b010, 0b100]),
+    ('-', [0, 0b111, 0, 0, 0]),
+    ('.', [0, 0, 0, 0, 0b010]),
+    ('/', [0b001, 0b001, 0b010, 0b100, 0b100]),
+    // digits 0-9
+    ('0', [0b111, 0b101, 0b101, 0b101, 0b111]),
+    ('1', [0b010, 0b110, 0b010, 0b010, 0b111]),
+    ('2', [0b111, 0b001, 0b111, 0b100, 0b111]),
+    ('3', [0b111, 0b001, 0b111, 0b001, 0b111]),
+    ('4', [0b101, 0b101, 0b111, 0b001, 0b001]),
+    ('5', [0b111, 0b100, 0b111, 0b001, 0b111]),
+    ('6', [0b111, 0b100, 0b111, 0b101, 0b111]),
+    ('7', [0b111, 0b001, 0b010, 0b100, 0b100]),
+    ('8', [0b111, 0b101, 0b111, 0b101, 0b111]),
+    ('9', [0b111, 0b101, 0b111, 0b001, 0b111]),
+    (':', [0, 0b010, 0, 0b010, 0]),
+    (';', [0, 0b010, 0, 0b010, 0b100]),
+    ('<', [0b001, 0b010, 0b100, 0b010, 0b001]),
+    ('=', [0, 0b111, 0, 0b111, 0]),
+    ('>', [0b100, 0b010, 0b001, 0b010, 0b100]),
+    ('?', [0b110, 0b001, 0b010, 0, 0b010]),
+    ('@', [0b111, 0b101, 0b111, 0b100, 0b111]),
+    // A-Z
+    ('A', [0b010, 0b101, 0b111, 0b101, 0b101]),
+    ('B', [0b110, 0b101, 0b110, 0b101, 0b110]),
+    ('C', [0b011, 0b100, 0b100, 0b100, 0b011]),
+    ('D', [0b110, 0b101, 0b101, 0b101, 0b110]),
+    ('E', [0b111, 0b100, 0b110, 0b100, 0b111]),
+    ('F', [0b111, 0b100, 0b110, 0b100, 0b100]),
+    ('G', [0b011, 0b100, 0b101, 0b101, 0b011]),
+    ('H', [0b101, 0b101, 0b111, 0b101, 0b101]),
+    ('I', [0b111, 0b010, 0b010, 0b010, 0b111]),
+    ('J', [0b001, 0b001, 0b001, 0b101, 0b010]),
+    ('K', [0b101, 0b110, 0b100, 0b110, 0b101]),
+    ('L', [0b100, 0b100, 0b100, 0b100, 0b111]),
+    ('M', [0b101, 0b111, 0b111, 0b101, 0b101]),
+    ('N', [0b101, 0b111, 0b111, 0b111, 0b101]),
+    ('O', [0b111, 0b101, 0b101, 0b101, 0b111]),
+    ('P', [0b110, 0b101, 0b110, 0b100, 0b100]),
+    ('Q', [0b010, 0b101, 0b101, 0b110, 0b011]),
+    ('R', [0b110, 0b101, 0b110, 0b110, 0b101]),
+    ('S', [0b011, 0b100, 0b011, 0b001, 0b110]),
+    ('T', [0b111, 0b010, 0b010, 0b010, 0b010]),
+    ('U', [0b101, 0b101, 0b101, 0b101, 0b111]),
+    ('V', [0b101, 0b101, 0b101, 0b101, 0b010]),
+    ('W', [0b101, 0b101, 0b111, 0b111, 0b101]),
+    ('X', [0b101, 0b101, 0b010, 0b101, 0b101]),
+    ('Y', [0b101, 0b101, 0b010, 0b010, 0b010]),
+    ('Z', [0b111, 0b001, 0b010, 0b100, 0b111]),
+    ('[', [0b110, 0b100, 0b100, 0b100, 0b110]),
+    ('\\', [0b100, 0b100, 0b010, 0b001, 0b001]),
+    (']', [0b011, 0b001, 0b001, 0b001, 0b011]),
+    ('^', [0b010, 0b101, 0, 0, 0]),
+    ('_', [0, 0, 0, 0, 0b111]),
+    ('`', [0b100, 0b010, 0, 0, 0]),
+    ('{', [0b011, 0b010, 0b110, 0b010, 0b011]),
+    ('|', [0b010, 0b010, 0b010, 0b010, 0b010]),
+    ('}', [0b110, 0b010, 0b011, 0b010, 0b110]),
+    ('~', [0, 0b001, 0b111, 0b100, 0]),
+];
+
+fn build_builtin() -> Font {
+    // one row of cells; the last cell is the fallback box
+    let n = BUILTIN_GLYPHS.len() as i32 + 1;
+    let mut atlas = Image::new((n * GLYPH_W) as u32, GLYPH_H as u32);
+    let mut put = |cell: i32, rows: &[u8; 5]| {
+        for (y, bits) in rows.iter().enumerate() {
+            for x in 0..GLYPH_W {
+                if bits & (1 << (GLYPH_W - 1 - x)) != 0 {
+                    atlas.set_pixel(cell * GLYPH_W + x, y as i32, Color::WHITE);
+                }
+            }
+        }
+    };
+    let glyph = |cell: i32| Glyph {
+        page: 0,
+        src: IRect::new(cell * GLYPH_W, 0, GLYPH_W, GLYPH_H),
+        offset: (0, 0),
+        advance: GLYPH_W + 1,
+    };
+
+    let mut glyphs = HashMap::new();
+    for (i, (ch, rows)) in BUILTIN_GLYPHS.iter().enumerate() {
+        put(i as i32, rows);
+        glyphs.insert(*ch, glyph(i as i32));
+    }
+    put(n - 1, &BOX);
+    Font::new(vec![atlas], glyphs, GLYPH_H + 1, GLYPH_H, glyph(n - 1))
+}
@@ -1,11 +1,23 @@
-//! Minimal immediate-mode UI with tiny bitmap text.
+//! Minimal immediate-mode UI with bitmap text.
 //! - Rect hit-testing
 //! - Buttons with centered labels
 //! - Label panels with optional text
-//! Uses a 3x5 uppercase bitmap font drawn via fill_rect_f32.
+//! - Text boxes with wrapped, aligned, color-marked-up text (see `text`)
+//! - Optional nine-slice image skins per widget state (see `skin`)
+//!
+//! Text uses `Font::builtin()` (3x5 pixels) unless another font is set.
+//! Without skins, widgets are flat translucent rectangles.
+
+pub mod font;
+pub mod skin;
+pub mod text;
+
+pub use font::Font;
+pub use skin::{Skin, WidgetState};
+pub use text::{Align, TextLayout, TextStyle};
 
 use crate::input::{Input, MouseButton};
-use crate::render::canvas::{Canvas, Color, CanvasFloatExt};
+use crate::render::canvas::{BlendMode, Canvas, Color, IRect};
 
 #[derive(Clone, Copy)]
 pub struct Rect { pub x: f32, pub y: f32, pub w: f32, pub h: f32 }
@@ -14,53 +26,127 @@ impl Rect {
     pub fn contains(&self, p: crate::prelude::Vec2) -> bool {
         p.x >= self.x && p.x <= self.x + self.w && p.y >= self.y && p.y <= self.y + self.h
     }
+
+    /// Pixel rect for `Canvas::push_clip`, rounded the same way as `fill_rect_f32`.
+    pub fn to_irect(&self) -> IRect {
+        IRect::new(self.x.round() as i32, self.y.round() as i32, self.w.round() as i32, self.h.round() as i32)
+    }
 }
 
 pub struct Ui {
     hot: Option<u64>,
+    #[allow(dead_code)]
     active: Option<u64>,
     next_id: u64,
+    font: Font,
+    enabled: bool,
+    panel_skin: Option<Skin>,
+    button_skin: Option<Skin>,
+}
+
+impl Default for Ui {
+    fn default() -> Self { Self::new() }
 }
 
 impl Ui {
-    pub fn new() -> Self { Self { hot: None, active: None, next_id: 1 } }
-    pub fn begin(&mut self) { self.hot = None; self.next_id = 1; }
+    pub fn new() -> Self {
+        Self {
+            hot: None,
+            active: None,
+            next_id: 1,
+            font: Font::builtin().clone(),
+            enabled: true,
+            panel_skin: None,
+            button_skin: None,
+        }
+    }
+    pub fn begin(&mut self) { self.hot = None; self.next_id = 1; self.enabled = true; }
     fn make_id(&mut self) -> u64 { let id = self.next_id; self.next_id += 1; id }
 
-    /// Translucent panel with optional text (uppercased).
+    pub fn font(&self) -> &Font { &self.font }
+    pub fn set_font(&mut self, font: Font) { self.font = font; }
+
+    /// Skin for labels and text boxes (`Normal` state); `None` = flat.
+    pub fn set_panel_skin(&mut self, skin: Option<Skin>) { self.panel_skin = skin; }
+    /// Skin for buttons; `None` = flat.
+    pub fn set_button_skin(&mut self, skin: Option<Skin>) { self.button_skin = skin; }
+
+    /// Widgets after this call draw `Disabled` and ignore the mouse until re-enabled
+    /// (`begin()` re-enables).
+    pub fn set_enabled(&mut self, enabled: bool) { self.enabled = enabled; }
+
+    /// Translucent panel with optional text.
     pub fn label(&mut self, canvas: &mut impl Canvas, r: Rect, text: &str) {
-        panel(canvas, r);
-        if !text.is_empty() {
-            draw_text_centered(canvas, r, &text.to_uppercase(), Color(255,255,255,200));
-        }
+        let color = self.panel_skin.as_ref().map_or(Color(255,255,255,200), |s| s.text(WidgetState::Normal));
+        canvas.with_blend_mode(BlendMode::Alpha, |canvas| {
+            panel(canvas, self.panel_skin.as_ref(), r);
+            if !text.is_empty() {
+                // inside the 1px border
+                let inner = Rect { x: r.x + 1.0, y: r.y + 1.0, w: r.w - 2.0, h: r.h - 2.0 };
+                canvas.with_clip(inner.to_irect(), |canvas| {
+                    draw_text_centered(canvas, &self.font, r, text, color);
+                });
+            }
+        });
+    }
+
+    /// Panel with wrapped text (markup and `\n` allowed), clipped to the panel.
+    /// `style.max_width` is replaced by the panel's inner width.
+    pub fn text_box(&mut self, canvas: &mut impl Canvas, r: Rect, text: &str, style: &TextStyle) {
+        let inner = Rect { x: r.x + 3.0, y: r.y + 3.0, w: r.w - 6.0, h: r.h - 6.0 };
+        let style = TextStyle { max_width: Some(inner.w.floor() as i32), ..*style };
+        let layout = TextLayout::new(&self.font, text, &style);
+        canvas.with_blend_mode(BlendMode::Alpha, |canvas| {
+            panel(canvas, self.panel_skin.as_ref(), r);
+            canvas.with_clip(inner.to_irect(), |canvas| {
+                layout.draw(canvas, &self.font, inner.x.round() as i32, inner.y.round() as i32);
+            });
+        });
     }
 
     /// Returns true if clicked. Draws the label centered.
     pub fn button(&mut self, input: &Input, canvas: &mut impl Canvas, r: Rect, label: &str) -> bool {
         let id = self.make_id();
-        let hovered = r.contains(input.mouse_pos);
+        let hovered = self.enabled && r.contains(input.mouse_pos);
         if hovered { self.hot = Some(id); }
 
-        let pressed_now = input.mouse_pressed(MouseButton::Left);
-        let just_released = input.mouse_clicked(MouseButton::Left);
-
-        // Colors
-        let base = if hovered { Color(235,235,235,210) } else { Color(220,220,220,180) };
-        let border = if pressed_now && hovered { Color(255,100,200,255) } else { Color(80,80,80,220) };
-
-        // Border (2px) then inner fill
-        canvas.fill_rect_f32(r.x - 1.0, r.y - 1.0, r.w + 2.0, r.h + 2.0, border);
-        canvas.fill_rect_f32(r.x, r.y, r.w, r.h, base);
-
-        draw_text_centered(canvas, r, &label.to_uppercase(), Color(20,20,20,255));
-
-        hovered && just_released
+        let pressed = hovered && input.mouse_pressed(MouseButton::Left);
+        let clicked = hovered && input.mouse_clicked(MouseButton::Left);
+
+        let state = if !self.enabled {
+            WidgetState::Disabled
+        } else if pressed {
+            WidgetState::Active
+        } else if hovered {
+            WidgetState::Hot
+        } else {
+            WidgetState::Normal
+        };
+
+        canvas.with_blend_mode(BlendMode::Alpha, |canvas| {
+            let text = match &self.button_skin {
+                Some(skin) => {
+                    skin.draw(canvas, r.to_irect(), state);
+                    skin.text(state)
+                }
+                None => flat_button(canvas, r, state),
+            };
+            canvas.with_clip(r.to_irect(), |canvas| {
+                draw_text_centered(canvas, &self.font, r, label, text);
+            });
+        });
+
+        clicked
     }
 }
 
 /* ------------------------------ panels ------------------------------ */
 
-fn panel(canvas: &mut impl Canvas, r: Rect) {
+fn panel(canvas: &mut impl Canvas, skin: Option<&Skin>, r: Rect) {
+    if let Some(skin) = skin {
+        skin.draw(canvas, r.to_irect(), WidgetState::Normal);
+        return;
+    }
     canvas.fill_rect_f32(r.x, r.y, r.w, r.h, Color(0,0,0,140));
     // 1px border
     let b = Color(80,80,80,200);
@@ -70,110 +156,37 @@ fn panel(canvas: &mut impl Canvas, r: Rect) {
     canvas.fill_rect_f32(r.x + r.w - 1.0, r.y, 1.0, r.h, b);
 }
 
-/* ------------------------------ tiny font ------------------------------ */
-
-// 3x5 glyphs encoded as 3-bit rows (LSB at left).
-// Only the characters we need for the demo; unknown chars render as a box.
-const GLYPH_W: usize = 3;
-const GLYPH_H: usize = 5;
-
-fn glyph_rows(c: char) -> [u8; GLYPH_H] {
-    use Row as R;
-    match c {
-        ' ' => [0,0,0,0,0],
-        '-' => [0,0b111,0,0,0],
-        '+' => [0b010,0b010,0b111,0b010,0b010],
-        ':' => [0,0b010,0,0b010,0],
-        '(' => [0b001,0b010,0b010,0b010,0b001],
-        ')' => [0b100,0b010,0b010,0b010,0b100],
-        '@' => [0b111,0b101,0b111,0b100,0b111],
-
-        // digits 0-9
-        '0' => [0b111,0b101,0b101,0b101,0b111],
-        '1' => [0b010,0b110,0b010,0b010,0b111],
-        '2' => [0b111,0b001,0b111,0b100,0b111],
-        '3' => [0b111,0b001,0b111,0b001,0b111],
-        '4' => [0b101,0b101,0b111,0b001,0b001],
-        '5' => [0b111,0b100,0b111,0b001,0b111],
-        '6' => [0b111,0b100,0b111,0b101,0b111],
-        '7' => [0b111,0b001,0b010,0b100,0b100],
-        '8' => [0b111,0b101,0b111,0b101,0b111],
-        '9' => [0b111,0b101,0b111,0b001,0b111],
-
-        // A-Z (subset is enough for our labels)
-        'A' => [0b010,0b101,0b111,0b101,0b101],
-        'B' => [0b110,0b101,0b110,0b101,0b110],
-        'C' => [0b011,0b100,0b100,0b100,0b011],
-        'D' => [0b110,0b101,0b101,0b101,0b110],
-        'E' => [0b111,0b100,0b110,0b100,0b111],
-        'F' => [0b111,0b100,0b110,0b100,0b100],
-        'G' => [0b011,0b100,0b101,0b101,0b011],
-        'H' => [0b101,0b101,0b111,0b101,0b101],
-        'I' => [0b111,0b010,0b010,0b010,0b111],
-        'K' => [0b101,0b110,0b100,0b110,0b101],
-        'L' => [0b100,0b100,0b100,0b100,0b111],
-        'M' => [0b101,0b111,0b111,0b101,0b101],
-        'N' => [0b101,0b111,0b111,0b111,0b101],
-        'O' => [0b111,0b101,0b101,0b101,0b111],
-        'P' => [0b110,0b101,0b110,0b100,0b100],
-        'R' => [0b110,0b101,0b110,0b110,0b101],
-        'S' => [0b011,0b100,0b011,0b001,0b110],
-        'T' => [0b111,0b010,0b010,0b010,0b010],
-        'U' => [0b101,0b101,0b101,0b101,0b111],
-        'V' => [0b101,0b101,0b101,0b101,0b010],
-        'W' => [0b101,0b101,0b111,0b111,0b101],
-        'Y' => [0b101,0b101,0b010,0b010,0b010],
-        _ => [0b111,0b101,0b101,0b101,0b111], // unknown -> box
-    }
+// Unskinned button; returns the label color.
+fn flat_button(canvas: &mut impl Canvas, r: Rect, state: WidgetState) -> Color {
+    let base = match state {
+        WidgetState::Hot | WidgetState::Active => Color(235,235,235,210),
+        WidgetState::Normal => Color(220,220,220,180),
+        WidgetState::Disabled => Color(160,160,160,120),
+    };
+    let border = if state == WidgetState::Active { Color(255,100,200,255) } else { Color(80,80,80,220) };
+
+    // 1px border around the inner fill (edges only, so translucent fills don't stack)
+    canvas.fill_rect_f32(r.x - 1.0, r.y - 1.0, r.w + 2.0, 1.0, border);
+    canvas.fill_rect_f32(r.x - 1.0, r.y + r.h, r.w + 2.0, 1.0, border);
+    canvas.fill_rect_f32(r.x - 1.0, r.y, 1.0, r.h, border);
+    canvas.fill_rect_f32(r.x + r.w, r.y, 1.0, r.h, border);
+    canvas.fill_rect_f32(r.x, r.y, r.w, r.h, base);
+
+    if state == WidgetState::Disabled { Color(90,90,90,255) } else { Color(20,20,20,255) }
 }
 
-// Draw a string with a given pixel scale.
-fn draw_text(canvas: &mut impl Canvas, x: f32, y: f32, text: &str, color: Color, scale: f32) {
-    let mut cx = x;
-    for ch in text.chars() {
-        let rows = glyph_rows(ch);
-        for (ry, bits) in rows.iter().enumerate() {
-            // NEW (MSB-left → correct orientation)
-            for cxbit in 0..GLYPH_W {
-                let mask = 1 << (GLYPH_W - 1 - cxbit);
-                if (bits & mask) != 0 {
-                    canvas.fill_rect_f32(
-                        cx + (cxbit as f32) * scale,
-                        y  + (ry as f32) * scale,
-                        scale, scale, color
-                    );
-                }
-            }
-        }
-        // 1px spacing between glyphs
-        cx += (GLYPH_W as f32 + 1.0) * scale;
-    }
-}
+/* ------------------------------ text ------------------------------ */
 
-// Compute scaled width of a string in pixels.
-fn measure_text_px(text: &str, scale: f32) -> f32 {
-    let n = text.chars().count() as f32;
-    ((GLYPH_W as f32 + 1.0) * n - 1.0) * scale
-}
-
-// Center text inside a rect. Scale to fit if needed.
-fn draw_text_centered(canvas: &mut impl Canvas, r: Rect, text: &str, color: Color) {
+// Center one line inside a rect at the largest integer scale that fits.
+fn draw_text_centered(canvas: &mut impl Canvas, font: &Font, r: Rect, text: &str, color: Color) {
     if text.is_empty() { return; }
-    // Choose the largest integer-ish scale that fits height & width.
-    let max_h_scale = (r.h - 4.0).max(4.0) / (GLYPH_H as f32);
-    // Start with height-limited scale; clamp by width if needed.
-    let mut scale = max_h_scale.floor().max(1.0);
-    let mut w_px = measure_text_px(text, scale);
-    if w_px > r.w - 6.0 {
-        scale = ((r.w - 6.0) / ((GLYPH_W as f32 + 1.0) * text.chars().count() as f32 - 1.0)).floor().max(1.0);
-        w_px = measure_text_px(text, scale);
+    let (base, width) = (font.base().max(1) as f32, font.measure_line(text).max(1) as f32);
+    // height-limited scale, then clamp by width
+    let mut scale = ((r.h - 4.0).max(4.0) / base).floor().max(1.0);
+    if width * scale > r.w - 6.0 {
+        scale = ((r.w - 6.0) / width).floor().max(1.0);
     }
-    let x = r.x + (r.w - w_px) * 0.5;
-    let y = r.y + (r.h - (GLYPH_H as f32 * scale)) * 0.5;
-    draw_text(canvas, x, y, text, color, scale);
+    let x = r.x + (r.w - width * scale) * 0.5;
+    let y = r.y + (r.h - base * scale) * 0.5;
+    font.draw_line(canvas, x.round() as i32, y.round() as i32, text, color, scale as u32);
 }
-
-// Simple alias to make row defs readable if you tweak glyphs.
-#[allow(dead_code)]
-enum Row { _0=0, _1=1, _2=2, _3=3, _4=4, _5=5, _6=6, _7=7 }
-
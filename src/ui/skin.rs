@@ -0,0 +1,65 @@
+//! Image skins for `Ui` widgets: one nine-slice per widget state, cut from one image.
+
+use crate::render::canvas::{BlitParams, Canvas, Color, IRect, NineSlice};
+use crate::render::image::Image;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum WidgetState {
+    #[default]
+    Normal,
+    /// Under the mouse.
+    Hot,
+    /// Being pressed.
+    Active,
+    Disabled,
+}
+
+#[derive(Clone, Debug)]
+pub struct Skin {
+    pub image: Image,
+    pub normal: NineSlice,
+    pub hot: NineSlice,
+    pub active: NineSlice,
+    pub disabled: NineSlice,
+    pub text_color: Color,
+    pub disabled_text_color: Color,
+}
+
+impl Skin {
+    /// Every state uses `normal` until set otherwise.
+    pub fn new(image: Image, normal: NineSlice) -> Self {
+        Self {
+            image,
+            normal,
+            hot: normal,
+            active: normal,
+            disabled: normal,
+            text_color: Color(20, 20, 20, 255),
+            disabled_text_color: Color(90, 90, 90, 255),
+        }
+    }
+
+    /// States laid out as equal-sized cells left to right (normal, hot, active, disabled),
+    /// each with the same `border`. A common sprite-sheet layout for buttons.
+    pub fn from_strip(image: Image, cell: IRect, border: i32) -> Self {
+        let at = |i: i32| NineSlice::uniform(IRect { x: cell.x + i * cell.w, ..cell }, border);
+        Self { hot: at(1), active: at(2), disabled: at(3), ..Self::new(image, at(0)) }
+    }
+
+    pub fn slice(&self, state: WidgetState) -> &NineSlice {
+        match state {
+            WidgetState::Normal => &self.normal,
+            WidgetState::Hot => &self.hot,
+            WidgetState::Active => &self.active,
+            WidgetState::Disabled => &self.disabled,
+        }
+    }
+
+    pub fn text(&self, state: WidgetState) -> Color {
+        if state == WidgetState::Disabled { self.disabled_text_color } else { self.text_color }
+    }
+
+    pub fn draw(&self, canvas: &mut impl Canvas, dst: IRect, state: WidgetState) {
+        canvas.draw_nine_slice(&self.image, self.slice(state), dst, BlitParams::default());
+    }
+}
@@ -0,0 +1,210 @@
+//! Multi-line text layout for HUDs, dialogue and UI.
+//! - Word wrap to a max width, explicit `\n` line breaks
+//! - Left / center / right alignment, extra line spacing, integer pixel scale
+//! - Inline color markup: `{#rrggbb}` or `{#rrggbbaa}` starts a span, `{/}` ends it,
+//!   `{{` is a literal `{`
+//!
+//! `TextLayout::new` does the work once; `draw` can then be called every frame.
+//! `measure_text` / `draw_text` are one-shot shortcuts.
+
+use crate::render::canvas::{Canvas, Color};
+
+use super::font::Font;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextStyle {
+    /// Color outside markup spans.
+    pub color: Color,
+    /// Whole-number glyph scale.
+    pub scale: u32,
+    pub align: Align,
+    /// Wrap lines longer than this (pixels); `None` breaks only at `\n`.
+    pub max_width: Option<i32>,
+    /// Extra pixels between lines, on top of the font's line height.
+    pub line_spacing: i32,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self { color: Color::WHITE, scale: 1, align: Align::Left, max_width: None, line_spacing: 0 }
+    }
+}
+
+/// Same-colored piece of one line, positioned relative to the layout's top-left.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextRun {
+    pub x: i32,
+    pub y: i32,
+    pub text: String,
+    pub color: Color,
+}
+
+#[derive(Clone, Debug)]
+pub struct TextLayout {
+    runs: Vec<TextRun>,
+    line_widths: Vec<i32>,
+    width: i32,
+    height: i32,
+    scale: u32,
+}
+
+type Styled = (char, Color);
+
+impl TextLayout {
+    pub fn new(font: &Font, text: &str, style: &TextStyle) -> Self {
+        let s = style.scale.max(1) as i32;
+        let chars = parse_markup(text, style.color);
+        let max = style.max_width.map(|w| (w / s).max(1));
+
+        let mut lines: Vec<Vec<Styled>> = Vec::new();
+        for para in chars.split(|&(c, _)| c == '\n') {
+            match max {
+                Some(max) => wrap(font, para, max, &mut lines),
+                None => lines.push(para.to_vec()),
+            }
+        }
+
+        let line_widths: Vec<i32> = lines.iter().map(|l| font.measure_chars(l.iter().map(|&(c, _)| c)) * s).collect();
+        let widest = line_widths.iter().copied().max().unwrap_or(0);
+        let box_w = style.max_width.unwrap_or(widest);
+        let step = font.line_height() * s + style.line_spacing;
+
+        let mut runs = Vec::new();
+        for (i, (line, &w)) in lines.iter().zip(&line_widths).enumerate() {
+            let x0 = match style.align {
+                Align::Left => 0,
+                Align::Center => (box_w - w) / 2,
+                Align::Right => box_w - w,
+            };
+            let y = i as i32 * step;
+            let mut pen = 0;
+            for (j, &(ch, color)) in line.iter().enumerate() {
+                match runs.last_mut() {
+                    Some(TextRun { y: ry, color: rc, text, .. }) if *ry == y && *rc == color && j > 0 => text.push(ch),
+                    _ => runs.push(TextRun { x: x0 + pen * s, y, text: ch.to_string(), color }),
+                }
+                pen += font.advance(ch, line.get(j + 1).map(|&(c, _)| c));
+            }
+        }
+
+        let n = lines.len() as i32;
+        let height = if n == 0 { 0 } else { n * step - style.line_spacing };
+        Self { runs, line_widths, width: box_w.max(widest), height, scale: s as u32 }
+    }
+
+    /// Bounding box (pixels): the wrap width if set, else the widest line.
+    pub fn size(&self) -> (i32, i32) { (self.width, self.height) }
+    pub fn line_count(&self) -> usize { self.line_widths.len() }
+    /// Ink width of each line (pixels).
+    pub fn line_widths(&self) -> &[i32] { &self.line_widths }
+    pub fn runs(&self) -> &[TextRun] { &self.runs }
+
+    /// Draw with the layout's top-left at `(x, y)`. `font` should be the one used for layout.
+    pub fn draw(&self, canvas: &mut impl Canvas, font: &Font, x: i32, y: i32) {
+        for r in &self.runs {
+            font.draw_line(canvas, x + r.x, y + r.y, &r.text, r.color, self.scale);
+        }
+    }
+}
+
+/// Size of `text` laid out with `style` (see `TextLayout::size`).
+pub fn measure_text(font: &Font, text: &str, style: &TextStyle) -> (i32, i32) {
+    TextLayout::new(font, text, style).size()
+}
+
+/// Lay out and draw in one go; returns the size drawn.
+pub fn draw_text(canvas: &mut impl Canvas, font: &Font, x: i32, y: i32, text: &str, style: &TextStyle) -> (i32, i32) {
+    let layout = TextLayout::new(font, text, style);
+    layout.draw(canvas, font, x, y);
+    layout.size()
+}
+
+/* ------------------------------ markup ------------------------------ */
+
+fn parse_markup(text: &str, base: Color) -> Vec<Styled> {
+    let mut out = Vec::with_capacity(text.len());
+    let mut stack = vec![base];
+    let mut rest = text;
+    while let Some(ch) = rest.chars().next() {
+        let color = *stack.last().unwrap();
+        if ch == '{' {
+            if let Some(tail) = rest.strip_prefix("{{") {
+                out.push(('{', color));
+                rest = tail;
+                continue;
+            }
+            if let Some(tail) = rest.strip_prefix("{/}") {
+                if stack.len() > 1 { stack.pop(); }
+                rest = tail;
+                continue;
+            }
+            if let Some((tag, tail)) = rest[1..].split_once('}')
+                && let Some(c) = tag.strip_prefix('#').and_then(parse_color)
+            {
+                stack.push(c);
+                rest = tail;
+                continue;
+            }
+        }
+        // '\r' never draws; "\r\n" behaves like "\n"
+        if ch != '\r' { out.push((ch, color)); }
+        rest = &rest[ch.len_utf8()..];
+    }
+    out
+}
+
+fn parse_color(hex: &str) -> Option<Color> {
+    let byte = |i: usize| hex.get(i..i + 2).and_then(|b| u8::from_str_radix(b, 16).ok());
+    match hex.len() {
+        6 => Some(Color(byte(0)?, byte(2)?, byte(4)?, 255)),
+        8 => Some(Color(byte(0)?, byte(2)?, byte(4)?, byte(6)?)),
+        _ => None,
+    }
+}
+
+/* ------------------------------ wrapping ------------------------------ */
+
+// Greedy word wrap of one paragraph to `max` pixels (scale 1). Words longer than a
+// line are broken between characters; spaces at a break are dropped.
+fn wrap(font: &Font, para: &[Styled], max: i32, lines: &mut Vec<Vec<Styled>>) {
+    let width = |l: &[Styled]| font.measure_chars(l.iter().map(|&(c, _)| c));
+    let mut line: Vec<Styled> = Vec::new();
+    let mut start = 0usize;
+    for word in para.split(|&(c, _)| c == ' ') {
+        // the space before this word keeps its markup color
+        let space = start.checked_sub(1).map(|i| para[i]);
+        start += word.len() + 1;
+
+        let mut candidate = line.clone();
+        if !line.is_empty() { candidate.extend(space); }
+        candidate.extend_from_slice(word);
+        if line.is_empty() || width(&candidate) <= max {
+            line = candidate;
+        } else {
+            push_line(lines, std::mem::take(&mut line));
+            line.extend_from_slice(word);
+        }
+
+        // break words that don't fit on a line of their own
+        while line.len() > 1 && width(&line) > max {
+            let mut n = line.len() - 1;
+            while n > 1 && width(&line[..n]) > max { n -= 1; }
+            let tail = line.split_off(n);
+            push_line(lines, std::mem::replace(&mut line, tail));
+        }
+    }
+    push_line(lines, line);
+}
+
+fn push_line(lines: &mut Vec<Vec<Styled>>, mut line: Vec<Styled>) {
+    while line.last().is_some_and(|&(c, _)| c == ' ') { line.pop(); }
+    lines.push(line);
+}
@@ -0,0 +1,224 @@
+use daniengine::animation::{Animation, Animator, Frame, PlayMode, SpriteSheet};
+use daniengine::render::canvas::{Color, IRect};
+use daniengine::render::image::Image;
+
+// Quarter-second frames keep the float math exact.
+fn anim(n: i32, mode: PlayMode) -> Animation {
+    let frames = (0..n).map(|i| Frame::new(IRect::new(i * 8, 0, 8, 8), 0.25)).collect();
+    Animation::new("test", frames, mode)
+}
+
+/// Frame index after each of `steps` updates of one frame's length.
+fn sequence(a: &mut Animator, steps: usize) -> Vec<usize> {
+    (0..steps).map(|_| { a.update(0.25); a.frame_index() }).collect()
+}
+
+#[test]
+fn frames_advance_with_time() {
+    let mut a = Animator::new(anim(4, PlayMode::Loop));
+    a.update(0.125);
+    assert_eq!(a.frame_index(), 0);
+    a.update(0.125);
+    assert_eq!(a.frame_index(), 1);
+    // one big step can cross several frames and wrap
+    a.update(0.875);
+    assert_eq!(a.frame_index(), 0);
+    assert_eq!(a.current_frame().unwrap().region, IRect::new(0, 0, 8, 8));
+
+    a.speed = 0.0;
+    a.update(10.0);
+    assert_eq!(a.frame_index(), 0);
+    a.speed = 2.0;
+    a.update(0.25);
+    assert_eq!(a.frame_index(), 2);
+}
+
+#[test]
+fn loop_wraps_forever() {
+    let mut a = Animator::new(anim(3, PlayMode::Loop));
+    assert_eq!(sequence(&mut a, 7), [1, 2, 0, 1, 2, 0, 1]);
+    assert!(!a.is_finished());
+}
+
+#[test]
+fn ping_pong_order() {
+    let mut a = Animator::new(anim(4, PlayMode::PingPong));
+    assert_eq!(sequence(&mut a, 9), [1, 2, 3, 2, 1, 0, 1, 2, 3]);
+    let mut two = Animator::new(anim(2, PlayMode::PingPong));
+    assert_eq!(sequence(&mut two, 4), [1, 0, 1, 0]);
+    let mut one = Animator::new(anim(1, PlayMode::PingPong));
+    assert_eq!(sequence(&mut one, 3), [0, 0, 0]);
+    assert_eq!(anim(4, PlayMode::PingPong).duration(), 1.5);
+    assert_eq!(anim(4, PlayMode::Loop).duration(), 1.0);
+}
+
+#[test]
+fn once_stops_on_the_last_frame() {
+    let mut a = Animator::new(anim(3, PlayMode::Once));
+    assert_eq!(sequence(&mut a, 2), [1, 2]);
+    assert!(!a.is_finished());
+    a.update(0.25);
+    assert!(a.is_finished());
+    a.update(10.0);
+    assert_eq!(a.frame_index(), 2);
+
+    a.restart();
+    assert_eq!((a.frame_index(), a.is_finished()), (0, false));
+    a.update(100.0);
+    assert_eq!((a.frame_index(), a.is_finished()), (2, true));
+}
+
+#[test]
+fn finite_repeat_counts() {
+    let mut a = Animator::new(anim(3, PlayMode::Loop).with_repeat(2));
+    assert_eq!(sequence(&mut a, 8), [1, 2, 0, 1, 2, 2, 2, 2]);
+    assert!(a.is_finished());
+
+    // ping-pong: each direction is one pass
+    let mut a = Animator::new(anim(3, PlayMode::PingPong).with_repeat(3));
+    assert_eq!(sequence(&mut a, 8), [1, 2, 1, 0, 1, 2, 2, 2]);
+    assert!(a.is_finished());
+
+    // restarting resets the count
+    a.restart();
+    assert_eq!(sequence(&mut a, 3), [1, 2, 1]);
+}
+
+#[test]
+fn events_fire_on_entry() {
+    let walk = anim(4, PlayMode::Loop).with_event(0, "start").with_event(2, "step");
+    let mut a = Animator::new(walk);
+    assert_eq!(a.update(0.0), ["start"]);
+    assert!(a.update(0.0).is_empty());
+    assert!(a.update(0.25).is_empty());
+    assert_eq!(a.update(0.25), ["step"]);
+    // crossing several frames reports each entry in order
+    assert_eq!(a.update(1.0), ["start", "step"]);
+
+    // a finished animation doesn't repeat its last event
+    let mut once = Animator::new(anim(2, PlayMode::Once).with_event(1, "end"));
+    assert_eq!(once.update(10.0), ["end"]);
+    assert!(once.update(10.0).is_empty());
+}
+
+#[test]
+fn play_switches_only_on_a_new_name() {
+    let idle = anim(2, PlayMode::Loop).with_event(0, "idle");
+    let mut run = anim(3, PlayMode::Loop).with_event(0, "run");
+    run.name = "run".into();
+    let mut a = Animator::new(idle.clone());
+    a.update(0.25);
+    a.play(&idle);
+    assert_eq!(a.frame_index(), 1);
+    a.play(&run);
+    assert_eq!((a.animation().name.as_str(), a.frame_index()), ("run", 0));
+    assert_eq!(a.update(0.0), ["run"]);
+}
+
+#[test]
+fn zero_length_frames_do_not_stall() {
+    let frames = (0..3).map(|i| Frame::new(IRect::new(i, 0, 1, 1), 0.0)).collect();
+    let mut a = Animator::new(Animation::new("fast", frames, PlayMode::Loop));
+    a.update(1.0);
+    let mut once = Animator::new(Animation::new("empty", Vec::new(), PlayMode::Once));
+    assert!(once.update(1.0).is_empty());
+    assert!(once.current_frame().is_none());
+}
+
+/* ------------------------------ Aseprite ------------------------------ */
+
+fn sheet() -> Image { Image::from_fn(32, 8, |x, _| Color(x as u8 * 8, 0, 0, 255)) }
+
+const FRAMES: [(i32, u32); 4] = [(0, 100), (8, 100), (16, 200), (24, 50)];
+
+fn frame_json(x: i32, ms: u32) -> String {
+    format!(
+        r#"{{ "frame": {{"x":{x},"y":0,"w":8,"h":8}}, "rotated": false, "trimmed": true,
+            "spriteSourceSize": {{"x":1,"y":2,"w":8,"h":8}}, "sourceSize": {{"w":10,"h":12}}, "duration": {ms} }}"#
+    )
+}
+
+const META: &str = r#""meta": {
+    "image": "sheet.png",
+    "frameTags": [
+        { "name": "walk", "from": 0, "to": 3, "direction": "forward" },
+        { "name": "back", "from": 1, "to": 3, "direction": "reverse" },
+        { "name": "bob", "from": 0, "to": 2, "direction": "pingpong" },
+        { "name": "hit", "from": 2, "to": 3, "direction": "forward", "repeat": "1" },
+        { "name": "blink", "from": 0, "to": 1, "direction": "forward", "repeat": "3" }
+    ],
+    "layers": [
+        { "name": "body" },
+        { "name": "events", "cels": [ { "frame": 2, "data": "footstep" }, { "frame": 3, "data": "" } ] }
+    ]
+}"#;
+
+fn hash_json() -> String {
+    let frames: Vec<String> = FRAMES.iter().enumerate().map(|(i, &(x, ms))| format!(r#""hero {i}.ase": {}"#, frame_json(x, ms))).collect();
+    format!("{{ \"frames\": {{ {} }}, {META} }}", frames.join(", "))
+}
+
+fn array_json() -> String {
+    let frames: Vec<String> = FRAMES
+        .iter()
+        .enumerate()
+        .map(|(i, &(x, ms))| frame_json(x, ms).replacen('{', &format!(r#"{{ "filename": "hero {i}.ase","#), 1))
+        .collect();
+    format!("{{ \"frames\": [ {} ], {META} }}", frames.join(", "))
+}
+
+#[test]
+fn aseprite_hash_and_array_exports_match() {
+    let hash = SpriteSheet::from_aseprite_json(&hash_json(), sheet()).unwrap();
+    let array = SpriteSheet::from_aseprite_json(&array_json(), sheet()).unwrap();
+    assert_eq!(hash.frames, array.frames);
+    assert_eq!(hash.animations, array.animations);
+
+    let f = &hash.frames[2];
+    assert_eq!(f.region, IRect::new(16, 0, 8, 8));
+    assert_eq!(f.duration, 0.2);
+    assert_eq!((f.offset, f.size), ((1, 2), (10, 12)));
+    assert_eq!(f.event.as_deref(), Some("footstep"));
+    // empty user data is not an event
+    assert_eq!(hash.frames[3].event, None);
+}
+
+#[test]
+fn aseprite_tags_become_animations() {
+    let s = SpriteSheet::from_aseprite_json(&hash_json(), sheet()).unwrap();
+    let xs = |name: &str| s.animation(name).unwrap().frames.iter().map(|f| f.region.x).collect::<Vec<_>>();
+    let mode = |name: &str| (s.animation(name).unwrap().mode, s.animation(name).unwrap().repeat);
+
+    assert_eq!(xs("walk"), [0, 8, 16, 24]);
+    assert_eq!(mode("walk"), (PlayMode::Loop, None));
+    assert_eq!(xs("back"), [24, 16, 8]);
+    assert_eq!(mode("back"), (PlayMode::Loop, None));
+    assert_eq!(xs("bob"), [0, 8, 16]);
+    assert_eq!(mode("bob"), (PlayMode::PingPong, None));
+    assert_eq!(mode("hit"), (PlayMode::Once, None));
+    assert_eq!(mode("blink"), (PlayMode::Loop, Some(3)));
+    assert!(s.animation("missing").is_none());
+
+    // events travel with their frames
+    let mut a = Animator::new(s.animation("walk").unwrap().clone());
+    assert_eq!(a.update(0.2), ["footstep"]);
+}
+
+#[test]
+fn aseprite_without_tags_is_one_default_loop() {
+    let json = format!("{{ \"frames\": [ {} ], \"meta\": {{}} }}", frame_json(0, 100));
+    let s = SpriteSheet::from_aseprite_json(&json, sheet()).unwrap();
+    assert_eq!(s.animations.len(), 1);
+    assert_eq!((s.animations[0].name.as_str(), s.animations[0].mode), ("default", PlayMode::Loop));
+}
+
+#[test]
+fn aseprite_errors() {
+    let bad = |json: &str| SpriteSheet::from_aseprite_json(json, sheet()).is_err();
+    assert!(bad("not json"));
+    assert!(bad("{}"));
+    assert!(bad(&hash_json().replace(r#""x":24,"y":0,"w":8"#, r#""x":30,"y":0,"w":8"#)));
+    assert!(bad(&hash_json().replacen(r#""rotated": false"#, r#""rotated": true"#, 1)));
+    assert!(bad(&hash_json().replace(r#""from": 1, "to": 3"#, r#""from": 1, "to": 4"#)));
+    assert!(bad(&hash_json().replace(r#""repeat": "3""#, r#""repeat": "lots""#)));
+}
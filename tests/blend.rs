@@ -0,0 +1,60 @@
+use daniengine::render::canvas::{BlendMode, Canvas, Color};
+use daniengine::render::image_canvas::ImageCanvas;
+
+const DST: Color = Color(100, 150, 200, 255);
+const SRC: Color = Color(200, 100, 50, 128);
+
+fn blended(dst: Color, mode: BlendMode, src: Color) -> Color {
+    let mut canvas = ImageCanvas::new(1, 1);
+    canvas.clear(dst);
+    canvas.with_blend_mode(mode, |c| c.fill_rect(0, 0, 1, 1, src));
+    canvas.pixel(0, 0).unwrap()
+}
+
+#[test]
+fn each_mode_over_an_opaque_pixel() {
+    assert_eq!(blended(DST, BlendMode::Replace, SRC), SRC);
+    assert_eq!(blended(DST, BlendMode::Alpha, SRC), Color(150, 125, 125, 255));
+    assert_eq!(blended(DST, BlendMode::Additive, SRC), Color(200, 200, 225, 255));
+    assert_eq!(blended(DST, BlendMode::Multiply, SRC), Color(89, 104, 119, 255));
+    assert_eq!(blended(DST, BlendMode::Screen, SRC), Color(161, 171, 205, 255));
+}
+
+#[test]
+fn opaque_source_matches_the_textbook_formulas() {
+    let src = Color(255, 128, 0, 255);
+    assert_eq!(blended(DST, BlendMode::Alpha, src), src);
+    assert_eq!(blended(DST, BlendMode::Additive, src), Color(255, 255, 200, 255));
+    // dst * src / 255
+    assert_eq!(blended(DST, BlendMode::Multiply, src), Color(100, 75, 0, 255));
+    // 255 - (255 - dst) * (255 - src) / 255, rounded
+    assert_eq!(blended(DST, BlendMode::Screen, src), Color(255, 203, 200, 255));
+}
+
+#[test]
+fn transparent_source_only_changes_replace() {
+    let clear = Color(9, 9, 9, 0);
+    for mode in [BlendMode::Alpha, BlendMode::Additive, BlendMode::Multiply, BlendMode::Screen] {
+        assert_eq!(blended(DST, mode, clear), DST, "{mode:?}");
+    }
+    assert_eq!(blended(DST, BlendMode::Replace, clear), clear);
+}
+
+#[test]
+fn alpha_onto_transparent_keeps_straight_color() {
+    assert_eq!(blended(Color(0, 0, 0, 0), BlendMode::Alpha, SRC), SRC);
+    // two half layers give 75% coverage with the color intact
+    let mut canvas = ImageCanvas::new(1, 1);
+    canvas.fill_rect(0, 0, 1, 1, Color(200, 0, 0, 128));
+    canvas.fill_rect(0, 0, 1, 1, Color(200, 0, 0, 128));
+    assert_eq!(canvas.pixel(0, 0), Some(Color(200, 0, 0, 192)));
+}
+
+#[test]
+fn blend_mode_is_canvas_state_restored_by_with_blend_mode() {
+    let mut canvas = ImageCanvas::new(1, 1);
+    assert_eq!(canvas.blend_mode(), BlendMode::Alpha);
+    canvas.set_blend_mode(BlendMode::Additive);
+    canvas.with_blend_mode(BlendMode::Multiply, |c| assert_eq!(c.blend_mode(), BlendMode::Multiply));
+    assert_eq!(canvas.blend_mode(), BlendMode::Additive);
+}
@@ -0,0 +1,106 @@
+use daniengine::camera::{Camera2D, CameraCanvas};
+use daniengine::math::Vec2;
+use daniengine::particles::{EmitterConfig, ParticleSystem};
+use daniengine::render::canvas::{Canvas, Color, IRect};
+use daniengine::render::image_canvas::ImageCanvas;
+
+const INK: Color = Color(255, 255, 255, 255);
+const BG: Color = Color(0, 0, 0, 255);
+
+fn canvas() -> ImageCanvas {
+    let mut c = ImageCanvas::new(32, 32);
+    c.clear(BG);
+    c
+}
+
+// World (x, y) lands on screen (16 + (x - 2) * 8, ...): world 0.5 is screen 4.
+fn zoomed() -> Camera2D {
+    let mut cam = Camera2D::new(IRect::new(0, 0, 32, 32));
+    cam.position = Vec2::new(2.0, 2.0);
+    cam.zoom = 8.0;
+    cam
+}
+
+fn lit_bounds(c: &ImageCanvas) -> Option<(i32, i32, i32, i32)> {
+    let mut b: Option<(i32, i32, i32, i32)> = None;
+    for y in 0..32 {
+        for x in 0..32 {
+            if c.pixel(x, y) != Some(BG) {
+                let (x0, y0, x1, y1) = b.unwrap_or((x, y, x, y));
+                b = Some((x0.min(x), y0.min(y), x1.max(x), y1.max(y)));
+            }
+        }
+    }
+    b
+}
+
+#[test]
+fn screen_to_world_inverts_world_to_screen() {
+    let viewport = IRect::new(10, 20, 320, 180);
+    for (zoom, rotation) in [(1.0, 0.0), (3.5, 0.0), (0.25, 1.0), (2.0, -2.3), (6.0, std::f32::consts::PI)] {
+        let mut cam = Camera2D::new(viewport);
+        cam.position = Vec2::new(-40.0, 125.5);
+        cam.zoom = zoom;
+        cam.rotation = rotation;
+        for p in [Vec2::new(0.0, 0.0), Vec2::new(-40.0, 125.5), Vec2::new(17.25, -3.5), Vec2::new(-300.0, 900.0)] {
+            let back = cam.screen_to_world(cam.world_to_screen(p));
+            assert!((back.x - p.x).abs() < 1e-3 && (back.y - p.y).abs() < 1e-3, "zoom {zoom} rot {rotation}: {p:?} -> {back:?}");
+        }
+    }
+}
+
+#[test]
+fn world_to_screen_applies_zoom_and_rotation() {
+    let mut cam = Camera2D::new(IRect::new(0, 0, 100, 100));
+    cam.position = Vec2::new(10.0, 10.0);
+    cam.zoom = 2.0;
+    cam.rotation = std::f32::consts::FRAC_PI_2;
+    // the camera's center is the viewport center; a world step along +x turns to -y on screen
+    let c = cam.world_to_screen(Vec2::new(10.0, 10.0));
+    assert!((c.x - 50.0).abs() < 1e-4 && (c.y - 50.0).abs() < 1e-4);
+    let p = cam.world_to_screen(Vec2::new(15.0, 10.0));
+    assert!((p.x - 50.0).abs() < 1e-4 && (p.y - 40.0).abs() < 1e-4, "{p:?}");
+}
+
+// Generic code only knows `C: Canvas`; the f32 hooks still dispatch to `CameraCanvas`.
+fn draw_generic<C: Canvas>(c: &mut C) {
+    c.fill_rect_f32(0.5, 0.5, 1.0, 1.0, INK);
+}
+
+#[test]
+fn generic_f32_calls_keep_sub_pixel_precision() {
+    let mut c = canvas();
+    draw_generic(&mut CameraCanvas::new(&mut c, zoomed()));
+    // rounding to whole world units first would have put it at 8..16
+    assert_eq!(lit_bounds(&c), Some((4, 4, 11, 11)));
+
+    let mut c = canvas();
+    CameraCanvas::new(&mut c, zoomed()).draw_line_f32(0.5, 0.5, 2.5, 0.5, INK);
+    assert_eq!(lit_bounds(&c), Some((4, 4, 20, 4)));
+
+    let mut c = canvas();
+    CameraCanvas::new(&mut c, zoomed()).draw_circle_f32(2.0, 2.0, 0.5, INK);
+    assert_eq!(lit_bounds(&c), Some((12, 12, 20, 20)));
+}
+
+#[test]
+fn particles_keep_sub_pixel_positions_under_zoom() {
+    let mut ps = ParticleSystem::new(4);
+    ps.emit_burst([0.5, 0.5], EmitterConfig {
+        count: 1,
+        speed_min: 0.0,
+        speed_max: 0.0,
+        spread_radians: 0.0,
+        base_direction: 0.0,
+        life_min: 1.0,
+        life_max: 1.0,
+        size_min: 1.0,
+        size_max: 1.0,
+        start_color: INK,
+        end_color: INK,
+    });
+    let mut c = canvas();
+    ps.draw(&mut CameraCanvas::new(&mut c, zoomed()));
+    assert_eq!(lit_bounds(&c), Some((4, 4, 11, 11)));
+    assert_eq!(c.pixel(4, 4), Some(INK));
+}
@@ -0,0 +1,95 @@
+use daniengine::camera::{Camera2D, CameraRig};
+use daniengine::math::Vec2;
+use daniengine::physics::{Aabb, Body};
+use daniengine::render::canvas::IRect;
+
+fn rig(seed: u32) -> CameraRig {
+    // viewport center is (100, 50), so the camera starts there
+    CameraRig::new(Camera2D::new(IRect::new(0, 0, 200, 100)), seed)
+}
+
+fn body_at(x: f32, y: f32) -> Body {
+    Body { pos: Vec2::new(x, y), vel: Vec2::default(), size: Vec2::default() }
+}
+
+fn assert_near(a: Vec2, b: Vec2) {
+    assert!((a.x - b.x).abs() < 1e-3 && (a.y - b.y).abs() < 1e-3, "{a:?} != {b:?}");
+}
+
+#[test]
+fn shake_is_deterministic_for_a_seed() {
+    let run = |seed| {
+        let mut r = rig(seed);
+        r.add_trauma(0.8);
+        (0..20)
+            .map(|i| {
+                r.update(if i % 3 == 0 { 1.0 / 30.0 } else { 1.0 / 60.0 });
+                let v = r.view();
+                (v.position.x, v.position.y, v.rotation)
+            })
+            .collect::<Vec<_>>()
+    };
+    let a = run(7);
+    assert_eq!(a, run(7));
+    assert_ne!(a, run(8));
+    // trauma > 0 actually moves the view
+    assert!(a.iter().any(|&(x, y, rot)| x != 100.0 || y != 50.0 || rot != 0.0));
+}
+
+#[test]
+fn shake_stops_when_trauma_decays() {
+    let mut r = rig(1);
+    r.add_trauma(0.5);
+    r.update(0.1);
+    assert!(r.trauma() > 0.0);
+    for _ in 0..10 { r.update(0.1); }
+    assert_eq!(r.trauma(), 0.0);
+    let v = r.view();
+    assert_eq!((v.position.x, v.position.y, v.rotation), (100.0, 50.0, 0.0));
+}
+
+#[test]
+fn target_inside_dead_zone_does_not_move_camera() {
+    let mut r = rig(0);
+    r.dead_zone = Vec2::new(16.0, 12.0);
+    r.follow(&body_at(110.0, 58.0));
+    for _ in 0..60 { r.update(1.0 / 60.0); }
+    assert_eq!((r.camera.position.x, r.camera.position.y), (100.0, 50.0));
+}
+
+#[test]
+fn target_outside_dead_zone_pulls_camera_to_its_edge() {
+    let mut r = rig(0);
+    r.dead_zone = Vec2::new(16.0, 12.0);
+    r.follow(&body_at(150.0, 20.0));
+    for _ in 0..600 { r.update(1.0 / 60.0); }
+    // the target ends on the dead-zone border, not the view center
+    assert_near(r.camera.position, Vec2::new(134.0, 32.0));
+}
+
+#[test]
+fn bounds_clamp_at_every_edge() {
+    let mut r = rig(0);
+    r.bounds = Some(Aabb { x: 0.0, y: 0.0, w: 400.0, h: 300.0 });
+    // half view is 100x50 world units at zoom 1
+    r.follow(&body_at(-500.0, -500.0));
+    r.snap_to_target();
+    assert_near(r.camera.position, Vec2::new(100.0, 50.0));
+    r.follow(&body_at(1000.0, 1000.0));
+    r.snap_to_target();
+    assert_near(r.camera.position, Vec2::new(300.0, 250.0));
+
+    // zooming in shrinks the half view, so the clamp moves outward
+    r.camera.zoom = 2.0;
+    r.update(1.0 / 60.0);
+    assert_near(r.camera.position, Vec2::new(350.0, 275.0));
+}
+
+#[test]
+fn bounds_smaller_than_the_view_center_it() {
+    let mut r = rig(0);
+    r.bounds = Some(Aabb { x: 10.0, y: 20.0, w: 100.0, h: 60.0 });
+    r.follow(&body_at(500.0, -300.0));
+    r.snap_to_target();
+    assert_near(r.camera.position, Vec2::new(60.0, 50.0));
+}
@@ -0,0 +1,134 @@
+use std::path::{Path, PathBuf};
+
+use daniengine::render::canvas::{Canvas, Color};
+use daniengine::render::capture::{Capture, GifFrame, encode_gif};
+use daniengine::render::codec::load_image;
+use daniengine::render::image_canvas::ImageCanvas;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("daniengine-capture-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn canvas(w: u32, h: u32, color: Color) -> ImageCanvas {
+    let mut c = ImageCanvas::new(w, h);
+    c.clear(color);
+    c
+}
+
+/// (frame count, delays in centiseconds, first pixel of each frame)
+fn decode(bytes: &[u8]) -> (usize, Vec<u16>, Vec<[u8; 4]>) {
+    let mut opts = gif::DecodeOptions::new();
+    opts.set_color_output(gif::ColorOutput::RGBA);
+    let mut dec = opts.read_info(bytes).expect("valid GIF");
+    let (mut delays, mut firsts) = (Vec::new(), Vec::new());
+    while let Some(frame) = dec.read_next_frame().unwrap() {
+        delays.push(frame.delay);
+        firsts.push(frame.buffer[..4].try_into().unwrap());
+    }
+    (delays.len(), delays, firsts)
+}
+
+fn frames(dts: &[f32]) -> Vec<GifFrame> {
+    dts.iter().map(|&dt| GifFrame { rgba: [200, 40, 40, 255].repeat(4 * 4), dt }).collect()
+}
+
+fn files(dir: &Path) -> Vec<String> {
+    let mut names: Vec<_> = std::fs::read_dir(dir).unwrap().map(|e| e.unwrap().file_name().into_string().unwrap()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn gif_delays_round_the_running_time() {
+    // 30 fps: 3.33cs each; rounding every delay alone would give 3,3,3,3,3,3 (18cs, not 20)
+    let bytes = encode_gif(4, 4, &frames(&[1.0 / 30.0; 6]), 10).unwrap();
+    let (n, delays, _) = decode(&bytes);
+    assert_eq!(n, 6);
+    assert_eq!(delays, [3, 4, 3, 3, 4, 3]);
+    assert_eq!(delays.iter().sum::<u16>(), 20);
+}
+
+#[test]
+fn gif_delays_are_clamped_to_two_centiseconds() {
+    let bytes = encode_gif(4, 4, &frames(&[0.004, -1.0, 0.0, 0.25]), 10).unwrap();
+    assert_eq!(decode(&bytes).1, [2, 2, 2, 25]);
+}
+
+#[test]
+fn gif_rejects_mismatched_frames() {
+    let mut f = frames(&[0.1]);
+    f[0].rgba.pop();
+    assert!(encode_gif(4, 4, &f, 10).is_err());
+    assert!(encode_gif(70_000, 1, &[], 10).is_err());
+}
+
+#[test]
+fn recording_stops_after_the_requested_frame_count() {
+    let dir = temp_dir("count");
+    let mut cap = Capture::new(&dir, "clip");
+    cap.record_gif(0);
+    assert!(!cap.is_recording());
+
+    cap.record_gif(3);
+    cap.record_gif(10); // ignored while recording
+    let colors = [Color(255, 0, 0, 255), Color(0, 255, 0, 255), Color(0, 0, 255, 255), Color(9, 9, 9, 255)];
+    // each frame's delay is the dt of the call after it; the last reuses its own
+    let dts = [0.1, 0.04, 0.06, 0.5];
+    let mut written = Vec::new();
+    for (color, dt) in colors.iter().zip(dts) {
+        written.push(cap.capture_frame(&canvas(4, 4, *color), dt).unwrap());
+    }
+    assert!(!cap.is_recording());
+    assert!(written[0].is_empty() && written[1].is_empty() && written[3].is_empty());
+    assert_eq!(written[2], [dir.join("clip-0001.gif")]);
+
+    let (n, delays, firsts) = decode(&std::fs::read(&written[2][0]).unwrap());
+    assert_eq!(n, 3);
+    assert_eq!(delays, [4, 6, 6]);
+    assert_eq!(firsts, [[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255]]);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn resizing_mid_clip_keeps_the_frames_so_far() {
+    let dir = temp_dir("resize");
+    let mut cap = Capture::new(&dir, "clip");
+    cap.record_gif(5);
+    assert!(cap.capture_frame(&canvas(4, 4, Color(1, 2, 3, 255)), 0.1).unwrap().is_empty());
+    assert!(cap.capture_frame(&canvas(4, 4, Color(1, 2, 3, 255)), 0.1).unwrap().is_empty());
+    let written = cap.capture_frame(&canvas(8, 4, Color(1, 2, 3, 255)), 0.1).unwrap();
+    assert_eq!(written.len(), 1);
+    assert_eq!(decode(&std::fs::read(&written[0]).unwrap()).0, 2);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn screenshots_are_numbered_and_skip_existing_files() {
+    let dir = temp_dir("shots");
+    let mut cap = Capture::new(&dir, "shot");
+    let frame = canvas(3, 2, Color(10, 20, 30, 255));
+
+    // nothing pending, nothing written (and no directory created)
+    assert!(cap.capture_frame(&frame, 0.016).unwrap().is_empty());
+    assert!(!dir.exists());
+
+    cap.screenshot();
+    assert_eq!(cap.capture_frame(&frame, 0.016).unwrap(), [dir.join("shot-0001.png")]);
+    assert!(cap.capture_frame(&frame, 0.016).unwrap().is_empty());
+
+    std::fs::write(dir.join("shot-0002.png"), b"taken").unwrap();
+    cap.screenshot();
+    assert_eq!(cap.capture_frame(&frame, 0.016).unwrap(), [dir.join("shot-0003.png")]);
+
+    // GIFs share the counter
+    cap.record_gif(1);
+    assert_eq!(cap.capture_frame(&frame, 0.016).unwrap(), [dir.join("shot-0004.gif")]);
+
+    assert_eq!(files(&dir), ["shot-0001.png", "shot-0002.png", "shot-0003.png", "shot-0004.gif"]);
+    let img = load_image(dir.join("shot-0001.png")).unwrap();
+    assert_eq!(img.size(), (3, 2));
+    assert_eq!(img.pixel(2, 1), Some(Color(10, 20, 30, 255)));
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,87 @@
+use daniengine::math::Vec2;
+use daniengine::render::canvas::{BlitParams, Canvas, CanvasFloatExt, Color, IRect, ImageTransform, LineCap};
+use daniengine::render::image::Image;
+use daniengine::render::image_canvas::ImageCanvas;
+use daniengine::ui::font::Font;
+
+const BG: Color = Color(0, 0, 0, 255);
+const INK: Color = Color(255, 255, 255, 255);
+
+fn canvas() -> ImageCanvas {
+    let mut c = ImageCanvas::new(16, 16);
+    c.clear(BG);
+    c
+}
+
+type Draw = Box<dyn Fn(&mut ImageCanvas)>;
+
+fn painted(c: &ImageCanvas) -> Vec<(i32, i32)> {
+    (0..16).flat_map(|y| (0..16).map(move |x| (x, y))).filter(|&(x, y)| c.pixel(x, y) != Some(BG)).collect()
+}
+
+fn inside(r: IRect) -> impl Fn(&(i32, i32)) -> bool {
+    move |&(x, y)| x >= r.x && y >= r.y && x < r.x + r.w && y < r.y + r.h
+}
+
+#[test]
+fn nested_clips_intersect_and_pop_restores() {
+    let mut c = canvas();
+    assert_eq!(c.clip_rect(), IRect::new(0, 0, 16, 16));
+    c.push_clip(IRect::new(2, 2, 8, 8));
+    c.push_clip(IRect::new(6, -5, 20, 9));
+    assert_eq!(c.clip_rect(), IRect::new(6, 2, 4, 2));
+    c.fill_rect(0, 0, 16, 16, INK);
+    assert_eq!(painted(&c).len(), 8);
+
+    c.pop_clip();
+    assert_eq!(c.clip_rect(), IRect::new(2, 2, 8, 8));
+    c.pop_clip();
+    assert_eq!(c.clip_rect(), IRect::new(0, 0, 16, 16));
+    // unbalanced pop is ignored
+    c.pop_clip();
+    assert_eq!(c.clip_rect(), IRect::new(0, 0, 16, 16));
+}
+
+#[test]
+fn disjoint_clip_draws_nothing() {
+    let mut c = canvas();
+    c.with_clip(IRect::new(0, 0, 4, 4), |c| {
+        c.with_clip(IRect::new(8, 8, 4, 4), |c| {
+            assert!(c.clip_rect().is_empty());
+            c.fill_rect(0, 0, 16, 16, INK);
+            c.clear(INK);
+        });
+    });
+    assert!(painted(&c).is_empty());
+}
+
+#[test]
+fn every_primitive_honors_the_clip() {
+    let clip = IRect::new(4, 4, 6, 5);
+    let img = Image::from_fn(16, 16, |_, _| INK);
+    let draws: Vec<(&str, Draw)> = vec![
+        ("clear", Box::new(|c| c.clear(INK))),
+        ("line", Box::new(|c| c.draw_line(0, 0, 15, 15, INK))),
+        ("circle", Box::new(|c| c.draw_circle(7, 7, 3, INK))),
+        ("fill_circle", Box::new(|c| c.fill_circle(7, 7, 6, INK))),
+        ("ellipse", Box::new(|c| c.fill_ellipse(7, 7, 7, 3, INK))),
+        ("rounded", Box::new(|c| c.fill_rounded_rect(0, 0, 16, 16, 4, INK))),
+        ("polygon", Box::new(|c| c.fill_polygon(&[(0, 0), (16, 2), (8, 16)], INK))),
+        ("aa_line", Box::new(|c| c.draw_line_aa_f32(0.0, 3.0, 16.0, 12.0, INK))),
+        ("thick", Box::new(|c| c.draw_thick_line_f32(Vec2::new(0.0, 7.0), Vec2::new(16.0, 7.0), 6.0, LineCap::Round, INK))),
+        ("blit", Box::new(move |c| c.draw_image(&img, 0, 0, BlitParams::default()))),
+        ("transformed", Box::new({
+            let img = Image::from_fn(8, 8, |_, _| INK);
+            let xf = ImageTransform { rotation: 0.7, scale: Vec2::new(2.0, 2.0), ..ImageTransform::centered(7.0, 7.0, 8, 8) };
+            move |c| c.draw_image_transformed(&img, img.bounds(), xf, BlitParams::default())
+        })),
+        ("text", Box::new(|c| { Font::builtin().draw_line(c, 0, 5, "WWWWWW", INK, 2); })),
+    ];
+    for (name, draw) in draws {
+        let mut c = canvas();
+        c.with_clip(clip, |c| draw(c));
+        let px = painted(&c);
+        assert!(!px.is_empty(), "{name} drew nothing");
+        assert!(px.iter().all(inside(clip)), "{name} bled outside the clip");
+    }
+}
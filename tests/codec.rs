@@ -0,0 +1,139 @@
+use daniengine::render::canvas::Color;
+use daniengine::render::codec::{self, PpmFormat};
+use daniengine::render::image::Image;
+
+#[test]
+fn plain_ppm_lines_fit_in_70_chars_and_round_trip() {
+    let (w, h) = (30, 3);
+    let rgba: Vec<u8> = (0..w * h * 4).map(|i| if i % 4 == 3 { 255 } else { (i * 37 % 256) as u8 }).collect();
+    let ppm = codec::encode_ppm(w, h, &rgba, PpmFormat::Plain).unwrap();
+
+    let text = std::str::from_utf8(&ppm).unwrap();
+    assert!(text.lines().all(|l| l.len() <= 70), "{text}");
+
+    let img = codec::decode_ppm(&ppm).unwrap();
+    assert_eq!(img.size(), (w, h));
+    assert_eq!(img.pixels(), &rgba[..]);
+}
+
+fn qoi(width: u32, height: u32, channels: u8, ops: &[u8]) -> Vec<u8> {
+    let mut out = b"qoif".to_vec();
+    out.extend_from_slice(&width.to_be_bytes());
+    out.extend_from_slice(&height.to_be_bytes());
+    out.extend_from_slice(&[channels, 0]);
+    out.extend_from_slice(ops);
+    out.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 1]);
+    out
+}
+
+fn err(r: anyhow::Result<Image>) -> String {
+    format!("{:#}", r.expect_err("expected an error"))
+}
+
+#[test]
+fn qoi_decodes_every_op() {
+    let ops = [
+        0xFE, 10, 20, 30, // RGB
+        0x76, // DIFF: r+1, g-1, b+0
+        0xA5, 0x6A, // LUMA: g+5, r+3, b+7
+        0xC1, // RUN of 2
+        0xFF, 1, 2, 3, 4, // RGBA
+        0x09, // INDEX -> first pixel
+        0x07, // INDEX -> second pixel
+    ];
+    let img = codec::decode_image(&qoi(4, 2, 4, &ops)).unwrap();
+    let expected = [
+        Color(10, 20, 30, 255), Color(11, 19, 30, 255), Color(14, 24, 37, 255), Color(14, 24, 37, 255),
+        Color(14, 24, 37, 255), Color(1, 2, 3, 4), Color(10, 20, 30, 255), Color(11, 19, 30, 255),
+    ];
+    for (i, c) in expected.iter().enumerate() {
+        assert_eq!(img.pixel(i as i32 % 4, i as i32 / 4), Some(*c), "pixel {i}");
+    }
+}
+
+#[test]
+fn qoi_rejects_bad_input() {
+    let px = [0xFE, 1, 2, 3];
+    assert!(err(codec::decode_qoi(b"qoif")).contains("too short"));
+    let mut bad_magic = qoi(1, 1, 4, &px);
+    bad_magic[..4].copy_from_slice(b"qoix");
+    assert!(err(codec::decode_qoi(&bad_magic)).contains("bad magic"));
+    assert!(err(codec::decode_qoi(&qoi(0, 1, 4, &px))).contains("zero-sized"));
+    assert!(err(codec::decode_qoi(&qoi(1, 1, 5, &px))).contains("channel count"));
+    assert!(err(codec::decode_qoi(&qoi(100_000, 100_000, 4, &px))).contains("too large"));
+    let mut no_end = qoi(1, 1, 4, &px);
+    no_end.truncate(no_end.len() - 1);
+    assert!(err(codec::decode_qoi(&no_end)).contains("end marker"));
+    assert!(err(codec::decode_qoi(&qoi(2, 1, 4, &px))).contains("truncated"));
+    assert!(err(codec::decode_qoi(&qoi(1, 1, 4, &[0xFE, 1]))).contains("truncated"));
+}
+
+fn sample_rgba(w: u32, h: u32) -> Vec<u8> {
+    (0..w * h).flat_map(|i| [(i * 40) as u8, (255 - i * 20) as u8, (i * 7) as u8, (255 - i * 30) as u8]).collect()
+}
+
+#[test]
+fn png_round_trips_rgba() {
+    let rgba = sample_rgba(3, 2);
+    let png = codec::encode_png(3, 2, &rgba).unwrap();
+    let img = codec::decode_image(&png).unwrap();
+    assert_eq!(img.size(), (3, 2));
+    assert_eq!(img.pixels(), &rgba[..]);
+}
+
+#[test]
+fn png_expands_grayscale() {
+    let mut bytes = Vec::new();
+    {
+        let mut enc = png::Encoder::new(&mut bytes, 2, 1);
+        enc.set_color(png::ColorType::Grayscale);
+        enc.set_depth(png::BitDepth::Eight);
+        enc.write_header().unwrap().write_image_data(&[0, 200]).unwrap();
+    }
+    let img = codec::decode_png(&bytes).unwrap();
+    assert_eq!(img.pixels(), &[0, 0, 0, 255, 200, 200, 200, 255]);
+}
+
+#[test]
+fn png_and_unknown_formats_report_errors() {
+    assert!(codec::decode_png(b"\x89PNG\r\n\x1a\nnot really").is_err());
+    assert!(err(codec::decode_image(b"GIF89a")).contains("unrecognized"));
+    let mut png = codec::encode_png(3, 2, &sample_rgba(3, 2)).unwrap();
+    png.truncate(png.len() / 2);
+    assert!(codec::decode_png(&png).is_err());
+}
+
+#[test]
+fn binary_ppm_round_trips_and_drops_alpha() {
+    let rgba = sample_rgba(3, 2);
+    let ppm = codec::encode_ppm(3, 2, &rgba, PpmFormat::Binary).unwrap();
+    let img = codec::decode_image(&ppm).unwrap();
+    let opaque: Vec<u8> = rgba.chunks_exact(4).flat_map(|p| [p[0], p[1], p[2], 255]).collect();
+    assert_eq!(img.pixels(), &opaque[..]);
+}
+
+#[test]
+fn ppm_header_comments_and_16_bit_samples() {
+    let ascii = b"P3 # comment\n2 1\n# another\n15\n15 0 0  0 15 15\n";
+    assert_eq!(codec::decode_ppm(ascii).unwrap().pixels(), &[255, 0, 0, 255, 0, 255, 255, 255]);
+
+    let mut wide = b"P6\n1 1\n65535\n".to_vec();
+    wide.extend_from_slice(&[0xFF, 0xFF, 0x80, 0x00, 0x00, 0x00]);
+    assert_eq!(codec::decode_ppm(&wide).unwrap().pixels(), &[255, 128, 0, 255]);
+}
+
+#[test]
+fn ppm_rejects_bad_input() {
+    assert!(err(codec::decode_ppm(b"")).contains("missing magic"));
+    assert!(err(codec::decode_ppm(b"P5 1 1 255 x")).contains("unsupported magic"));
+    assert!(err(codec::decode_ppm(b"P3 1")).contains("missing height"));
+    assert!(err(codec::decode_ppm(b"P3 1 x 255")).contains("invalid height"));
+    assert!(err(codec::decode_ppm(b"P3 0 1 255")).contains("zero-sized"));
+    assert!(err(codec::decode_ppm(b"P3 1 1 0 0 0 0")).contains("maxval 0"));
+    assert!(err(codec::decode_ppm(b"P3 1 1 70000 0 0 0")).contains("maxval 70000"));
+    assert!(err(codec::decode_ppm(b"P6 100000 100000 255\n")).contains("too large"));
+    assert!(err(codec::decode_ppm(b"P6 2 1 255\nabc")).contains("truncated"));
+    assert!(err(codec::decode_ppm(b"P3 1 1 255 1 2")).contains("truncated"));
+    assert!(err(codec::decode_ppm(b"P3 1 1 255 1 2 z")).contains("invalid sample"));
+    assert!(err(codec::decode_ppm(b"P3 1 1 15 1 2 16")).contains("exceeds maxval"));
+}
@@ -0,0 +1,144 @@
+use daniengine::render::canvas::{BlendMode, BlitParams, Canvas, Color, IRect};
+use daniengine::render::draw_list::{DrawCmd, DrawList};
+use daniengine::render::image::Image;
+use daniengine::render::image_canvas::ImageCanvas;
+
+const RED: Color = Color(255, 0, 0, 255);
+const GREEN: Color = Color(0, 255, 0, 255);
+const BLUE: Color = Color(0, 0, 255, 255);
+
+fn rect(list: &mut DrawList, x: i32, color: Color) { list.fill_rect(x, 0, 4, 4, color); }
+
+#[test]
+fn sort_orders_by_z_then_recording_order() {
+    let mut list = DrawList::new(32, 32);
+    list.set_z(1);
+    rect(&mut list, 0, RED);
+    list.set_z(0);
+    rect(&mut list, 1, GREEN);
+    list.with_z(1, |l| rect(l, 2, BLUE));
+    rect(&mut list, 3, RED);
+    assert_eq!(list.z(), 0);
+
+    list.sort();
+    let order: Vec<_> = list.items().iter().map(|it| (it.z, it.seq)).collect();
+    assert_eq!(order, [(0, 1), (0, 3), (1, 0), (1, 2)]);
+    // sorting again changes nothing
+    list.sort();
+    assert_eq!(list.items().iter().map(|it| (it.z, it.seq)).collect::<Vec<_>>(), order);
+}
+
+// Draw the same scene through any canvas.
+fn scene(c: &mut impl Canvas) {
+    c.clear(Color(10, 10, 10, 255));
+    c.fill_rect(2, 2, 12, 8, RED);
+    c.push_clip(IRect::new(4, 4, 8, 8));
+    c.fill_circle(8, 8, 6, GREEN);
+    c.set_blend_mode(BlendMode::Additive);
+    c.draw_line(0, 15, 15, 0, Color(0, 0, 200, 255));
+    c.set_blend_mode(BlendMode::Alpha);
+    c.pop_clip();
+    let sprite = Image::from_fn(3, 2, |x, y| Color(40 * x as u8, 0, 90 * y as u8, 200));
+    c.draw_image(&sprite, 11, 12, BlitParams { flip_x: true, ..BlitParams::default() });
+}
+
+#[test]
+fn replay_matches_drawing_directly() {
+    let mut direct = ImageCanvas::new(16, 16);
+    scene(&mut direct);
+
+    let mut list = DrawList::new(16, 16);
+    scene(&mut list);
+    let mut replayed = ImageCanvas::new(16, 16);
+    replayed.set_blend_mode(BlendMode::Multiply);
+    list.replay(&mut replayed);
+
+    assert_eq!(replayed.blend_mode(), BlendMode::Multiply);
+    assert_eq!(replayed.clip_rect(), IRect::new(0, 0, 16, 16));
+    assert_eq!(replayed.into_image().pixels(), direct.into_image().pixels());
+}
+
+#[test]
+fn items_keep_blend_and_clip() {
+    let mut list = DrawList::new(16, 16);
+    scene(&mut list);
+    let line = list.items().iter().find(|it| matches!(it.cmd, DrawCmd::Line { .. })).unwrap();
+    assert_eq!(line.blend, BlendMode::Additive);
+    assert_eq!(line.clip, IRect::new(4, 4, 8, 8));
+    // fully clipped commands are not recorded
+    list.push_clip(IRect::new(100, 100, 4, 4));
+    rect(&mut list, 0, RED);
+    assert_eq!(list.len(), 5);
+}
+
+#[test]
+fn to_text_lists_one_command_per_line() {
+    let mut list = DrawList::new(320, 180);
+    list.fill_rect(8, 8, 16, 16, Color(255, 128, 0, 255));
+    list.with_z(2, |l| {
+        l.push_clip(IRect::new(0, 0, 100, 50));
+        l.with_blend_mode(BlendMode::Additive, |l| l.fill_circle(5, 6, 3, GREEN));
+        l.pop_clip();
+    });
+    list.fill_polygon(&[(0, 0), (4, 0), (2, 3)], BLUE);
+    list.draw_image(&Image::from_fn(2, 2, |_, _| RED), 1, 2, BlitParams { alpha: 128, ..BlitParams::default() });
+
+    let text = list.to_text();
+    let lines: Vec<_> = text.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], "z=0 Alpha clip=0,0,320,180 FillRect 8,8 16x16 #ff8000ff");
+    assert_eq!(lines[1], "z=2 Additive clip=0,0,100,50 FillCircle 5,6 r=3 #00ff00ff");
+    assert_eq!(lines[2], "z=0 Alpha clip=0,0,320,180 FillPolygon 0,0 4,0 2,3 #0000ffff");
+    assert!(lines[3].starts_with("z=0 Alpha clip=0,0,320,180 Image 2x2:"), "{}", lines[3]);
+    assert!(lines[3].ends_with(" at 1,2 alpha=128"), "{}", lines[3]);
+    assert!(text.ends_with('\n'));
+}
+
+#[test]
+fn diff_is_empty_for_the_same_frame() {
+    let (mut a, mut b) = (DrawList::new(16, 16), DrawList::new(16, 16));
+    scene(&mut a);
+    scene(&mut b);
+    assert!(a.diff(&b).is_empty());
+}
+
+#[test]
+fn diff_ignores_recording_order_after_sort() {
+    let (mut a, mut b) = (DrawList::new(16, 16), DrawList::new(16, 16));
+    a.with_z(1, |l| rect(l, 0, RED));
+    rect(&mut a, 1, GREEN);
+    rect(&mut b, 1, GREEN);
+    b.with_z(1, |l| rect(l, 0, RED));
+    a.sort();
+    b.sort();
+    assert_ne!(a.items()[0].seq, b.items()[0].seq);
+    assert!(a.diff(&b).is_empty(), "{:?}", a.diff(&b));
+}
+
+#[test]
+fn diff_aligns_insertions_and_removals() {
+    let mut a = DrawList::new(16, 16);
+    for x in 0..5 { rect(&mut a, x, RED); }
+    let mut b = DrawList::new(16, 16);
+    for x in 0..5 {
+        if x == 2 { rect(&mut b, 9, BLUE); }
+        rect(&mut b, x, RED);
+    }
+
+    // one inserted command, not a cascade of changes after it
+    assert_eq!(a.diff(&b), ["#2 + z=0 Alpha clip=0,0,16,16 FillRect 9,0 4x4 #0000ffff"]);
+    assert_eq!(b.diff(&a), ["#2 - z=0 Alpha clip=0,0,16,16 FillRect 9,0 4x4 #0000ffff"]);
+}
+
+#[test]
+fn diff_reports_changed_commands_as_remove_plus_add() {
+    let (mut a, mut b) = (DrawList::new(16, 16), DrawList::new(16, 16));
+    for x in 0..3 { rect(&mut a, x, RED); }
+    for x in 0..3 { rect(&mut b, x, if x == 1 { GREEN } else { RED }); }
+    rect(&mut b, 7, RED);
+    assert_eq!(a.diff(&b), [
+        "#1 - z=0 Alpha clip=0,0,16,16 FillRect 1,0 4x4 #ff0000ff",
+        "#1 + z=0 Alpha clip=0,0,16,16 FillRect 1,0 4x4 #00ff00ff",
+        "#3 + z=0 Alpha clip=0,0,16,16 FillRect 7,0 4x4 #ff0000ff",
+    ]);
+}
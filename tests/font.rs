@@ -0,0 +1,238 @@
+use daniengine::render::canvas::{Canvas, Color, IRect};
+use daniengine::render::codec::encode_png;
+use daniengine::render::image::Image;
+use daniengine::render::image_canvas::ImageCanvas;
+use daniengine::ui::font::{Font, Glyph};
+
+const BG: Color = Color(0, 0, 0, 255);
+
+/* ------------------------------ BMFont ------------------------------ */
+
+const FNT: &str = r#"info face="Tiny Font" size=8 bold=0 italic=0 charset="" unicode=1
+common lineHeight=8 base=7 scaleW=16 scaleH=8 pages=1 packed=0
+page id=0 file="tiny.png"
+chars count=5
+char id=65   x=0  y=0 width=4 height=6 xoffset=0 yoffset=1 xadvance=5 page=0 chnl=15
+char id=86   x=4  y=0 width=4 height=6 xoffset=0 yoffset=1 xadvance=5 page=0 chnl=15
+char id=105  x=8  y=0 width=1 height=6 xoffset=1 yoffset=1 xadvance=3 page=0 chnl=15
+char id=32   x=0  y=0 width=0 height=0 xoffset=0 yoffset=0 xadvance=3 page=0 chnl=15
+char id=63   x=9  y=0 width=3 height=6 xoffset=0 yoffset=1 xadvance=4 page=0 chnl=15
+kernings count=1
+kerning first=65 second=86 amount=-1
+"#;
+
+fn page() -> Image { Image::from_fn(16, 8, |_, _| Color::WHITE) }
+
+fn bmfont() -> Font { Font::from_bmfont(FNT, vec![page()]).unwrap() }
+
+#[test]
+fn bmfont_metrics_and_glyphs() {
+    let font = bmfont();
+    assert_eq!((font.line_height(), font.base()), (8, 7));
+    assert_eq!(*font.glyph('A'), Glyph { page: 0, src: IRect::new(0, 0, 4, 6), offset: (0, 1), advance: 5 });
+    assert_eq!(*font.glyph('i'), Glyph { page: 0, src: IRect::new(8, 0, 1, 6), offset: (1, 1), advance: 3 });
+    assert_eq!(font.glyph(' ').advance, 3);
+    assert!(font.has_glyph('V') && !font.has_glyph('a'));
+}
+
+#[test]
+fn bmfont_kerning_and_variable_advance() {
+    let font = bmfont();
+    assert_eq!(font.kerning('A', 'V'), -1);
+    assert_eq!(font.kerning('V', 'A'), 0);
+    assert_eq!(font.advance('A', Some('V')), 4);
+    assert_eq!(font.advance('A', Some('i')), 5);
+    assert_eq!(font.advance('i', None), 3);
+
+    // A ink 0..4, V kerned to 4..8; the advance after the last ink doesn't count
+    assert_eq!(font.measure_line("AV"), 8);
+    assert_eq!(font.measure_line("VA"), 9);
+    // i is 1px wide at offset 1 with a 3px advance: ink at 1, 4, 7
+    assert_eq!(font.measure_line("iii"), 8);
+    assert_eq!(font.measure_line(""), 0);
+}
+
+#[test]
+fn bmfont_draws_glyphs_at_their_offsets() {
+    let font = bmfont();
+    let mut c = ImageCanvas::new(16, 10);
+    c.clear(BG);
+    let red = Color(255, 0, 0, 255);
+    let pen = font.draw_line(&mut c, 1, 2, "AVi", red, 1);
+    assert_eq!(pen, 1 + 4 + 5 + 3);
+    // A at x 1..5, y 3..9 (yoffset 1); V kerned to x 5..9; i at 10 + 1
+    assert_eq!(c.pixel(1, 3), Some(red));
+    assert_eq!(c.pixel(1, 2), Some(BG));
+    assert_eq!(c.pixel(8, 8), Some(red));
+    assert_eq!(c.pixel(9, 5), Some(BG));
+    assert_eq!(c.pixel(11, 5), Some(red));
+    assert_eq!(c.pixel(10, 5), Some(BG));
+}
+
+#[test]
+fn bmfont_fallbacks() {
+    let font = bmfont();
+    // lowercase and accented letters fold to their base letter
+    assert_eq!(font.glyph('a'), font.glyph('A'));
+    assert_eq!(font.glyph('Á'), font.glyph('A'));
+    assert_eq!(font.glyph('v'), font.glyph('V'));
+    assert_eq!(font.glyph('¿'), font.glyph('?'));
+    assert_eq!(font.glyph('\u{a0}'), font.glyph(' '));
+    // anything else uses '?'
+    assert_eq!(font.glyph('Z'), font.glyph('?'));
+    assert_eq!(font.glyph('€'), font.glyph('?'));
+}
+
+#[test]
+fn bmfont_errors() {
+    let no_common: String = FNT.lines().filter(|l| !l.starts_with("common")).map(|l| format!("{l}\n")).collect();
+    assert!(Font::from_bmfont(&no_common, vec![page()]).is_err());
+    assert!(Font::from_bmfont("common lineHeight=8 base=7\n", vec![page()]).is_err());
+    // glyph outside its page, page not loaded, bad number
+    assert!(Font::from_bmfont(FNT, vec![Image::from_fn(8, 8, |_, _| Color::WHITE)]).is_err());
+    assert!(Font::from_bmfont(FNT, vec![]).is_err());
+    let bad = FNT.replace("xadvance=5 page=0 chnl=15\nchar id=86", "xadvance=five page=0 chnl=15\nchar id=86");
+    let err = Font::from_bmfont(&bad, vec![page()]).unwrap_err();
+    assert!(format!("{err:#}").contains("line 5: bad xadvance"), "{err:#}");
+}
+
+#[test]
+fn load_bmfont_reads_page_images_next_to_the_fnt() {
+    let dir = std::env::temp_dir().join(format!("daniengine-font-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let img = page();
+    std::fs::write(dir.join("tiny.png"), encode_png(16, 8, img.pixels()).unwrap()).unwrap();
+    std::fs::write(dir.join("tiny.fnt"), FNT).unwrap();
+    std::fs::write(dir.join("binary.fnt"), b"BMF\x03").unwrap();
+
+    let font = Font::load_bmfont(dir.join("tiny.fnt")).unwrap();
+    assert_eq!(font.measure_line("AV"), 8);
+    let err = Font::load_bmfont(dir.join("binary.fnt")).unwrap_err();
+    assert!(format!("{err:#}").contains("binary BMFont"), "{err:#}");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/* ------------------------------ PSF ------------------------------ */
+
+/// PSF1 with `height`-row 8px glyphs; `set` fills glyph rows, `table` is appended as is.
+fn psf1(mode: u8, height: u8, set: &[(usize, &[u8])], table: &[u16]) -> Vec<u8> {
+    let count = if mode & 1 != 0 { 512 } else { 256 };
+    let mut out = vec![0x36, 0x04, mode, height];
+    let mut data = vec![0u8; count * height as usize];
+    for &(g, rows) in set { data[g * height as usize..][..rows.len()].copy_from_slice(rows); }
+    out.extend(data);
+    for w in table { out.extend(w.to_le_bytes()); }
+    out
+}
+
+/// PSF2 with 5x3 glyphs (one byte per row).
+fn psf2(glyphs: &[[u8; 3]], table: Option<&[u8]>) -> Vec<u8> {
+    let mut out = vec![0x72, 0xb5, 0x4a, 0x86];
+    for v in [0, 32, table.is_some() as u32, glyphs.len() as u32, 3, 3, 5] { out.extend(v.to_le_bytes()); }
+    for g in glyphs { out.extend(g); }
+    out.extend(table.unwrap_or(&[]));
+    out
+}
+
+fn lit_row(font: &Font, ch: char, row: i32, width: i32) -> Vec<bool> {
+    let mut c = ImageCanvas::new(width as u32, font.line_height() as u32);
+    c.clear(BG);
+    font.draw_line(&mut c, 0, 0, &ch.to_string(), Color::WHITE, 1);
+    (0..width).map(|x| c.pixel(x, row) == Some(Color::WHITE)).collect()
+}
+
+#[test]
+fn psf1_without_table_maps_glyphs_by_code_point() {
+    let bytes = psf1(0, 4, &[(65, &[0b1000_0001, 0b0100_0000]), (63, &[0xFF])], &[]);
+    let font = Font::from_psf(&bytes).unwrap();
+    assert_eq!((font.line_height(), font.base()), (4, 4));
+    assert_eq!(*font.glyph('A'), Glyph { page: 0, src: IRect::new(8, 16, 8, 4), offset: (0, 0), advance: 8 });
+    assert!(font.has_glyph('\u{ff}') && !font.has_glyph('\u{100}'));
+    assert_eq!(font.glyph('\u{100}'), font.glyph('?'));
+    assert_eq!(lit_row(&font, 'A', 0, 8), [true, false, false, false, false, false, false, true]);
+    assert_eq!(lit_row(&font, 'A', 1, 8), [false, true, false, false, false, false, false, false]);
+    assert_eq!(font.measure_line("AAA"), 24);
+
+    let big = Font::from_psf(&psf1(1, 2, &[], &[])).unwrap();
+    assert!(big.has_glyph('\u{1ff}'));
+}
+
+#[test]
+fn psf1_unicode_table() {
+    // glyph 1: é and Ω plus a combining sequence (ignored); glyph 2: '?'
+    let mut table = vec![0xFFFF, 0x00E9, 0x03A9, 0xFFFE, 0x0041, 0x0301, 0xFFFF, 0x003F, 0xFFFF];
+    table.extend(std::iter::repeat_n(0xFFFF, 253));
+    let bytes = psf1(0x02, 2, &[(1, &[0xF0, 0x0F]), (2, &[0x18])], &table);
+    let font = Font::from_psf(&bytes).unwrap();
+    assert_eq!(font.glyph('é').src, IRect::new(8, 0, 8, 2));
+    assert_eq!(font.glyph('Ω'), font.glyph('é'));
+    assert!(!font.has_glyph('A') && !font.has_glyph('\u{1}'));
+    // unmapped characters use '?'
+    assert_eq!(font.glyph('A').src, IRect::new(16, 0, 8, 2));
+
+    // a table that ends early is an error
+    let short = psf1(0x02, 2, &[], &[0xFFFF, 0xFFFF]);
+    assert!(Font::from_psf(&short).is_err());
+}
+
+#[test]
+fn psf2_header_glyph_size_and_unicode_table() {
+    let glyphs = [[0xF8, 0x88, 0xF8], [0x80, 0, 0x08], [0x20, 0x20, 0x20], [0, 0, 0]];
+    let plain = Font::from_psf(&psf2(&glyphs, None)).unwrap();
+    assert_eq!(plain.line_height(), 3);
+    assert_eq!(*plain.glyph('\u{1}'), Glyph { page: 0, src: IRect::new(5, 0, 5, 3), offset: (0, 0), advance: 5 });
+    assert_eq!(lit_row(&plain, '\u{0}', 1, 5), [true, false, false, false, true]);
+    assert_eq!(lit_row(&plain, '\u{1}', 2, 5), [false, false, false, false, true]);
+
+    // glyph 0: '?', glyph 1: 'ü' plus a sequence, glyph 2: 'a' and 'b', glyph 3: nothing
+    let mut table = b"?\xFF".to_vec();
+    table.extend("ü".as_bytes());
+    table.extend(b"\xFEu\xCC\x88\xFF");
+    table.extend(b"ab\xFF\xFF");
+    let font = Font::from_psf(&psf2(&glyphs, Some(&table))).unwrap();
+    assert_eq!(font.glyph('ü').src, IRect::new(5, 0, 5, 3));
+    assert_eq!(font.glyph('a'), font.glyph('b'));
+    assert_eq!(font.glyph('b').src, IRect::new(10, 0, 5, 3));
+    assert!(!font.has_glyph('u') && !font.has_glyph('\u{3}'));
+    assert_eq!(font.glyph('u'), font.glyph('?'));
+    assert_eq!(lit_row(&font, 'a', 1, 5), [false, false, true, false, false]);
+}
+
+#[test]
+fn psf_errors() {
+    assert!(Font::from_psf(b"").is_err());
+    assert!(Font::from_psf(b"not a font").is_err());
+    assert!(Font::from_psf(&[0x36, 0x04, 0, 8, 0, 0]).is_err()); // truncated glyphs
+    assert!(Font::from_psf(&[0x36, 0x04, 0, 0]).is_err()); // zero height
+    let mut b = psf2(&[[0; 3]], None);
+    b[20] = 2; // bytes per glyph < 3 rows
+    assert!(Font::from_psf(&b).is_err());
+    let b = psf2(&[[0; 3]], None);
+    assert!(Font::from_psf(&b[..b.len() - 1]).is_err());
+    // one entry (plus the empty tail after its 0xFF) for three glyphs
+    assert!(Font::from_psf(&psf2(&[[0; 3]; 3], Some(b"?\xFF"))).is_err());
+}
+
+/* ------------------------------ built-in ------------------------------ */
+
+#[test]
+fn builtin_covers_printable_ascii() {
+    let font = Font::builtin();
+    let boxed = font.glyph('\u{1F600}');
+    for ch in "JQXZ".chars().chain('A'..='Z').chain('0'..='9') {
+        assert!(font.has_glyph(ch), "{ch:?}");
+    }
+    for ch in "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~ ".chars() {
+        assert!(font.has_glyph(ch), "{ch:?}");
+        assert_ne!(font.glyph(ch), boxed, "{ch:?}");
+    }
+    // lowercase folds to uppercase
+    for ch in 'a'..='z' {
+        assert!(!font.has_glyph(ch));
+        assert_eq!(font.glyph(ch), font.glyph(ch.to_ascii_uppercase()), "{ch:?}");
+    }
+    // letters that used to be missing are not the box
+    for ch in "JQXZ".chars() { assert_ne!(font.glyph(ch), boxed, "{ch:?}"); }
+    assert_eq!(font.glyph('é'), font.glyph('E'));
+    assert_eq!((font.line_height(), font.glyph('A').advance), (6, 4));
+}
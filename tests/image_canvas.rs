@@ -0,0 +1,58 @@
+use daniengine::render::canvas::{Canvas, Color, Framebuffer};
+use daniengine::render::image::Image;
+use daniengine::render::image_canvas::ImageCanvas;
+
+const RED: Color = Color(255, 0, 0, 255);
+const BLUE: Color = Color(0, 0, 255, 255);
+
+#[test]
+fn new_canvas_is_transparent_and_pixel_is_bounds_checked() {
+    let canvas = ImageCanvas::new(4, 3);
+    assert_eq!(canvas.size(), (4, 3));
+    assert_eq!(canvas.frame().len(), 4 * 3 * 4);
+    assert_eq!(canvas.pixel(0, 0), Some(Color(0, 0, 0, 0)));
+    assert_eq!(canvas.pixel(3, 2), Some(Color(0, 0, 0, 0)));
+    for (x, y) in [(-1, 0), (0, -1), (4, 0), (0, 3), (i32::MIN, i32::MAX)] {
+        assert_eq!(canvas.pixel(x, y), None, "({x}, {y})");
+    }
+}
+
+#[test]
+fn clear_fills_every_pixel() {
+    let mut canvas = ImageCanvas::new(3, 2);
+    canvas.clear(RED);
+    assert!(canvas.frame().chunks_exact(4).all(|px| px == [255, 0, 0, 255]));
+}
+
+#[test]
+fn fill_rect_is_clipped_to_the_canvas() {
+    let mut canvas = ImageCanvas::new(4, 4);
+    canvas.clear(BLUE);
+    canvas.fill_rect(-2, 2, 4, 10, RED);
+    canvas.fill_rect(10, 10, 5, 5, RED);
+    canvas.fill_rect(1, 1, 0, 3, RED);
+    canvas.fill_rect(1, 1, -3, 3, RED);
+
+    for y in 0..4 {
+        for x in 0..4 {
+            let expected = if x < 2 && y >= 2 { RED } else { BLUE };
+            assert_eq!(canvas.pixel(x, y), Some(expected), "({x}, {y})");
+        }
+    }
+}
+
+#[test]
+fn from_image_keeps_pixels_and_present_calls_back() {
+    let mut canvas = ImageCanvas::from_image(Image::from_fn(2, 2, |x, _| if x == 0 { RED } else { BLUE }));
+    assert_eq!(canvas.pixel(1, 1), Some(BLUE));
+
+    let seen = std::rc::Rc::new(std::cell::Cell::new((0, 0, 0)));
+    let s = seen.clone();
+    canvas.set_present_callback(move |frame, w, h| {
+        s.set((frame.len(), w, h));
+        Ok(())
+    });
+    canvas.present().unwrap();
+    assert_eq!(seen.get(), (16, 2, 2));
+    assert_eq!(canvas.into_image().pixel(0, 1), Some(RED));
+}
@@ -0,0 +1,43 @@
+use daniengine::camera::Camera2D;
+use daniengine::lighting::{Light, Lighting};
+use daniengine::math::Vec2;
+use daniengine::physics::Aabb;
+use daniengine::render::canvas::{Canvas, Color, IRect};
+use daniengine::render::image_canvas::ImageCanvas;
+
+const AMBIENT: Color = Color(0, 0, 0, 255);
+
+fn lit(lighting: &mut Lighting) -> ImageCanvas {
+    let mut canvas = ImageCanvas::new(64, 32);
+    canvas.clear(Color::WHITE);
+    lighting.apply(&mut canvas, &Camera2D::new(IRect::new(0, 0, 64, 32)));
+    canvas
+}
+
+fn brightness(lighting: &Lighting, x: i32, y: i32) -> f32 { lighting.light_at(x, y).unwrap()[0] }
+
+#[test]
+fn box_casts_shadow_behind_it() {
+    let mut lighting = Lighting::new(AMBIENT);
+    lighting.add_light(Light::point(Vec2::new(8.0, 16.0), 60.0, Color::WHITE).with_falloff(1.0));
+    lighting.add_aabb(Aabb { x: 24.0, y: 12.0, w: 8.0, h: 8.0 });
+    let canvas = lit(&mut lighting);
+
+    assert!(brightness(&lighting, 20, 16) > 0.5, "in front of the box");
+    assert!(brightness(&lighting, 28, 16) > 0.3, "box interior stays lit");
+    assert_eq!(brightness(&lighting, 40, 16), 0.0, "behind the box");
+    assert!(brightness(&lighting, 40, 2) > 0.0, "beside the shadow");
+    assert_eq!(canvas.pixel(40, 16), Some(AMBIENT));
+}
+
+#[test]
+fn light_inside_box_is_not_blocked() {
+    let mut lighting = Lighting::new(AMBIENT);
+    lighting.add_light(Light::point(Vec2::new(28.0, 16.0), 30.0, Color::WHITE));
+    lighting.add_aabb(Aabb { x: 24.0, y: 12.0, w: 8.0, h: 8.0 });
+    lit(&mut lighting);
+
+    for (x, y) in [(28, 16), (10, 16), (46, 16), (28, 4), (28, 28)] {
+        assert!(brightness(&lighting, x, y) > 0.0, "({x}, {y}) should be lit");
+    }
+}
@@ -0,0 +1,98 @@
+use daniengine::math::Vec2;
+use daniengine::render::canvas::{BlendMode, Canvas, CanvasFloatExt, Color, LineCap};
+use daniengine::render::image_canvas::ImageCanvas;
+
+const BG: Color = Color(0, 0, 0, 255);
+const INK: Color = Color(255, 255, 255, 255);
+
+fn canvas(w: u32, h: u32) -> ImageCanvas {
+    let mut c = ImageCanvas::new(w, h);
+    c.clear(BG);
+    c
+}
+
+fn painted(c: &ImageCanvas) -> Vec<(i32, i32)> {
+    let (w, h) = c.size();
+    (0..h as i32).flat_map(|y| (0..w as i32).map(move |x| (x, y))).filter(|&(x, y)| c.pixel(x, y) != Some(BG)).collect()
+}
+
+// red channel over a black background = coverage * 255
+fn level(c: &ImageCanvas, x: i32, y: i32) -> u8 { c.pixel(x, y).unwrap().0 }
+
+#[test]
+fn aa_line_on_pixel_centers_is_crisp() {
+    let mut c = canvas(10, 3);
+    c.draw_line_aa_f32(0.5, 1.5, 9.5, 1.5, INK);
+    assert!(painted(&c).iter().all(|&(_, y)| y == 1));
+    for x in 1..9 { assert_eq!(level(&c, x, 1), 255, "x = {x}"); }
+}
+
+#[test]
+fn aa_line_between_rows_splits_coverage() {
+    let mut c = canvas(10, 4);
+    c.draw_line_aa_f32(0.5, 2.0, 9.5, 2.0, INK);
+    for x in 1..9 {
+        let (a, b) = (level(&c, x, 1), level(&c, x, 2));
+        assert!((126..=129).contains(&a) && (126..=129).contains(&b), "x = {x}: {a} {b}");
+    }
+
+    // a shallow slope: coverage of each interior column sums to ~1
+    let mut c = canvas(20, 8);
+    c.draw_line_aa_f32(1.5, 1.5, 18.5, 6.5, INK);
+    for x in 3..17 {
+        let total: u32 = (0..8).map(|y| level(&c, x, y) as u32).sum();
+        assert!((250..=260).contains(&total), "x = {x}: {total}");
+    }
+}
+
+#[test]
+fn aa_line_in_replace_mode_blends_and_restores() {
+    let mut c = canvas(10, 4);
+    c.set_blend_mode(BlendMode::Replace);
+    c.draw_line_aa_f32(0.5, 2.0, 9.5, 2.0, INK);
+    assert_eq!(c.pixel(4, 1).unwrap().3, 255, "coverage blended, not written as alpha");
+    assert_eq!(c.blend_mode(), BlendMode::Replace);
+}
+
+#[test]
+fn thick_line_caps() {
+    let draw = |cap| {
+        let mut c = canvas(16, 10);
+        c.draw_thick_line_f32(Vec2::new(4.0, 5.0), Vec2::new(12.0, 5.0), 4.0, cap, INK);
+        c
+    };
+    let butt = painted(&draw(LineCap::Butt));
+    assert_eq!(butt.len(), 8 * 4);
+    assert!(butt.iter().all(|&(x, y)| (4..12).contains(&x) && (3..7).contains(&y)));
+
+    let square = painted(&draw(LineCap::Square));
+    assert_eq!(square.len(), 12 * 4);
+    assert!(square.iter().all(|&(x, y)| (2..14).contains(&x) && (3..7).contains(&y)));
+
+    let round = painted(&draw(LineCap::Round));
+    assert!(butt.iter().all(|p| round.contains(p)));
+    assert!(round.iter().all(|p| square.contains(p)));
+    assert!(round.len() > butt.len() && round.len() < square.len());
+    assert!(!round.contains(&(2, 3)) && round.contains(&(2, 5)));
+}
+
+#[test]
+fn zero_length_thick_line_depends_on_cap() {
+    let dot = |cap| {
+        let mut c = canvas(10, 10);
+        c.draw_thick_line_f32(Vec2::new(5.0, 5.0), Vec2::new(5.0, 5.0), 4.0, cap, INK);
+        painted(&c).len()
+    };
+    assert_eq!(dot(LineCap::Butt), 0);
+    assert_eq!(dot(LineCap::Square), 16);
+    assert!(dot(LineCap::Round) > 0 && dot(LineCap::Round) < 16);
+}
+
+#[test]
+fn translucent_thick_line_blends_once() {
+    let mut c = canvas(20, 20);
+    c.draw_thick_line_f32(Vec2::new(3.0, 3.0), Vec2::new(16.0, 14.0), 5.0, LineCap::Round, Color(255, 0, 0, 128));
+    let px = painted(&c);
+    assert!(!px.is_empty());
+    assert!(px.iter().all(|&(x, y)| c.pixel(x, y) == Some(Color(128, 0, 0, 255))));
+}
@@ -0,0 +1,15 @@
+use daniengine::render::canvas::Color;
+use daniengine::render::indexed::Palette;
+
+#[test]
+fn gpl_keeps_color_names_with_colons() {
+    let gpl = "GIMP Palette\nName: Test\nColumns: 2\n# comment\n0 0 0 Black\n255 0 0 Red: dark\n0 0 255\tBlue\n";
+    let p = Palette::from_gpl(gpl).unwrap();
+    assert_eq!(p.colors(), &[Color(0, 0, 0, 255), Color(255, 0, 0, 255), Color(0, 0, 255, 255)]);
+}
+
+#[test]
+fn gpl_short_line_reports_its_line_number() {
+    let err = Palette::from_gpl("GIMP Palette\n0 0 0\n255 0\n").unwrap_err();
+    assert!(format!("{err:#}").contains("line 3"), "{err:#}");
+}
@@ -0,0 +1,109 @@
+use daniengine::math::Vec2;
+use daniengine::parallax::{Parallax, ParallaxLayer};
+use daniengine::render::canvas::{Canvas, Color, IRect};
+use daniengine::render::image_canvas::ImageCanvas;
+
+// Never produced by the layer image (its blue channel is 7).
+const BG: Color = Color(0, 0, 99, 255);
+const W: i32 = 7;
+const H: i32 = 5;
+
+fn texel(x: u32, y: u32) -> Color { Color(x as u8 * 30, y as u8 * 40, 7, 255) }
+
+fn tiled(scroll: Vec2) -> ParallaxLayer { ParallaxLayer::procedural(W as u32, H as u32, scroll, texel) }
+
+const CAMERAS: [(f32, f32); 8] = [
+    (0.0, 0.0),
+    (-1.0, -1.0),
+    (-7.4, -3.6),
+    (-1000.3, -12345.0),
+    (3.0, 2.0),
+    (96.5, 40.25),
+    (12345.6, 999.9),
+    (1.0e6, -1.0e6),
+];
+
+/// Every pixel of the canvas clip shows the texel that the layer origin puts there.
+fn assert_covers(layer: &ParallaxLayer, canvas: &mut ImageCanvas, camera: Vec2) {
+    canvas.clear(BG);
+    layer.draw(canvas, camera);
+    let o = layer.origin(camera);
+    let (ox, oy) = (o.x.round() as i32, o.y.round() as i32);
+    let clip = canvas.clip_rect();
+    for y in clip.y..clip.y + clip.h {
+        for x in clip.x..clip.x + clip.w {
+            let want = texel((x - ox).rem_euclid(W) as u32, (y - oy).rem_euclid(H) as u32);
+            assert_eq!(canvas.pixel(x, y), Some(want), "camera {camera:?} at ({x}, {y})");
+        }
+    }
+}
+
+#[test]
+fn repeating_layer_covers_the_viewport_at_any_scroll() {
+    let mut canvas = ImageCanvas::new(20, 12);
+    for scroll in [Vec2::new(1.0, 1.0), Vec2::new(0.5, 0.25), Vec2::new(2.0, 3.0)] {
+        let layer = tiled(scroll);
+        for (cx, cy) in CAMERAS { assert_covers(&layer, &mut canvas, Vec2::new(cx, cy)); }
+    }
+}
+
+#[test]
+fn repeating_layer_covers_a_clipped_region() {
+    let mut canvas = ImageCanvas::new(20, 12);
+    canvas.clear(BG);
+    canvas.push_clip(IRect::new(5, 3, 9, 7));
+    let layer = tiled(Vec2::new(1.0, 1.0));
+    for (cx, cy) in CAMERAS { assert_covers(&layer, &mut canvas, Vec2::new(cx, cy)); }
+    // nothing spills outside the clip
+    assert_eq!(canvas.pixel(4, 3), Some(BG));
+    assert_eq!(canvas.pixel(14, 9), Some(BG));
+}
+
+#[test]
+fn drifting_layer_keeps_covering() {
+    let mut canvas = ImageCanvas::new(20, 12);
+    for velocity in [Vec2::new(13.0, -4.5), Vec2::new(-31.0, 17.0)] {
+        let mut layer = tiled(Vec2::new(0.5, 0.5));
+        layer.velocity = velocity;
+        layer.offset = Vec2::new(-3.0, 2.0);
+        for step in 0..50 {
+            layer.update(0.37);
+            let (cx, cy) = CAMERAS[step % CAMERAS.len()];
+            assert_covers(&layer, &mut canvas, Vec2::new(cx, cy));
+        }
+        // the drift is kept within one tile on repeating axes
+        let o = layer.origin(Vec2::default());
+        assert!((-3.0..-3.0 + W as f32).contains(&o.x) && (2.0..2.0 + H as f32).contains(&o.y), "{o:?}");
+    }
+}
+
+#[test]
+fn single_axis_repeat_draws_one_band() {
+    let mut canvas = ImageCanvas::new(20, 12);
+    canvas.clear(BG);
+    let mut layer = tiled(Vec2::new(1.0, 1.0));
+    layer.repeat_y = false;
+    layer.offset = Vec2::new(0.0, 4.0);
+    layer.draw(&mut canvas, Vec2::new(-555.0, 0.0));
+    for x in 0..20 {
+        assert_eq!(canvas.pixel(x, 3), Some(BG));
+        assert_eq!(canvas.pixel(x, 4), Some(texel((x - 555).rem_euclid(W) as u32, 0)));
+        assert_eq!(canvas.pixel(x, 8), Some(texel((x - 555).rem_euclid(W) as u32, 4)));
+        assert_eq!(canvas.pixel(x, 9), Some(BG));
+    }
+}
+
+#[test]
+fn layers_draw_back_to_front_and_skip_hidden_ones() {
+    let mut canvas = ImageCanvas::new(4, 4);
+    canvas.clear(BG);
+    let mut p = Parallax::new();
+    p.add(tiled(Vec2::new(0.0, 0.0)));
+    let front = p.add(ParallaxLayer::procedural(1, 1, Vec2::new(1.0, 1.0), |_, _| Color::WHITE));
+    p.draw(&mut canvas, Vec2::new(-50.0, 80.0));
+    assert_eq!(canvas.pixel(3, 3), Some(Color::WHITE));
+
+    p.layers_mut()[front].visible = false;
+    p.draw(&mut canvas, Vec2::new(-50.0, 80.0));
+    assert_eq!(canvas.pixel(3, 3), Some(texel(3, 3)));
+}
@@ -0,0 +1,97 @@
+use daniengine::render::canvas::{Canvas, Color};
+use daniengine::render::image_canvas::ImageCanvas;
+use daniengine::render::postfx::{Blur, BlurKind, Effect, PostChain};
+
+// Two effects that don't commute, to see the order they ran in.
+struct Add(u8);
+struct Double;
+
+impl Effect for Add {
+    fn name(&self) -> &'static str { "add" }
+    fn apply(&mut self, frame: &mut [u8], _: u32, _: u32) { frame[0] = frame[0].wrapping_add(self.0); }
+}
+
+impl Effect for Double {
+    fn name(&self) -> &'static str { "double" }
+    fn apply(&mut self, frame: &mut [u8], _: u32, _: u32) { frame[0] = frame[0].wrapping_mul(2); }
+}
+
+fn run(chain: &mut PostChain) -> u8 {
+    let mut c = ImageCanvas::new(1, 1);
+    c.clear(Color(1, 0, 0, 255));
+    chain.apply(&mut c);
+    c.pixel(0, 0).unwrap().0
+}
+
+#[test]
+fn effects_run_in_insertion_order() {
+    let mut chain = PostChain::new();
+    chain.push(Add(10)).push(Double);
+    assert_eq!(run(&mut chain), 22);
+
+    let mut chain = PostChain::new();
+    chain.push(Double).push(Add(10));
+    assert_eq!(run(&mut chain), 12);
+}
+
+#[test]
+fn enable_disable_and_toggle() {
+    let mut chain = PostChain::new();
+    chain.push(Add(10)).push(Double);
+    assert!(chain.is_enabled("add") && chain.is_enabled("double"));
+
+    assert!(chain.set_enabled("double", false));
+    assert!(!chain.is_enabled("double"));
+    assert_eq!(run(&mut chain), 11);
+
+    assert_eq!(chain.toggle("double"), Some(true));
+    assert_eq!(run(&mut chain), 22);
+    assert_eq!(chain.toggle("add"), Some(false));
+    assert_eq!(run(&mut chain), 2);
+
+    assert!(!chain.set_enabled("missing", true));
+    assert_eq!(chain.toggle("missing"), None);
+    assert!(!chain.is_enabled("missing"));
+}
+
+#[test]
+fn get_mut_reaches_effect_parameters() {
+    let mut chain = PostChain::new();
+    chain.push(Add(10));
+    chain.get_mut::<Add>().unwrap().0 = 3;
+    assert!(chain.get_mut::<Double>().is_none());
+    assert_eq!(run(&mut chain), 4);
+}
+
+#[test]
+fn blur_keeps_a_flat_color() {
+    let flat = Color(37, 200, 90, 180);
+    for kind in [BlurKind::Box, BlurKind::Gaussian] {
+        for radius in [1, 3, 20] {
+            let mut c = ImageCanvas::new(13, 7);
+            c.clear(flat);
+            let mut chain = PostChain::new();
+            chain.push(Blur::new(kind, radius));
+            chain.apply(&mut c);
+            for y in 0..7 {
+                for x in 0..13 {
+                    assert_eq!(c.pixel(x, y), Some(flat), "{kind:?} r={radius} at {x},{y}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn blur_spreads_a_dot() {
+    let mut c = ImageCanvas::new(9, 9);
+    c.clear(Color(0, 0, 0, 255));
+    c.fill_rect(4, 4, 1, 1, Color(255, 255, 255, 255));
+    let mut chain = PostChain::new();
+    chain.push(Blur::new(BlurKind::Box, 1));
+    chain.apply(&mut c);
+    // 3x3 box: 255 / 9 everywhere in the neighborhood, nothing beyond it
+    assert_eq!(c.pixel(4, 4), Some(Color(28, 28, 28, 255)));
+    assert_eq!(c.pixel(3, 5), Some(Color(28, 28, 28, 255)));
+    assert_eq!(c.pixel(2, 4), Some(Color(0, 0, 0, 255)));
+}
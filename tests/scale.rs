@@ -0,0 +1,48 @@
+use daniengine::render::canvas::IRect;
+use daniengine::render::scale::{ScaleMode, buffer_size, canvas_rect};
+
+const CANVAS: (u32, u32) = (320, 180);
+
+#[test]
+fn exact_multiples_fill_the_window() {
+    for mode in [ScaleMode::Integer, ScaleMode::Fractional] {
+        assert_eq!(canvas_rect((320, 180), CANVAS, mode), IRect::new(0, 0, 320, 180));
+        assert_eq!(canvas_rect((960, 540), CANVAS, mode), IRect::new(0, 0, 960, 540));
+    }
+}
+
+#[test]
+fn integer_uses_the_largest_whole_scale_and_centers() {
+    // 3.125x3.33 fits; 3x leaves 40x60 of border
+    assert_eq!(buffer_size((1000, 600), CANVAS, ScaleMode::Integer), CANVAS);
+    assert_eq!(canvas_rect((1000, 600), CANVAS, ScaleMode::Integer), IRect::new(20, 30, 960, 540));
+    // odd leftovers put the extra pixel on the right/bottom
+    assert_eq!(canvas_rect((641, 361), CANVAS, ScaleMode::Integer), IRect::new(0, 0, 640, 360));
+    assert_eq!(canvas_rect((643, 363), CANVAS, ScaleMode::Integer), IRect::new(1, 1, 640, 360));
+}
+
+#[test]
+fn letterbox_and_pillarbox_keep_the_aspect_ratio() {
+    // wide window: bars left and right
+    assert_eq!(canvas_rect((2000, 600), CANVAS, ScaleMode::Integer), IRect::new(520, 30, 960, 540));
+    assert_eq!(canvas_rect((2000, 600), CANVAS, ScaleMode::Fractional), IRect::new(466, 0, 1067, 600));
+    // tall window: bars top and bottom
+    assert_eq!(canvas_rect((640, 1000), CANVAS, ScaleMode::Integer), IRect::new(0, 320, 640, 360));
+    assert_eq!(canvas_rect((700, 1000), CANVAS, ScaleMode::Fractional), IRect::new(0, 303, 700, 394));
+}
+
+#[test]
+fn fractional_stretches_to_the_limiting_axis() {
+    assert_eq!(buffer_size((1000, 600), CANVAS, ScaleMode::Fractional), (1000, 563));
+    assert_eq!(canvas_rect((1000, 600), CANVAS, ScaleMode::Fractional), IRect::new(0, 18, 1000, 563));
+}
+
+#[test]
+fn windows_smaller_than_the_canvas_show_it_at_1x_cropped() {
+    for mode in [ScaleMode::Integer, ScaleMode::Fractional] {
+        assert_eq!(buffer_size((100, 50), CANVAS, mode), CANVAS);
+        assert_eq!(canvas_rect((100, 50), CANVAS, mode), IRect::new(-110, -65, 320, 180));
+        // smaller on one axis only
+        assert_eq!(canvas_rect((400, 100), CANVAS, mode), IRect::new(40, -40, 320, 180));
+    }
+}
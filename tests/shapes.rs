@@ -0,0 +1,149 @@
+use daniengine::math::Vec2;
+use daniengine::render::canvas::{Canvas, CanvasFloatExt, Color, IRect};
+use daniengine::render::image_canvas::ImageCanvas;
+
+const INK: Color = Color(255, 255, 255, 255);
+
+fn canvas(w: u32, h: u32) -> ImageCanvas {
+    let mut c = ImageCanvas::new(w, h);
+    c.clear(Color(0, 0, 0, 255));
+    c
+}
+
+fn lit(canvas: &ImageCanvas) -> Vec<(i32, i32)> {
+    let (w, h) = canvas.size();
+    let mut out = Vec::new();
+    for y in 0..h as i32 {
+        for x in 0..w as i32 {
+            if canvas.pixel(x, y) == Some(INK) { out.push((x, y)); }
+        }
+    }
+    out
+}
+
+fn is_lit(canvas: &ImageCanvas, x: i32, y: i32) -> bool { canvas.pixel(x, y) == Some(INK) }
+
+#[test]
+fn polygon_square_matches_fill_rect() {
+    let mut a = canvas(8, 8);
+    a.fill_polygon(&[(2, 1), (6, 1), (6, 5), (2, 5)], INK);
+    let mut b = canvas(8, 8);
+    b.fill_rect(2, 1, 4, 4, INK);
+    assert_eq!(lit(&a), lit(&b));
+}
+
+#[test]
+fn concave_polygon_leaves_its_notch_empty() {
+    // U shape: 6 wide, 6 tall, 2px notch from the top down to y = 4
+    let mut c = canvas(6, 6);
+    c.fill_polygon(&[(0, 0), (2, 0), (2, 4), (4, 4), (4, 0), (6, 0), (6, 6), (0, 6)], INK);
+    assert_eq!(lit(&c).len(), 36 - 8);
+    assert!(!is_lit(&c, 2, 0) && !is_lit(&c, 3, 3));
+    assert!(is_lit(&c, 1, 0) && is_lit(&c, 4, 0) && is_lit(&c, 3, 4));
+}
+
+#[test]
+fn even_odd_rule_cuts_holes() {
+    // outer 8x8 square and inner 4x4 square in one path (the bridge edge is traversed twice)
+    let mut c = canvas(8, 8);
+    c.fill_polygon(&[(0, 0), (8, 0), (8, 8), (0, 8), (0, 0), (2, 2), (2, 6), (6, 6), (6, 2), (2, 2)], INK);
+    assert_eq!(lit(&c).len(), 64 - 16);
+    assert!(!is_lit(&c, 2, 2) && !is_lit(&c, 5, 5));
+    assert!(is_lit(&c, 1, 1) && is_lit(&c, 6, 6));
+
+    // a pentagram's center pentagon is inside two edges, so it stays empty
+    let star: Vec<(i32, i32)> = (0..5)
+        .map(|i| {
+            let a = (i * 2) as f32 * std::f32::consts::TAU / 5.0 - std::f32::consts::FRAC_PI_2;
+            ((20.0 + 18.0 * a.cos()).round() as i32, (20.0 + 18.0 * a.sin()).round() as i32)
+        })
+        .collect();
+    let mut c = canvas(40, 40);
+    c.fill_polygon(&star, INK);
+    assert!(!is_lit(&c, 20, 20));
+    assert!(is_lit(&c, 20, 6), "top point");
+}
+
+#[test]
+fn triangle_covers_pixel_centers_inside() {
+    let mut c = canvas(6, 6);
+    c.fill_triangle((0, 0), (4, 0), (0, 4), INK);
+    // centers (x + .5) + (y + .5) < 4
+    let expected: Vec<(i32, i32)> = (0..4).flat_map(|y| (0..4).map(move |x| (x, y))).filter(|&(x, y)| x + y < 3).collect();
+    let mut got = lit(&c);
+    got.sort_by_key(|&(x, y)| (y, x));
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn polygon_respects_clip() {
+    let mut c = canvas(8, 8);
+    c.with_clip(IRect::new(2, 2, 3, 3), |c| c.fill_polygon(&[(0, 0), (8, 0), (8, 8), (0, 8)], INK));
+    assert_eq!(lit(&c).len(), 9);
+    assert!(is_lit(&c, 2, 2) && is_lit(&c, 4, 4) && !is_lit(&c, 5, 5));
+}
+
+#[test]
+fn fill_circle_contains_its_outline() {
+    for r in [0, 1, 3, 6] {
+        let mut outline = canvas(16, 16);
+        outline.draw_circle(8, 8, r, INK);
+        let mut disc = canvas(16, 16);
+        disc.fill_circle(8, 8, r, INK);
+        let filled = lit(&disc);
+        assert!(lit(&outline).iter().all(|p| filled.contains(p)), "r = {r}");
+        // symmetric about the center
+        assert!(filled.iter().all(|&(x, y)| filled.contains(&(16 - x, y)) && filled.contains(&(x, 16 - y))), "r = {r}");
+        assert!(filled.iter().all(|&(x, y)| (x - 8).abs() <= r && (y - 8).abs() <= r));
+    }
+    let mut dot = canvas(3, 3);
+    dot.fill_circle(1, 1, 0, INK);
+    assert_eq!(lit(&dot), [(1, 1)]);
+}
+
+#[test]
+fn ellipses_stay_inside_their_radii() {
+    let mut c = canvas(21, 11);
+    c.fill_ellipse(10, 5, 8, 3, INK);
+    let filled = lit(&c);
+    assert!(filled.iter().all(|&(x, y)| (x - 10).abs() <= 8 && (y - 5).abs() <= 3));
+    assert!(filled.contains(&(2, 5)) && filled.contains(&(18, 5)) && filled.contains(&(10, 2)) && filled.contains(&(10, 8)));
+    assert!(!filled.contains(&(2, 2)));
+
+    let mut outline = canvas(21, 11);
+    outline.draw_ellipse(10, 5, 8, 3, INK);
+    assert!(lit(&outline).iter().all(|p| filled.contains(p)));
+    assert!(!is_lit(&outline, 10, 5));
+
+    let mut flat = canvas(9, 3);
+    flat.fill_ellipse(4, 1, 3, 0, INK);
+    assert_eq!(lit(&flat), [(1, 1), (2, 1), (3, 1), (4, 1), (5, 1), (6, 1), (7, 1)]);
+}
+
+#[test]
+fn rounded_rect_trims_only_the_corners() {
+    let mut c = canvas(12, 8);
+    c.fill_rounded_rect(1, 1, 10, 6, 3, INK);
+    for p in [(1, 1), (10, 1), (1, 6), (10, 6)] { assert!(!is_lit(&c, p.0, p.1), "corner {p:?}"); }
+    for p in [(5, 1), (1, 4), (10, 4), (5, 6), (5, 4)] { assert!(is_lit(&c, p.0, p.1), "edge {p:?}"); }
+    assert!(lit(&c).iter().all(|&(x, y)| (1..11).contains(&x) && (1..7).contains(&y)));
+
+    // radius 0 is a plain rect; huge radii are clamped to half the short side
+    let mut square = canvas(12, 8);
+    square.fill_rounded_rect(1, 1, 10, 6, 0, INK);
+    assert_eq!(lit(&square).len(), 60);
+    let mut pill = canvas(12, 8);
+    pill.fill_rounded_rect(1, 1, 10, 6, 100, INK);
+    let mut clamped = canvas(12, 8);
+    clamped.fill_rounded_rect(1, 1, 10, 6, 3, INK);
+    assert_eq!(lit(&pill), lit(&clamped));
+}
+
+#[test]
+fn f32_polygon_matches_integer_version() {
+    let mut a = canvas(10, 10);
+    a.fill_polygon(&[(1, 1), (9, 2), (4, 9)], INK);
+    let mut b = canvas(10, 10);
+    b.fill_polygon_f32(&[Vec2::new(1.0, 1.0), Vec2::new(9.0, 2.0), Vec2::new(4.0, 9.0)], INK);
+    assert_eq!(lit(&a), lit(&b));
+}
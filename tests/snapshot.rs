@@ -0,0 +1,109 @@
+use std::path::PathBuf;
+
+use daniengine::particles::{EmitterConfig, ParticleSystem};
+use daniengine::render::canvas::{BlendMode, Canvas, Color};
+use daniengine::render::image_canvas::ImageCanvas;
+use daniengine::render::snapshot::{UPDATE_ENV, assert_snapshot, check_snapshot, compare_rgba};
+use daniengine::render::target::RenderTarget;
+use daniengine::ui::font::Font;
+
+fn snapshot(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots").join(name)
+}
+
+#[test]
+fn blend_modes_and_layer_compositing() {
+    let mut canvas = ImageCanvas::new(40, 20);
+    canvas.clear(Color(100, 100, 100, 255));
+    canvas.fill_rect(0, 10, 40, 10, Color(20, 40, 200, 255));
+
+    // one column per mode, straddling both backgrounds
+    let modes = [BlendMode::Replace, BlendMode::Alpha, BlendMode::Additive, BlendMode::Multiply, BlendMode::Screen];
+    for (i, mode) in modes.into_iter().enumerate() {
+        canvas.with_blend_mode(mode, |c| c.fill_rect(i as i32 * 8, 4, 6, 12, Color(255, 128, 0, 128)));
+    }
+
+    // half-res translucent layer scaled 2x onto the bottom rows
+    let mut layer = RenderTarget::scaled(40, 4, 2.0);
+    layer.fill_rect(0, 0, 10, 1, Color(0, 255, 0, 200));
+    layer.opacity = 128;
+    layer.offset = (0, 16);
+    layer.composite_onto(&mut canvas);
+
+    assert_snapshot(&canvas, snapshot("blend_modes.png"), 0);
+}
+
+#[test]
+fn missing_reference_fails() {
+    if std::env::var(UPDATE_ENV).is_ok() { return; }
+    let canvas = ImageCanvas::new(4, 4);
+    let path = std::env::temp_dir().join("daniengine-snapshot-test/does_not_exist.png");
+    let err = check_snapshot(&canvas, &path, 0).unwrap_err();
+    assert!(format!("{err:#}").contains("DANIENGINE_UPDATE_SNAPSHOTS"), "{err:#}");
+    assert!(!path.exists());
+}
+
+#[test]
+fn circles_and_lines() {
+    let mut canvas = ImageCanvas::new(32, 24);
+    canvas.clear(Color(0, 0, 0, 255));
+    canvas.draw_circle(8, 8, 6, Color(255, 255, 255, 255));
+    canvas.fill_circle(22, 8, 5, Color(255, 128, 0, 255));
+    canvas.draw_circle(8, 8, 0, Color(0, 255, 0, 255));
+    canvas.draw_line(0, 23, 31, 16, Color(0, 200, 255, 255));
+    canvas.draw_line(16, 0, 16, 23, Color(255, 0, 255, 255));
+    canvas.draw_line(30, 22, 20, 17, Color(255, 255, 0, 255));
+
+    assert_snapshot(&canvas, snapshot("circles_and_lines.png"), 0);
+}
+
+#[test]
+fn builtin_font() {
+    let mut canvas = ImageCanvas::new(96, 22);
+    canvas.clear(Color(0, 0, 0, 255));
+    let font = Font::builtin();
+    font.draw_line(&mut canvas, 1, 1, "Hello, World! 0123", Color::WHITE, 1);
+    font.draw_line(&mut canvas, 1, 8, "JQXZ jqxz {}[]?@#", Color(255, 200, 0, 255), 1);
+    font.draw_line(&mut canvas, 1, 15, "x2", Color(0, 255, 128, 255), 2);
+
+    assert_snapshot(&canvas, snapshot("builtin_font.png"), 0);
+}
+
+#[test]
+fn particles() {
+    let mut ps = ParticleSystem::new(64);
+    ps.set_gravity(0.0, 40.0);
+    let config = EmitterConfig {
+        count: 24,
+        speed_min: 10.0,
+        speed_max: 30.0,
+        spread_radians: std::f32::consts::PI,
+        base_direction: 0.0,
+        life_min: 1.0,
+        life_max: 2.0,
+        size_min: 1.0,
+        size_max: 3.0,
+        start_color: Color(255, 220, 80, 255),
+        end_color: Color(255, 0, 0, 0),
+    };
+    ps.emit_burst([16.0, 12.0], config);
+    for _ in 0..30 { ps.update(1.0 / 60.0); }
+
+    let mut canvas = ImageCanvas::new(32, 24);
+    canvas.clear(Color(0, 0, 0, 255));
+    ps.draw_additive(&mut canvas);
+
+    assert_snapshot(&canvas, snapshot("particles.png"), 0);
+}
+
+#[test]
+fn compare_rgba_counts_size_mismatch() {
+    let a = [10u8; 16];
+    assert_eq!(compare_rgba(&a, &a, 0).mismatched, 0);
+
+    let short = compare_rgba(&a, &a[..8], 255);
+    assert_eq!((short.mismatched, short.max_delta), (2, 255));
+    let long = compare_rgba(&a[..8], &a, 255);
+    assert_eq!((long.mismatched, long.max_delta), (2, 255));
+    assert_eq!(long.diff.len(), 16);
+}
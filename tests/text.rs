@@ -0,0 +1,142 @@
+use daniengine::render::canvas::{Canvas, Color};
+use daniengine::render::image_canvas::ImageCanvas;
+use daniengine::ui::font::Font;
+use daniengine::ui::text::{Align, TextLayout, TextRun, TextStyle, draw_text, measure_text};
+
+// Built-in font: 3px glyphs, 4px advance, 6px lines.
+const RED: Color = Color(255, 0, 0, 255);
+const GREEN: Color = Color(0, 255, 0, 255);
+const W: Color = Color::WHITE;
+
+fn layout(text: &str, style: TextStyle) -> TextLayout { TextLayout::new(Font::builtin(), text, &style) }
+
+fn wrapped(max: i32) -> TextStyle { TextStyle { max_width: Some(max), ..TextStyle::default() } }
+
+// Text of each line that has runs, top to bottom.
+fn lines(l: &TextLayout) -> Vec<String> {
+    let mut out: Vec<(i32, String)> = Vec::new();
+    for r in l.runs() {
+        match out.last_mut() {
+            Some((y, text)) if *y == r.y => text.push_str(&r.text),
+            _ => out.push((r.y, r.text.clone())),
+        }
+    }
+    out.into_iter().map(|(_, t)| t).collect()
+}
+
+fn run(x: i32, y: i32, text: &str, color: Color) -> TextRun { TextRun { x, y, text: text.into(), color } }
+
+#[test]
+fn trailing_whitespace_is_not_ink() {
+    let font = Font::builtin();
+    assert_eq!(font.measure_line("AB"), 7);
+    assert_eq!(font.measure_line("AB "), 7);
+    assert_eq!(font.measure_line("AB  \t"), 7);
+    assert_eq!(font.measure_line("  A"), 11);
+    assert_eq!(font.measure_line("   "), 0);
+    assert_eq!(layout("AB  ", TextStyle::default()).size(), (7, 6));
+}
+
+#[test]
+fn wraps_at_word_boundaries() {
+    let l = layout("HELLO WORLD FOO", wrapped(40));
+    assert_eq!(lines(&l), ["HELLO", "WORLD FOO"]);
+    assert_eq!(l.line_widths(), [19, 35]);
+    assert_eq!(l.size(), (40, 12));
+
+    // a line may end exactly at the limit
+    assert_eq!(lines(&layout("HELLO WORLD", wrapped(43))), ["HELLO WORLD"]);
+    assert_eq!(lines(&layout("HELLO WORLD", wrapped(42))), ["HELLO", "WORLD"]);
+}
+
+#[test]
+fn long_words_break_between_characters() {
+    assert_eq!(lines(&layout("ABCDEFGHIJ", wrapped(15))), ["ABCD", "EFGH", "IJ"]);
+    assert_eq!(lines(&layout("AB ABCDEFGHIJ", wrapped(15))), ["AB", "ABCD", "EFGH", "IJ"]);
+    // one character per line at worst
+    assert_eq!(lines(&layout("ABC", wrapped(1))), ["A", "B", "C"]);
+}
+
+#[test]
+fn spaces_at_a_break_are_dropped() {
+    assert_eq!(lines(&layout("AB   CD", wrapped(10))), ["AB", "CD"]);
+    assert_eq!(lines(&layout("AB CD   ", wrapped(10))), ["AB", "CD"]);
+}
+
+#[test]
+fn newlines_always_break() {
+    assert_eq!(lines(&layout("A\nB\r\nC", TextStyle::default())), ["A", "B", "C"]);
+    let l = layout("A\n\nB", TextStyle { line_spacing: 2, ..TextStyle::default() });
+    assert_eq!(l.line_count(), 3);
+    assert_eq!(l.runs().last().unwrap().y, 16);
+    assert_eq!(l.size(), (3, 22));
+    assert_eq!(layout("", TextStyle::default()).size(), (0, 6));
+}
+
+#[test]
+fn alignment_offsets() {
+    let style = |align| TextStyle { align, max_width: Some(40), ..TextStyle::default() };
+    assert_eq!(layout("AB", style(Align::Left)).runs()[0].x, 0);
+    assert_eq!(layout("AB", style(Align::Center)).runs()[0].x, 16);
+    assert_eq!(layout("AB", style(Align::Right)).runs()[0].x, 33);
+    // trailing spaces don't push right-aligned text left
+    assert_eq!(layout("AB  ", style(Align::Right)).runs()[0].x, 33);
+
+    // without a wrap width lines align inside the widest one
+    let l = layout("ABC\nA", TextStyle { align: Align::Center, ..TextStyle::default() });
+    assert_eq!(l.runs(), [run(0, 0, "ABC", W), run(4, 6, "A", W)]);
+    let l = layout("ABC\nA", TextStyle { align: Align::Right, ..TextStyle::default() });
+    assert_eq!(l.runs()[1].x, 8);
+}
+
+#[test]
+fn scale_multiplies_positions_and_sizes() {
+    let l = layout("A{#ff0000}B", TextStyle { scale: 2, ..TextStyle::default() });
+    assert_eq!(l.runs(), [run(0, 0, "A", W), run(8, 0, "B", RED)]);
+    assert_eq!(l.size(), (14, 12));
+    // the wrap width is in scaled pixels
+    assert_eq!(lines(&layout("AB CD", TextStyle { scale: 2, max_width: Some(20), ..TextStyle::default() })), ["AB", "CD"]);
+}
+
+#[test]
+fn color_spans_nest_and_close() {
+    let l = layout("A{#ff0000}B{/}C", TextStyle::default());
+    assert_eq!(l.runs(), [run(0, 0, "A", W), run(4, 0, "B", RED), run(8, 0, "C", W)]);
+
+    let l = layout("{#ff0000}A{#00ff00}B{/}C{/}D", TextStyle::default());
+    assert_eq!(l.runs(), [run(0, 0, "A", RED), run(4, 0, "B", GREEN), run(8, 0, "C", RED), run(12, 0, "D", W)]);
+
+    let l = layout("{#11223344}A B", TextStyle::default());
+    assert_eq!(l.runs(), [run(0, 0, "A B", Color(0x11, 0x22, 0x33, 0x44))]);
+
+    // spans carry across wrapped lines
+    let l = layout("{#ff0000}AB CD{/}E", wrapped(12));
+    assert_eq!(l.runs(), [run(0, 0, "AB", RED), run(0, 6, "CD", RED), run(8, 6, "E", W)]);
+}
+
+#[test]
+fn escaped_and_malformed_markup_is_literal() {
+    let text = |s: &str| layout(s, TextStyle::default()).runs().iter().map(|r| r.text.clone()).collect::<String>();
+    assert_eq!(text("{{#ff0000}"), "{#ff0000}");
+    assert_eq!(text("A{{B"), "A{B");
+    assert_eq!(text("{#12}"), "{#12}");
+    assert_eq!(text("{#zzzzzz}A"), "{#zzzzzz}A");
+    assert_eq!(text("{#ff0000"), "{#ff0000");
+    assert_eq!(text("{red}A}"), "{red}A}");
+    // a stray close keeps the base color
+    let l = layout("{/}A{/}", TextStyle::default());
+    assert_eq!(l.runs(), [run(0, 0, "A", W)]);
+}
+
+#[test]
+fn draw_text_uses_span_colors() {
+    let mut c = ImageCanvas::new(16, 8);
+    c.clear(Color(0, 0, 0, 255));
+    let size = draw_text(&mut c, Font::builtin(), 1, 1, "I{#ff0000}I", &TextStyle::default());
+    assert_eq!(size, measure_text(Font::builtin(), "I{#ff0000}I", &TextStyle::default()));
+    assert_eq!(size, (7, 6));
+    // 'I' has a full top row
+    assert_eq!(c.pixel(1, 1), Some(W));
+    assert_eq!(c.pixel(5, 1), Some(RED));
+    assert_eq!(c.pixel(4, 1), Some(Color(0, 0, 0, 255)));
+}
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use daniengine::math::Vec2;
+use daniengine::render::canvas::Color;
+use daniengine::tilemap::tiled::{PropertyValue, Shape, TiledMap, TiledObject};
+use daniengine::tilemap::{Tile, TileFlags};
+
+fn object(shape: Shape) -> TiledObject {
+    TiledObject {
+        id: 1,
+        name: String::new(),
+        class: String::new(),
+        layer: String::new(),
+        pos: Vec2::new(10.0, 20.0),
+        rotation: 30.0,
+        shape,
+        tile: None,
+        visible: true,
+        properties: HashMap::new(),
+    }
+}
+
+#[test]
+fn empty_polygon_aabb_is_a_point() {
+    for shape in [Shape::Polygon(Vec::new()), Shape::Polyline(Vec::new())] {
+        let b = object(shape).aabb();
+        assert_eq!((b.x, b.y, b.w, b.h), (10.0, 20.0, 0.0, 0.0));
+    }
+}
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tiled").join(name)
+}
+
+fn expected_tiles() -> Vec<Tile> {
+    vec![
+        Tile::new(1),
+        Tile::new(2).with_flags(TileFlags::FLIP_X),
+        Tile::new(3).with_flags(TileFlags::FLIP_Y),
+        Tile::new(4).with_flags(TileFlags::FLIP_DIAG),
+        Tile::EMPTY,
+        Tile::new(1).with_flags(TileFlags::all()),
+        Tile::new(2),
+        Tile::new(3),
+    ]
+}
+
+fn check_common(map: &TiledMap) {
+    let tm = &map.tilemap;
+    assert_eq!((tm.width, tm.height, tm.tile_w, tm.tile_h), (4, 2, 8, 8));
+    let set = &tm.tilesets()[0];
+    assert_eq!((set.first_gid, set.tile_count, set.columns), (1, 4, 4));
+    assert_eq!(set.image.pixel(9, 0), Some(Color(0, 255, 0, 255)));
+
+    // group offsets add up, opacity multiplies, hidden groups hide their children
+    let nested = tm.layer("nested").unwrap();
+    assert_eq!(nested.offset, (5, 3));
+    assert_eq!(nested.opacity, 64);
+    assert!(!nested.visible);
+    assert_eq!(nested.tiles(), &expected_tiles()[..]);
+
+    let spawn = map.objects.iter().find(|o| o.name == "spawn").unwrap();
+    assert_eq!((spawn.class.as_str(), spawn.layer.as_str(), &spawn.shape), ("player", "things", &Shape::Point));
+    assert_eq!(spawn.pos, Vec2::new(14.0, 22.0));
+    let walls = map.bodies("solid");
+    assert_eq!(walls.len(), 1);
+    assert_eq!((walls[0].pos, walls[0].size), (Vec2::new(4.0, 2.0), Vec2::new(16.0, 8.0)));
+
+    let p = &map.properties;
+    assert_eq!(p["dark"], PropertyValue::Bool(true));
+    assert_eq!(p["level"], PropertyValue::Int(3));
+    assert_eq!(p["gravity"], PropertyValue::Float(9.5));
+    assert_eq!(p["title"].as_str(), Some("Cave: entrance"));
+    assert_eq!(p["fog"].as_color(), Some(Color(255, 32, 0, 128)));
+    assert_eq!(p["sky"].as_color(), Some(Color(0, 0, 255, 255)));
+}
+
+#[test]
+fn tmx_encodings_decode_to_the_same_gids() {
+    let map = TiledMap::load(fixture("map.tmx")).unwrap();
+    for name in ["csv", "base64", "zlib", "gzip"] {
+        assert_eq!(map.tilemap.layer(name).unwrap().tiles(), &expected_tiles()[..], "layer {name}");
+    }
+    check_common(&map);
+}
+
+#[test]
+fn tmj_matches_tmx() {
+    let map = TiledMap::load(fixture("map.tmj")).unwrap();
+    for name in ["array", "zlib"] {
+        assert_eq!(map.tilemap.layer(name).unwrap().tiles(), &expected_tiles()[..], "layer {name}");
+    }
+    check_common(&map);
+}
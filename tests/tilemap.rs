@@ -0,0 +1,142 @@
+use daniengine::math::Vec2;
+use daniengine::render::canvas::{Canvas, Color};
+use daniengine::render::image::Image;
+use daniengine::render::image_canvas::ImageCanvas;
+use daniengine::tilemap::{Tile, TileFlags, TileFrame, Tilemap, Tileset};
+
+const RED: Color = Color(255, 0, 0, 255);
+const BLACK: Color = Color(0, 0, 0, 255);
+
+#[test]
+fn oversized_tile_straddling_left_clip_edge_is_drawn() {
+    // 32px tiles on a 16px grid; cell 0 spans x = -16..16 once scrolled by 16
+    let mut map = Tilemap::new(4, 2, 16, 16);
+    map.add_tileset(Tileset::new("big", Image::from_fn(32, 32, |_, _| RED), 32, 32));
+    map.add_layer("ground");
+    map.layers_mut()[0].set(0, 1, Tile::new(1));
+
+    let mut canvas = ImageCanvas::new(32, 32);
+    canvas.clear(BLACK);
+    map.draw(&mut canvas, Vec2::new(16.0, 0.0));
+
+    assert_eq!(canvas.pixel(0, 0), Some(RED));
+    assert_eq!(canvas.pixel(15, 31), Some(RED));
+    assert_eq!(canvas.pixel(16, 0), Some(BLACK));
+}
+
+/* ------------------------------ flips ------------------------------ */
+
+// One 3x3 tile where every pixel is unique.
+fn source(x: u32, y: u32) -> Color { Color(x as u8 * 80, y as u8 * 80, 7, 255) }
+
+/// Draws the tile with `flags` at (1, 1) and reads back which source pixel landed where.
+fn flipped(flags: TileFlags) -> [[(u8, u8); 3]; 3] {
+    let mut map = Tilemap::new(1, 1, 3, 3);
+    map.add_tileset(Tileset::new("t", Image::from_fn(3, 3, source), 3, 3));
+    map.add_layer("ground");
+    map.layers_mut()[0].set(0, 0, Tile::new(1).with_flags(flags));
+    let mut canvas = ImageCanvas::new(5, 5);
+    canvas.clear(BLACK);
+    map.draw(&mut canvas, Vec2::new(-1.0, -1.0));
+
+    assert_eq!(canvas.pixel(0, 0), Some(BLACK));
+    assert_eq!(canvas.pixel(4, 4), Some(BLACK));
+    let mut out = [[(0, 0); 3]; 3];
+    for (y, row) in out.iter_mut().enumerate() {
+        for (x, px) in row.iter_mut().enumerate() {
+            let Color(r, g, _, _) = canvas.pixel(x as i32 + 1, y as i32 + 1).unwrap();
+            *px = (r / 80, g / 80);
+        }
+    }
+    out
+}
+
+/// Tiled's rule: transpose first, then mirror horizontally and/or vertically.
+fn expected(flags: TileFlags) -> [[(u8, u8); 3]; 3] {
+    let mut out = [[(0, 0); 3]; 3];
+    for (y, row) in out.iter_mut().enumerate() {
+        for (x, px) in row.iter_mut().enumerate() {
+            let x = if flags.contains(TileFlags::FLIP_X) { 2 - x } else { x };
+            let y = if flags.contains(TileFlags::FLIP_Y) { 2 - y } else { y };
+            let (sx, sy) = if flags.contains(TileFlags::FLIP_DIAG) { (y, x) } else { (x, y) };
+            *px = (sx as u8, sy as u8);
+        }
+    }
+    out
+}
+
+#[test]
+fn flips_mirror_and_transpose() {
+    assert_eq!(flipped(TileFlags::empty())[0], [(0, 0), (1, 0), (2, 0)]);
+    assert_eq!(flipped(TileFlags::FLIP_X)[0], [(2, 0), (1, 0), (0, 0)]);
+    assert_eq!(flipped(TileFlags::FLIP_Y)[0], [(0, 2), (1, 2), (2, 2)]);
+    assert_eq!(flipped(TileFlags::FLIP_DIAG)[0], [(0, 0), (0, 1), (0, 2)]);
+    // diagonal + horizontal is a quarter turn clockwise
+    assert_eq!(flipped(TileFlags::FLIP_DIAG | TileFlags::FLIP_X)[0], [(0, 2), (0, 1), (0, 0)]);
+}
+
+#[test]
+fn every_flip_combination_matches_tiled() {
+    for bits in 0..8 {
+        let flags = TileFlags::from_bits_truncate(bits);
+        assert_eq!(flipped(flags), expected(flags), "{flags:?}");
+    }
+}
+
+/* ------------------------------ animated tiles ------------------------------ */
+
+fn frames(list: &[(u32, f32)]) -> Vec<TileFrame> { list.iter().map(|&(tile, duration)| TileFrame { tile, duration }).collect() }
+
+fn animated_set() -> Tileset {
+    // four 1x1 tiles: red, green, blue, white
+    let colors = [RED, Color(0, 255, 0, 255), Color(0, 0, 255, 255), Color::WHITE];
+    let mut set = Tileset::new("anim", Image::from_fn(4, 1, |x, _| colors[x as usize]), 1, 1);
+    set.set_animation(0, frames(&[(1, 0.5), (2, 0.25), (3, 0.25)]));
+    set
+}
+
+#[test]
+fn animated_tiles_cycle_through_their_frames() {
+    let set = animated_set();
+    let at = |t: f32| set.animated(0, t);
+    assert_eq!([at(0.0), at(0.49), at(0.5), at(0.75), at(0.99), at(1.0)], [1, 1, 2, 3, 3, 1]);
+    // time wraps both ways
+    assert_eq!([at(10.6), at(-0.1), at(-0.3), at(-1.0)], [2, 3, 2, 1]);
+    // tiles without an animation show themselves
+    assert_eq!(set.animated(3, 5.0), 3);
+
+    let mut set = set;
+    set.set_animation(0, Vec::new());
+    assert!(set.animation(0).is_none());
+    assert_eq!(set.animated(0, 0.6), 0);
+}
+
+#[test]
+fn degenerate_frame_durations_are_clamped() {
+    let mut set = animated_set();
+    // zero and negative frames still take a moment each, so the cycle doesn't stick
+    set.set_animation(0, frames(&[(1, 0.0), (2, -1.0), (3, 0.5)]));
+    assert_eq!([set.animated(0, 0.0), set.animated(0, 0.0015), set.animated(0, 0.1)], [1, 2, 3]);
+    // every frame degenerate: the total stays positive
+    set.set_animation(0, frames(&[(1, 0.0), (2, -5.0)]));
+    assert_eq!([set.animated(0, 0.0), set.animated(0, 0.0015), set.animated(0, 0.0025)], [1, 2, 1]);
+    for t in [0.0, 0.3, -7.0, 1e6] {
+        assert!([1, 2].contains(&set.animated(0, t)), "{t}");
+    }
+}
+
+#[test]
+fn map_update_drives_animated_tiles() {
+    let mut map = Tilemap::new(1, 1, 1, 1);
+    map.add_tileset(animated_set());
+    map.add_layer("ground");
+    map.layers_mut()[0].set(0, 0, Tile::new(1));
+    let mut seen = Vec::new();
+    for _ in 0..4 {
+        let mut canvas = ImageCanvas::new(1, 1);
+        map.draw(&mut canvas, Vec2::default());
+        seen.push(canvas.pixel(0, 0).unwrap());
+        map.update(0.25);
+    }
+    assert_eq!(seen, [Color(0, 255, 0, 255), Color(0, 255, 0, 255), Color(0, 0, 255, 255), Color::WHITE]);
+}
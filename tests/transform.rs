@@ -0,0 +1,87 @@
+use std::f32::consts::FRAC_PI_2;
+
+use daniengine::math::Vec2;
+use daniengine::render::canvas::{BlitParams, Canvas, Color, ImageTransform, Sampling};
+use daniengine::render::image::Image;
+use daniengine::render::image_canvas::ImageCanvas;
+
+const A: Color = Color(255, 0, 0, 255);
+const B: Color = Color(0, 255, 0, 255);
+const C: Color = Color(0, 0, 255, 255);
+const D: Color = Color(255, 255, 0, 255);
+const BG: Color = Color(0, 0, 0, 255);
+
+// A B
+// C D
+fn quad() -> Image { Image::from_fn(2, 2, |x, y| [[A, B], [C, D]][y as usize][x as usize]) }
+
+fn rows(canvas: &ImageCanvas) -> Vec<Vec<Color>> {
+    let (w, h) = canvas.size();
+    (0..h as i32).map(|y| (0..w as i32).map(|x| canvas.pixel(x, y).unwrap()).collect()).collect()
+}
+
+fn draw(w: u32, h: u32, image: &Image, xf: ImageTransform) -> ImageCanvas {
+    let mut canvas = ImageCanvas::new(w, h);
+    canvas.clear(BG);
+    canvas.draw_image_transformed(image, image.bounds(), xf, BlitParams::default());
+    canvas
+}
+
+#[test]
+fn identity_matches_a_plain_blit() {
+    let img = quad();
+    let mut plain = ImageCanvas::new(4, 4);
+    plain.clear(BG);
+    plain.draw_image(&img, 1, 1, BlitParams::default());
+    assert_eq!(rows(&draw(4, 4, &img, ImageTransform::at(1.0, 1.0))), rows(&plain));
+}
+
+#[test]
+fn quarter_turn_is_clockwise_around_the_pivot() {
+    let xf = ImageTransform { rotation: FRAC_PI_2, ..ImageTransform::centered(1.0, 1.0, 2, 2) };
+    assert_eq!(rows(&draw(2, 2, &quad(), xf)), vec![vec![C, A], vec![D, B]]);
+}
+
+#[test]
+fn nearest_scaling_repeats_texels() {
+    let xf = ImageTransform { scale: Vec2::new(2.0, 1.5), ..ImageTransform::at(0.0, 0.0) };
+    let canvas = draw(4, 3, &quad(), xf);
+    // pixel centers at y = 0.5, 1.5, 2.5 map to source rows 0.33, 1.0, 1.67
+    assert_eq!(rows(&canvas), vec![vec![A, A, B, B], vec![C, C, D, D], vec![C, C, D, D]]);
+}
+
+#[test]
+fn bilinear_blends_where_nearest_does_not() {
+    let img = Image::from_fn(2, 1, |x, _| if x == 0 { BG } else { Color::WHITE });
+    let scaled = |sampling| ImageTransform { scale: Vec2::new(4.0, 1.0), sampling, ..ImageTransform::at(0.0, 0.0) };
+
+    let nearest: Vec<u8> = rows(&draw(8, 1, &img, scaled(Sampling::Nearest)))[0].iter().map(|c| c.0).collect();
+    assert_eq!(nearest, [0, 0, 0, 0, 255, 255, 255, 255]);
+
+    let smooth: Vec<u8> = rows(&draw(8, 1, &img, scaled(Sampling::Bilinear)))[0].iter().map(|c| c.0).collect();
+    assert!(smooth.windows(2).all(|w| w[0] <= w[1]), "{smooth:?}");
+    assert!(smooth.iter().any(|&v| v > 0 && v < 255), "{smooth:?}");
+    assert_eq!((smooth[0], smooth[7]), (0, 255));
+}
+
+#[test]
+fn clipped_against_canvas_bounds() {
+    // 2x scaled quad hanging off the top-left corner: only the D quarter lands on screen
+    let xf = ImageTransform { scale: Vec2::new(2.0, 2.0), ..ImageTransform::at(-2.0, -2.0) };
+    let canvas = draw(3, 3, &quad(), xf);
+    assert_eq!(rows(&canvas), vec![vec![D, D, BG], vec![D, D, BG], vec![BG, BG, BG]]);
+
+    // entirely off-screen or degenerate: nothing drawn, no panic
+    for xf in [ImageTransform::at(100.0, -50.0), ImageTransform { scale: Vec2::new(0.0, 1.0), ..ImageTransform::default() }] {
+        assert!(rows(&draw(3, 3, &quad(), xf)).iter().flatten().all(|&c| c == BG));
+    }
+}
+
+#[test]
+fn flips_apply_before_the_transform() {
+    let mut canvas = ImageCanvas::new(2, 2);
+    let params = BlitParams { flip_x: true, ..BlitParams::default() };
+    let img = quad();
+    canvas.draw_image_transformed(&img, img.bounds(), ImageTransform::at(0.0, 0.0), params);
+    assert_eq!(rows(&canvas), vec![vec![B, A], vec![D, C]]);
+}
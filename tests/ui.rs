@@ -0,0 +1,181 @@
+use daniengine::input::{Input, MouseButton};
+use daniengine::math::Vec2;
+use daniengine::render::canvas::{BlitParams, Canvas, Color, IRect, NineSlice, SliceFill};
+use daniengine::render::image::Image;
+use daniengine::render::image_canvas::ImageCanvas;
+use daniengine::ui::{Rect, Skin, Ui, WidgetState};
+use winit::event::{DeviceId, ElementState, WindowEvent};
+
+const BG: Color = Color(0, 0, 0, 255);
+
+/* ------------------------------ skin states ------------------------------ */
+
+const STATE_COLORS: [Color; 4] = [
+    Color(200, 0, 0, 255),   // normal
+    Color(0, 200, 0, 255),   // hot
+    Color(0, 0, 200, 255),   // active
+    Color(90, 90, 90, 255),  // disabled
+];
+
+// Four solid 4x4 cells, one per state.
+fn skin() -> Skin {
+    let image = Image::from_fn(16, 4, |x, _| STATE_COLORS[x as usize / 4]);
+    Skin::from_strip(image, IRect::new(0, 0, 4, 4), 1)
+}
+
+fn mouse(input: &mut Input, state: ElementState) {
+    // SAFETY: the dummy id is only compared, never dereferenced
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    input.handle_window_event(&WindowEvent::MouseInput { device_id, state, button: MouseButton::Left, modifiers: Default::default() });
+}
+
+const BUTTON: Rect = Rect { x: 2.0, y: 2.0, w: 24.0, h: 10.0 };
+
+/// Draws one button for the current input; returns (clicked, color at its corner).
+fn frame(ui: &mut Ui, input: &Input, enabled: bool) -> (bool, Color) {
+    let mut c = ImageCanvas::new(32, 16);
+    c.clear(BG);
+    ui.begin();
+    ui.set_enabled(enabled);
+    let clicked = ui.button(input, &mut c, BUTTON, "OK");
+    (clicked, c.pixel(2, 2).unwrap())
+}
+
+fn ui() -> Ui {
+    let mut ui = Ui::new();
+    ui.set_button_skin(Some(skin()));
+    ui
+}
+
+#[test]
+fn skin_state_follows_the_mouse() {
+    let mut ui = ui();
+    let mut input = Input::new();
+    input.set_mouse_pos(Vec2::new(30.0, 14.0));
+    assert_eq!(frame(&mut ui, &input, true), (false, STATE_COLORS[0]));
+
+    input.set_mouse_pos(Vec2::new(10.0, 6.0));
+    assert_eq!(frame(&mut ui, &input, true), (false, STATE_COLORS[1]));
+
+    input.begin_frame();
+    mouse(&mut input, ElementState::Pressed);
+    assert_eq!(frame(&mut ui, &input, true), (false, STATE_COLORS[2]));
+
+    input.begin_frame();
+    mouse(&mut input, ElementState::Released);
+    assert_eq!(frame(&mut ui, &input, true), (true, STATE_COLORS[1]));
+
+    input.begin_frame();
+    assert_eq!(frame(&mut ui, &input, true), (false, STATE_COLORS[1]));
+}
+
+#[test]
+fn disabled_buttons_ignore_the_mouse() {
+    let mut ui = ui();
+    let mut input = Input::new();
+    input.set_mouse_pos(Vec2::new(10.0, 6.0));
+    mouse(&mut input, ElementState::Pressed);
+    assert_eq!(frame(&mut ui, &input, false), (false, STATE_COLORS[3]));
+    input.begin_frame();
+    mouse(&mut input, ElementState::Released);
+    assert_eq!(frame(&mut ui, &input, false), (false, STATE_COLORS[3]));
+}
+
+#[test]
+fn release_outside_is_not_a_click() {
+    let mut ui = ui();
+    let mut input = Input::new();
+    input.set_mouse_pos(Vec2::new(10.0, 6.0));
+    mouse(&mut input, ElementState::Pressed);
+    frame(&mut ui, &input, true);
+    input.begin_frame();
+    input.set_mouse_pos(Vec2::new(30.0, 14.0));
+    mouse(&mut input, ElementState::Released);
+    assert_eq!(frame(&mut ui, &input, true), (false, STATE_COLORS[0]));
+}
+
+#[test]
+fn skin_text_color_depends_on_state() {
+    let s = skin();
+    assert_eq!(s.text(WidgetState::Normal), s.text_color);
+    assert_eq!(s.text(WidgetState::Active), s.text_color);
+    assert_eq!(s.text(WidgetState::Disabled), s.disabled_text_color);
+    assert_eq!(s.slice(WidgetState::Hot).src, IRect::new(4, 0, 4, 4));
+}
+
+/* ------------------------------ nine-slice ------------------------------ */
+
+// Every source pixel is unique, so each destination pixel tells where it was sampled.
+fn source() -> Image { Image::from_fn(6, 6, |x, y| Color(x as u8 * 40, y as u8 * 40, 7, 255)) }
+
+fn sampled(c: &ImageCanvas, x: i32, y: i32) -> Option<(i32, i32)> {
+    let Color(r, g, b, _) = c.pixel(x, y)?;
+    (b == 7).then_some((r as i32 / 40, g as i32 / 40))
+}
+
+fn nine(slice: NineSlice, w: i32, h: i32) -> ImageCanvas {
+    let mut c = ImageCanvas::new(w as u32 + 2, h as u32 + 2);
+    c.clear(BG);
+    c.draw_nine_slice(&source(), &slice, IRect::new(1, 1, w, h), BlitParams::default());
+    c
+}
+
+/// Source column/row for each destination column/row along one axis.
+fn axis(c: &ImageCanvas, len: i32, horizontal: bool) -> Vec<i32> {
+    (0..len)
+        .map(|i| {
+            let (x, y) = if horizontal { (1 + i, 1) } else { (1, 1 + i) };
+            let (sx, sy) = sampled(c, x, y).unwrap();
+            if horizontal { sx } else { sy }
+        })
+        .collect()
+}
+
+#[test]
+fn stretch_keeps_corners_and_scales_the_middle() {
+    let c = nine(NineSlice::uniform(IRect::new(0, 0, 6, 6), 2), 10, 8);
+    // 2px corners, the 2px middle scaled to 6 (x) and 4 (y), nearest
+    assert_eq!(axis(&c, 10, true), [0, 1, 2, 2, 2, 3, 3, 3, 4, 5]);
+    assert_eq!(axis(&c, 8, false), [0, 1, 2, 2, 3, 3, 4, 5]);
+    // nothing outside dst
+    assert_eq!(c.pixel(0, 0), Some(BG));
+    assert_eq!(c.pixel(11, 9), Some(BG));
+}
+
+#[test]
+fn tile_repeats_the_middle_and_crops_the_last_copy() {
+    let slice = NineSlice::uniform(IRect::new(0, 0, 6, 6), 2).with_fill(SliceFill::Tile, SliceFill::Tile);
+    let c = nine(slice, 9, 7);
+    assert_eq!(axis(&c, 9, true), [0, 1, 2, 3, 2, 3, 2, 4, 5]);
+    assert_eq!(axis(&c, 7, false), [0, 1, 2, 3, 2, 4, 5]);
+    // center cell tiles on both axes
+    assert_eq!(sampled(&c, 1 + 4, 1 + 4), Some((2, 2)));
+    assert_eq!(sampled(&c, 1 + 3, 1 + 3), Some((3, 3)));
+}
+
+#[test]
+fn smaller_than_the_corners_crops_them_proportionally() {
+    let c = nine(NineSlice::new(IRect::new(0, 0, 6, 6), 2, 2, 2, 2), 3, 3);
+    // 4px of corners in 3px: 1px of the left/top corner, 2px of the right/bottom one
+    assert_eq!(axis(&c, 3, true), [0, 4, 5]);
+    assert_eq!(axis(&c, 3, false), [0, 4, 5]);
+    assert_eq!(sampled(&c, 3, 3), Some((5, 5)));
+    assert_eq!(c.pixel(4, 4), Some(BG));
+
+    // uneven borders split by their ratio
+    let c = nine(NineSlice::new(IRect::new(0, 0, 6, 6), 3, 1, 1, 1), 2, 6);
+    assert_eq!(axis(&c, 2, true), [0, 5]);
+}
+
+#[test]
+fn hollow_and_empty_destinations() {
+    let mut slice = NineSlice::uniform(IRect::new(0, 0, 6, 6), 2);
+    slice.hollow = true;
+    let c = nine(slice, 8, 8);
+    assert_eq!(c.pixel(4, 4), Some(BG));
+    assert_eq!(sampled(&c, 1, 4), Some((0, 2)));
+
+    let c = nine(NineSlice::uniform(IRect::new(0, 0, 6, 6), 2), 0, 0);
+    assert_eq!(c.pixel(1, 1), Some(BG));
+}